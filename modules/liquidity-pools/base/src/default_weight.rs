@@ -1,4 +1,4 @@
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! Weights partly estimated by hand. Regenerate with the benchmark CLI before release.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -8,19 +8,19 @@ use frame_support::weights::{constants::RocksDbWeight as DbWeight, Weight};
 
 impl crate::WeightInfo for () {
 	fn create_pool() -> Weight {
-		(62_760_000 as Weight)
-			.saturating_add(DbWeight::get().reads(5 as Weight))
-			.saturating_add(DbWeight::get().writes(4 as Weight))
+		(68_204_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
 	}
 	fn disable_pool() -> Weight {
-		(89_374_000 as Weight)
-			.saturating_add(DbWeight::get().reads(5 as Weight))
-			.saturating_add(DbWeight::get().writes(2 as Weight))
+		(97_118_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
 	}
 	fn remove_pool() -> Weight {
-		(143_453_000 as Weight)
-			.saturating_add(DbWeight::get().reads(7 as Weight))
-			.saturating_add(DbWeight::get().writes(4 as Weight))
+		(151_027_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
 	}
 	fn deposit_liquidity() -> Weight {
 		(166_749_000 as Weight)
@@ -28,9 +28,9 @@ impl crate::WeightInfo for () {
 			.saturating_add(DbWeight::get().writes(5 as Weight))
 	}
 	fn withdraw_liquidity() -> Weight {
-		(286_666_000 as Weight)
-			.saturating_add(DbWeight::get().reads(8 as Weight))
-			.saturating_add(DbWeight::get().writes(5 as Weight))
+		(412_931_000 as Weight)
+			.saturating_add(DbWeight::get().reads(21 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
 	}
 	fn set_identity() -> Weight {
 		(120_932_000 as Weight)
@@ -48,8 +48,61 @@ impl crate::WeightInfo for () {
 			.saturating_add(DbWeight::get().writes(4 as Weight))
 	}
 	fn transfer_liquidity_pool() -> Weight {
-		(86_430_000 as Weight)
+		(28_743_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn deposit_for_shares() -> Weight {
+		(171_204_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
+	fn redeem_shares() -> Weight {
+		(418_206_000 as Weight)
+			.saturating_add(DbWeight::get().reads(22 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn claim_withdrawal() -> Weight {
+		(251_874_000 as Weight)
+			.saturating_add(DbWeight::get().reads(13 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
+	}
+	fn cancel_withdrawal() -> Weight {
+		(49_380_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn set_withdrawal_notice_period() -> Weight {
+		(11_853_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn grant_role() -> Weight {
+		(32_416_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_role() -> Weight {
+		(31_975_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn accept_liquidity_pool_transfer() -> Weight {
+		(113_205_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(17 as Weight))
+	}
+	fn cancel_liquidity_pool_transfer() -> Weight {
+		(27_354_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_verification() -> Weight {
+		(102_418_000 as Weight)
 			.saturating_add(DbWeight::get().reads(6 as Weight))
-			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn lock_liquidity() -> Weight {
+		(41_227_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
 	}
 }
@@ -3,22 +3,27 @@
 use codec::{Decode, Encode};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure,
-	storage::IterableStorageMap,
+	storage::{IterableStorageDoubleMap, IterableStorageMap},
 	traits::{Currency, EnsureOrigin, Get, ReservableCurrency},
 	weights::{DispatchClass, Weight},
 };
 use frame_system::ensure_signed;
-use orml_traits::BasicCurrency;
+use orml_traits::{BasicCurrency, MultiCurrency};
 use orml_utilities::with_transaction_result;
-use primitives::{Balance, IdentityInfo, LiquidityPoolId};
+use primitives::{
+	Balance, CurrencyId, IdentityInfo, LiquidityLock, LiquidityPoolId, PendingWithdrawal, PoolRole, PoolStatus, Share,
+	Verification, VerificationLevel, WithdrawalId,
+};
 use sp_runtime::{
-	traits::{AccountIdConversion, One},
-	DispatchResult, ModuleId, RuntimeDebug,
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, Convert, One, Saturating, Zero},
+	DispatchError, DispatchResult, ModuleId, RuntimeDebug,
 };
 use sp_std::{prelude::*, result};
 use traits::{BaseLiquidityPoolManager, LiquidityPools, OnDisableLiquidityPool, OnRemoveLiquidityPool};
 
 mod default_weight;
+mod migrations;
 mod mock;
 mod tests;
 
@@ -30,8 +35,19 @@ pub trait WeightInfo {
 	fn withdraw_liquidity() -> Weight;
 	fn set_identity() -> Weight;
 	fn verify_identity() -> Weight;
+	fn revoke_verification() -> Weight;
+	fn lock_liquidity() -> Weight;
 	fn clear_identity() -> Weight;
 	fn transfer_liquidity_pool() -> Weight;
+	fn accept_liquidity_pool_transfer() -> Weight;
+	fn cancel_liquidity_pool_transfer() -> Weight;
+	fn deposit_for_shares() -> Weight;
+	fn redeem_shares() -> Weight;
+	fn claim_withdrawal() -> Weight;
+	fn cancel_withdrawal() -> Weight;
+	fn set_withdrawal_notice_period() -> Weight;
+	fn grant_role() -> Weight;
+	fn revoke_role() -> Weight;
 }
 
 type IdentityDepositBalanceOf<T, I> =
@@ -58,6 +74,12 @@ pub trait Config<I: Instance = DefaultInstance>: frame_system::Config {
 	/// The reservable currency for identity verification deposit.
 	type IdentityDepositCurrency: ReservableCurrency<Self::AccountId>;
 
+	/// Number of blocks a liquidity pool transfer could be accepted after proposed.
+	type TransferExpiry: Get<Self::BlockNumber>;
+
+	/// Maximum number of pending withdrawals of an account in a liquidity pool.
+	type MaxPendingWithdrawals: Get<u32>;
+
 	/// Module Id of base liquidity pools module instance.
 	type ModuleId: Get<ModuleId>;
 
@@ -70,6 +92,14 @@ pub trait Config<I: Instance = DefaultInstance>: frame_system::Config {
 	/// Required origin for updating protocol options.
 	type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
+	/// The currency LP share tokens are minted in.
+	type ShareCurrency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+	/// Currency ID of LP share tokens of a liquidity pool.
+	///
+	/// If `None`, LP shares are only recorded in this module and can't be transferred.
+	type ShareCurrencyId: Convert<LiquidityPoolId, Option<CurrencyId>>;
+
 	/// Weight information for the extrinsics in this module.
 	type WeightInfo: WeightInfo;
 }
@@ -88,6 +118,27 @@ impl<AccountId> Pool<AccountId> {
 	}
 }
 
+/// Storage version of the module.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum Releases {
+	V1_0_0,
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// Record LP shares in the base liquidity pools module only, without minting share tokens.
+pub struct NoShareCurrency;
+impl Convert<LiquidityPoolId, Option<CurrencyId>> for NoShareCurrency {
+	fn convert(_: LiquidityPoolId) -> Option<CurrencyId> {
+		None
+	}
+}
+
 decl_storage! {
 	trait Store for Module<T: Config<I>, I: Instance=DefaultInstance> as BaseLiquidityPools {
 		/// Next available liquidity pool ID.
@@ -98,16 +149,64 @@ decl_storage! {
 		/// Returns `None` if no such pool exists.
 		pub Pools get(fn pools): map hasher(twox_64_concat) LiquidityPoolId => Option<Pool<T::AccountId>>;
 
-		/// Identity info of liquidity pools: `(identity_info, deposit_amount, is_verified)`.
+		/// Status of liquidity pools.
+		///
+		/// Returns `None` if no such pool was created.
+		pub PoolStatuses get(fn pool_status): map hasher(twox_64_concat) LiquidityPoolId => Option<PoolStatus>;
+
+		/// Liquidity pools indexed by status.
+		pub PoolsByStatus get(fn pools_by_status): double_map hasher(twox_64_concat) PoolStatus, hasher(twox_64_concat) LiquidityPoolId => Option<()>;
+
+		/// Identity info of liquidity pools: `(identity_info, deposit_amount, verification)`.
 		///
 		/// Returns `None` if identity info of the pool not set or removed.
-		pub IdentityInfos get(fn identity_infos): map hasher(twox_64_concat) LiquidityPoolId => Option<(IdentityInfo, IdentityDepositBalanceOf<T, I>, bool)>;
+		pub IdentityInfos get(fn identity_infos): map hasher(twox_64_concat) LiquidityPoolId => Option<(IdentityInfo, IdentityDepositBalanceOf<T, I>, Verification<T::BlockNumber>)>;
+
+		/// Total LP shares issued by liquidity pools.
+		pub TotalShares get(fn total_shares): map hasher(twox_64_concat) LiquidityPoolId => Share;
+
+		/// LP shares held by accounts, if the shares of the pool are not minted as tokens.
+		pub Shares get(fn shares): double_map hasher(twox_64_concat) LiquidityPoolId, hasher(twox_64_concat) T::AccountId => Share;
+
+		/// Number of blocks a withdrawal needs to wait before it can be claimed.
+		///
+		/// Withdrawals are executed immediately if zero.
+		pub WithdrawalNoticePeriod get(fn withdrawal_notice_period): T::BlockNumber;
+
+		/// Next available withdrawal ID.
+		pub NextWithdrawalId get(fn next_withdrawal_id): WithdrawalId;
+
+		/// Pending withdrawals of liquidity pools.
+		pub PendingWithdrawals get(fn pending_withdrawals): double_map hasher(twox_64_concat) LiquidityPoolId, hasher(twox_64_concat) WithdrawalId => Option<PendingWithdrawal<T::AccountId, T::BlockNumber>>;
+
+		/// Pending withdrawal IDs of an account in a liquidity pool.
+		///
+		/// At most `MaxPendingWithdrawals` per account.
+		pub PendingWithdrawalIds get(fn pending_withdrawal_ids): double_map hasher(twox_64_concat) LiquidityPoolId, hasher(twox_64_concat) T::AccountId => Vec<WithdrawalId>;
+
+		/// Pending liquidity pool transfers: `(to, expires_at)`.
+		pub PendingTransfers get(fn pending_transfer): map hasher(twox_64_concat) LiquidityPoolId => Option<(T::AccountId, T::BlockNumber)>;
+
+		/// Liquidity locked by pool owners.
+		pub LiquidityLocks get(fn liquidity_lock): map hasher(twox_64_concat) LiquidityPoolId => Option<LiquidityLock<T::BlockNumber>>;
+
+		/// Roles granted by pool owners.
+		pub Roles get(fn role): double_map hasher(twox_64_concat) LiquidityPoolId, hasher(twox_64_concat) T::AccountId => Option<PoolRole>;
+
+		/// Storage version of the module, new networks start with the latest version.
+		StorageVersion get(fn storage_version) build(|_: &GenesisConfig<T, I>| Releases::V2_0_0): Releases;
+	}
+
+	add_extra_genesis {
+		config(phantom): sp_std::marker::PhantomData<I>;
 	}
 }
 
 decl_event!(
 	pub enum Event<T, I=DefaultInstance> where
 		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		IdentityDepositBalance = IdentityDepositBalanceOf<T, I>,
 	{
 		/// Liquidity pool created: \[who, pool_id\]
 		LiquidityPoolCreated(AccountId, LiquidityPoolId),
@@ -127,14 +226,56 @@ decl_event!(
 		/// Identity set: \[who, pool_id\]
 		IdentitySet(AccountId, LiquidityPoolId),
 
-		/// Identity verified: \[pool_id\]
-		IdentityVerified(LiquidityPoolId),
+		/// Identity verified: \[pool_id, level, expires_at\]
+		IdentityVerified(LiquidityPoolId, VerificationLevel, Option<BlockNumber>),
+
+		/// Identity verification removed as identity info changed: \[pool_id\]
+		IdentityUnverified(LiquidityPoolId),
+
+		/// Identity verification revoked: \[pool_id, slashed_deposit\]
+		IdentityVerificationRevoked(LiquidityPoolId, IdentityDepositBalance),
 
 		/// Identity cleared: \[who, pool_id\]
 		IdentityCleared(AccountId, LiquidityPoolId),
 
 		/// Liquidity pool transferred to another owner: \[from, pool_id, to\]
 		LiquidityPoolTransferred(AccountId, LiquidityPoolId, AccountId),
+
+		/// Liquidity pool transfer proposed: \[owner, pool_id, to, expires_at\]
+		LiquidityPoolTransferProposed(AccountId, LiquidityPoolId, AccountId, BlockNumber),
+
+		/// Liquidity pool transfer cancelled: \[owner, pool_id\]
+		LiquidityPoolTransferCancelled(AccountId, LiquidityPoolId),
+
+		/// Liquidity deposited for LP shares: \[who, pool_id, amount, shares\]
+		SharesIssued(AccountId, LiquidityPoolId, Balance, Share),
+
+		/// LP shares redeemed for liquidity: \[who, pool_id, shares, amount\]
+		SharesRedeemed(AccountId, LiquidityPoolId, Share, Balance),
+
+		/// Withdrawal requested: \[who, pool_id, withdrawal_id, shares, claimable_at\]
+		WithdrawalRequested(AccountId, LiquidityPoolId, WithdrawalId, Share, BlockNumber),
+
+		/// Withdrawal claimed: \[who, pool_id, withdrawal_id, shares, amount\]
+		WithdrawalClaimed(AccountId, LiquidityPoolId, WithdrawalId, Share, Balance),
+
+		/// Withdrawal cancelled: \[who, pool_id, withdrawal_id\]
+		WithdrawalCancelled(AccountId, LiquidityPoolId, WithdrawalId),
+
+		/// Withdrawal notice period updated: \[period\]
+		WithdrawalNoticePeriodUpdated(BlockNumber),
+
+		/// Pool role granted: \[owner, pool_id, who, role\]
+		RoleGranted(AccountId, LiquidityPoolId, AccountId, PoolRole),
+
+		/// Pool role revoked: \[owner, pool_id, who\]
+		RoleRevoked(AccountId, LiquidityPoolId, AccountId),
+
+		/// Liquidity locked: \[who, pool_id, amount, until\]
+		LiquidityLocked(AccountId, LiquidityPoolId, Balance, BlockNumber),
+
+		/// Pool status changed: \[pool_id, status\]
+		PoolStatusChanged(LiquidityPoolId, PoolStatus),
 	}
 );
 
@@ -171,6 +312,60 @@ decl_error! {
 
 		/// Identify information not found.
 		IdentityInfoNotFound,
+
+		/// Liquidity is locked and cannot be withdrawn.
+		LiquidityLocked,
+
+		/// Lock expiry is not in the future.
+		InvalidLockExpiry,
+
+		/// An active lock can only be increased or extended.
+		CannotReduceLock,
+
+		/// Invalid verification level.
+		InvalidVerificationLevel,
+
+		/// Verification expiry is not in the future.
+		InvalidVerificationExpiry,
+
+		/// Not enough LP shares.
+		NotEnoughShares,
+
+		/// LP shares amount overflows maximum.
+		SharesOverflow,
+
+		/// LP shares can't be issued if the pool has no liquidity for existing shares.
+		CannotIssueShares,
+
+		/// No available withdrawal ID.
+		NoAvailableWithdrawalId,
+
+		/// Withdrawal not found.
+		WithdrawalNotFound,
+
+		/// Withdrawal notice period not passed yet.
+		WithdrawalNotClaimable,
+
+		/// The account has no role in the pool.
+		RoleNotFound,
+
+		/// No pending transfer of the pool.
+		TransferNotFound,
+
+		/// The pending transfer has expired.
+		TransferExpired,
+
+		/// Shares must not be zero.
+		ZeroShares,
+
+		/// Too many pending withdrawals.
+		TooManyPendingWithdrawals,
+
+		/// Cannot transfer pool to its owner.
+		TransferToOwner,
+
+		/// Pool is not active.
+		PoolNotActive,
 	}
 }
 
@@ -182,6 +377,18 @@ decl_module! {
 
 		const ExistentialDeposit: Balance = T::ExistentialDeposit::get();
 		const Deposit: IdentityDepositBalanceOf<T,I> = T::IdentityDeposit::get();
+		const TransferExpiry: T::BlockNumber = T::TransferExpiry::get();
+		const MaxPendingWithdrawals: u32 = T::MaxPendingWithdrawals::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() == Releases::V1_0_0 {
+				migrations::migrate_to_v2::<T, I>();
+				<StorageVersion<I>>::put(Releases::V2_0_0);
+				T::MaximumBlockWeight::get()
+			} else {
+				0
+			}
+		}
 
 		/// Create a liquidity pool.
 		///
@@ -223,10 +430,16 @@ decl_module! {
 		}
 
 		/// Deposit liquidity to a pool.
+		///
+		/// May only be called from the pool owner or a treasurer. The LP shares of deposited liquidity are issued to
+		/// the pool owner, other liquidity providers should use `deposit_for_shares`.
 		#[weight = (T::WeightInfo::deposit_liquidity(), DispatchClass::Operational)]
 		pub fn deposit_liquidity(origin, #[compact] pool_id: LiquidityPoolId, #[compact] amount: Balance) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
+				let owner = Self::owner(pool_id).ok_or(Error::<T, I>::PoolNotFound)?;
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Treasurer), Error::<T, I>::NoPermission);
+				Self::issue_shares(&owner, pool_id, amount)?;
 				Self::do_deposit_liquidity(&who, pool_id, amount)?;
 				Self::deposit_event(RawEvent::LiquidityDeposited(who, pool_id, amount));
 				Ok(())
@@ -234,6 +447,9 @@ decl_module! {
 		}
 
 		/// Withdraw liquidity from a pool.
+		///
+		/// If there is a withdrawal notice period, the LP shares of `amount` would be put into the withdrawal
+		/// queue, and the amount received on claim follows the pool's P&L during the notice period.
 		#[weight = T::WeightInfo::withdraw_liquidity()]
 		pub fn withdraw_liquidity(origin, #[compact] pool_id: LiquidityPoolId, #[compact] amount: Balance) {
 			with_transaction_result(|| {
@@ -248,9 +464,17 @@ decl_module! {
 				if new_balance < T::ExistentialDeposit::get() {
 					return Err(Error::<T, I>::CannotWithdrawExistentialDeposit.into());
 				}
-
-				Self::do_withdraw_liquidity(&who, pool_id, amount)?;
-				Self::deposit_event(RawEvent::LiquidityWithdrew(who, pool_id, amount));
+				Self::ensure_not_locked(&who, pool_id, amount)?;
+
+				Self::issue_unissued_shares(pool_id)?;
+				let shares = Self::shares_of_amount(pool_id, amount, true)?;
+				if Self::withdrawal_notice_period().is_zero() {
+					Self::burn_shares(&who, pool_id, shares)?;
+					Self::do_withdraw_liquidity(&who, pool_id, amount)?;
+					Self::deposit_event(RawEvent::LiquidityWithdrew(who, pool_id, amount));
+				} else {
+					Self::request_withdrawal(&who, pool_id, shares)?;
+				}
 
 				Ok(())
 			})?;
@@ -259,25 +483,46 @@ decl_module! {
 		/// Set identity of a liquidity pool.
 		///
 		/// May only be called from the pool owner. `IdentityDeposit` amount of balance would be reserved.
+		/// No-op if identity info not changed.
 		#[weight = T::WeightInfo::set_identity()]
 		pub fn set_identity(origin, #[compact] pool_id: LiquidityPoolId, identity_info: IdentityInfo) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				Self::do_set_identity(&who, pool_id, identity_info)?;
-				Self::deposit_event(RawEvent::IdentitySet(who, pool_id));
+				if Self::do_set_identity(&who, pool_id, identity_info)? {
+					Self::deposit_event(RawEvent::IdentitySet(who, pool_id));
+				}
 				Ok(())
 			})?;
 		}
 
-		/// Mark the identity of a liquidity pool as verified.
+		/// Verify the identity of a liquidity pool with `level`. Verification lapses at `expires_at` if any.
 		///
-		/// May only be called from `UpdateOrigin`.
+		/// May only be called from `UpdateOrigin`. Verification is removed if identity info changed.
 		#[weight = T::WeightInfo::verify_identity()]
-		pub fn verify_identity(origin, #[compact] pool_id: LiquidityPoolId) {
+		pub fn verify_identity(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			level: VerificationLevel,
+			expires_at: Option<T::BlockNumber>,
+		) {
 			with_transaction_result(|| {
 				T::UpdateOrigin::ensure_origin(origin)?;
-				Self::do_verify_identity(pool_id)?;
-				Self::deposit_event(RawEvent::IdentityVerified(pool_id));
+				Self::do_verify_identity(pool_id, level, expires_at)?;
+				Self::deposit_event(RawEvent::IdentityVerified(pool_id, level, expires_at));
+				Ok(())
+			})?;
+		}
+
+		/// Revoke the identity verification of a liquidity pool.
+		///
+		/// May only be called from `UpdateOrigin`. If `slash`, for fraudulent identity, the identity info would be
+		/// removed and the reserved deposit slashed.
+		#[weight = T::WeightInfo::revoke_verification()]
+		pub fn revoke_verification(origin, #[compact] pool_id: LiquidityPoolId, slash: bool) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				let slashed = Self::do_revoke_verification(pool_id, slash)?;
+				Self::deposit_event(RawEvent::IdentityVerificationRevoked(pool_id, slashed));
 				Ok(())
 			})?;
 		}
@@ -302,18 +547,191 @@ decl_module! {
 			})?;
 		}
 
-		/// Transfer the ownership of the liquidity pool to `to`.
+		/// Propose to transfer the ownership of the liquidity pool to `to`, replacing the existing proposal if any.
+		///
+		/// The transfer takes effect only once accepted by `to`, within `TransferExpiry` blocks.
 		///
 		/// May only be called from the pool owner.
 		#[weight = T::WeightInfo::transfer_liquidity_pool()]
 		pub fn transfer_liquidity_pool(origin, #[compact] pool_id: LiquidityPoolId, to: T::AccountId) {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_owner(pool_id, &who), Error::<T, I>::NoPermission);
+			ensure!(to != who, Error::<T, I>::TransferToOwner);
+
+			let expires_at = <frame_system::Module<T>>::block_number().saturating_add(T::TransferExpiry::get());
+			<PendingTransfers<T, I>>::insert(pool_id, (to.clone(), expires_at));
+
+			Self::deposit_event(RawEvent::LiquidityPoolTransferProposed(who, pool_id, to, expires_at));
+		}
+
+		/// Accept the proposed transfer of a liquidity pool.
+		///
+		/// May only be called from the proposed new owner. The identity info of the pool would be cleared, and the
+		/// reserved identity deposit released to the previous owner.
+		#[weight = T::WeightInfo::accept_liquidity_pool_transfer()]
+		pub fn accept_liquidity_pool_transfer(origin, #[compact] pool_id: LiquidityPoolId) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				let (to, expires_at) = Self::pending_transfer(pool_id).ok_or(Error::<T, I>::TransferNotFound)?;
+				ensure!(to == who, Error::<T, I>::NoPermission);
+				ensure!(
+					<frame_system::Module<T>>::block_number() <= expires_at,
+					Error::<T, I>::TransferExpired
+				);
+
+				let owner = Self::owner(pool_id).ok_or(Error::<T, I>::PoolNotFound)?;
+				if <IdentityInfos<T, I>>::contains_key(&pool_id) {
+					Self::do_clear_identity(&owner, pool_id)?;
+					Self::deposit_event(RawEvent::IdentityCleared(owner.clone(), pool_id));
+				}
+				Self::do_transfer_liquidity_pool(&owner, pool_id, &who)?;
+				Self::deposit_event(RawEvent::LiquidityPoolTransferred(owner, pool_id, who));
+
+				Ok(())
+			})?;
+		}
+
+		/// Cancel the proposed transfer of a liquidity pool.
+		///
+		/// May only be called from the pool owner.
+		#[weight = T::WeightInfo::cancel_liquidity_pool_transfer()]
+		pub fn cancel_liquidity_pool_transfer(origin, #[compact] pool_id: LiquidityPoolId) {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_owner(pool_id, &who), Error::<T, I>::NoPermission);
+			ensure!(<PendingTransfers<T, I>>::contains_key(pool_id), Error::<T, I>::TransferNotFound);
+
+			<PendingTransfers<T, I>>::remove(pool_id);
+
+			Self::deposit_event(RawEvent::LiquidityPoolTransferCancelled(who, pool_id));
+		}
+
+		/// Deposit liquidity to a pool, and receive pro-rata LP shares.
+		#[weight = T::WeightInfo::deposit_for_shares()]
+		pub fn deposit_for_shares(origin, #[compact] pool_id: LiquidityPoolId, #[compact] amount: Balance) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				let shares = Self::issue_shares(&who, pool_id, amount)?;
+				Self::do_deposit_liquidity(&who, pool_id, amount)?;
+				Self::deposit_event(RawEvent::SharesIssued(who, pool_id, amount, shares));
+				Ok(())
+			})?;
+		}
+
+		/// Redeem LP shares for the pro-rata liquidity of a pool.
+		///
+		/// If there is a withdrawal notice period, the shares would be put into the withdrawal queue.
+		#[weight = T::WeightInfo::redeem_shares()]
+		pub fn redeem_shares(origin, #[compact] pool_id: LiquidityPoolId, #[compact] shares: Share) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				if Self::withdrawal_notice_period().is_zero() {
+					let amount = Self::do_redeem_shares(&who, pool_id, shares)?;
+					Self::deposit_event(RawEvent::SharesRedeemed(who, pool_id, shares, amount));
+				} else {
+					// dry run to check the shares can be redeemed
+					let amount = Self::amount_of_shares(pool_id, shares)?;
+					Self::ensure_not_locked(&who, pool_id, amount)?;
+					Self::request_withdrawal(&who, pool_id, shares)?;
+				}
+				Ok(())
+			})?;
+		}
+
+		/// Claim a pending withdrawal after the withdrawal notice period.
+		///
+		/// May only be called from the account that requested the withdrawal.
+		#[weight = T::WeightInfo::claim_withdrawal()]
+		pub fn claim_withdrawal(origin, #[compact] pool_id: LiquidityPoolId, #[compact] withdrawal_id: WithdrawalId) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				Self::do_transfer_liquidity_pool(&who, pool_id, &to)?;
-				Self::deposit_event(RawEvent::LiquidityPoolTransferred(who, pool_id, to));
+				let withdrawal = Self::pending_withdrawals(pool_id, withdrawal_id).ok_or(Error::<T, I>::WithdrawalNotFound)?;
+				ensure!(withdrawal.who == who, Error::<T, I>::NoPermission);
+				ensure!(
+					<frame_system::Module<T>>::block_number() >= withdrawal.claimable_at,
+					Error::<T, I>::WithdrawalNotClaimable
+				);
+
+				Self::remove_pending_withdrawal(&who, pool_id, withdrawal_id);
+				let amount = Self::do_redeem_shares(&who, pool_id, withdrawal.shares)?;
+				Self::deposit_event(RawEvent::WithdrawalClaimed(who, pool_id, withdrawal_id, withdrawal.shares, amount));
+
 				Ok(())
 			})?;
 		}
+
+		/// Cancel a pending withdrawal.
+		///
+		/// May only be called from the account that requested the withdrawal.
+		#[weight = T::WeightInfo::cancel_withdrawal()]
+		pub fn cancel_withdrawal(origin, #[compact] pool_id: LiquidityPoolId, #[compact] withdrawal_id: WithdrawalId) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				let withdrawal = Self::pending_withdrawals(pool_id, withdrawal_id).ok_or(Error::<T, I>::WithdrawalNotFound)?;
+				ensure!(withdrawal.who == who, Error::<T, I>::NoPermission);
+
+				Self::remove_pending_withdrawal(&who, pool_id, withdrawal_id);
+				Self::deposit_event(RawEvent::WithdrawalCancelled(who, pool_id, withdrawal_id));
+
+				Ok(())
+			})?;
+		}
+
+		/// Set withdrawal notice period.
+		///
+		/// May only be called from `UpdateOrigin`.
+		#[weight = T::WeightInfo::set_withdrawal_notice_period()]
+		pub fn set_withdrawal_notice_period(origin, period: T::BlockNumber) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			<WithdrawalNoticePeriod<T, I>>::put(period);
+			Self::deposit_event(RawEvent::WithdrawalNoticePeriodUpdated(period));
+		}
+
+		/// Grant `role` in `pool_id` to `who`, replacing the existing role of `who` if any.
+		///
+		/// May only be called from the pool owner.
+		#[weight = T::WeightInfo::grant_role()]
+		pub fn grant_role(origin, #[compact] pool_id: LiquidityPoolId, who: T::AccountId, role: PoolRole) {
+			let owner = ensure_signed(origin)?;
+			ensure!(Self::is_owner(pool_id, &owner), Error::<T, I>::NoPermission);
+			<Roles<T, I>>::insert(pool_id, &who, role);
+			Self::deposit_event(RawEvent::RoleGranted(owner, pool_id, who, role));
+		}
+
+		/// Revoke the role of `who` in `pool_id`.
+		///
+		/// May only be called from the pool owner.
+		#[weight = T::WeightInfo::revoke_role()]
+		pub fn revoke_role(origin, #[compact] pool_id: LiquidityPoolId, who: T::AccountId) {
+			let owner = ensure_signed(origin)?;
+			ensure!(Self::is_owner(pool_id, &owner), Error::<T, I>::NoPermission);
+			ensure!(<Roles<T, I>>::contains_key(pool_id, &who), Error::<T, I>::RoleNotFound);
+			<Roles<T, I>>::remove(pool_id, &who);
+			Self::deposit_event(RawEvent::RoleRevoked(owner, pool_id, who));
+		}
+
+		/// Lock `amount` of liquidity in `pool_id` until block `until`.
+		///
+		/// May only be called from the pool owner. The lock is capped by the liquidity of the owner's LP shares,
+		/// which cannot be withdrawn by the owner until expired. An active lock can only be increased or extended.
+		/// Other liquidity providers are not limited.
+		#[weight = T::WeightInfo::lock_liquidity()]
+		pub fn lock_liquidity(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			#[compact] amount: Balance,
+			until: T::BlockNumber,
+		) {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_owner(pool_id, &who), Error::<T, I>::NoPermission);
+			ensure!(until > <frame_system::Module<T>>::block_number(), Error::<T, I>::InvalidLockExpiry);
+			ensure!(amount <= Self::owner_liquidity(pool_id), Error::<T, I>::NotEnoughShares);
+			if let Some(lock) = Self::active_liquidity_lock(pool_id) {
+				ensure!(amount >= lock.amount && until >= lock.until, Error::<T, I>::CannotReduceLock);
+			}
+
+			<LiquidityLocks<T, I>>::insert(pool_id, LiquidityLock { amount, until });
+			Self::deposit_event(RawEvent::LiquidityLocked(who, pool_id, amount, until));
+		}
 	}
 }
 
@@ -325,18 +743,46 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 	pub fn is_owner(pool_id: LiquidityPoolId, who: &T::AccountId) -> bool {
 		Self::owner(pool_id).map_or(false, |ref owner| owner == who)
 	}
+
+	pub fn has_role(pool_id: LiquidityPoolId, who: &T::AccountId, role: PoolRole) -> bool {
+		Self::is_owner(pool_id, who) || Self::role(pool_id, who) == Some(role)
+	}
 }
 
 impl<T: Config<I>, I: Instance> LiquidityPools<T::AccountId> for Module<T, I> {
 	fn all() -> Vec<LiquidityPoolId> {
-		// TODO: optimize once `iter_first_key` is ready
-		<Pools<T, I>>::iter().map(|(pool_id, _)| pool_id).collect()
+		[PoolStatus::Active, PoolStatus::Disabled, PoolStatus::WindingDown]
+			.iter()
+			.flat_map(|status| Self::pools_with_status(*status))
+			.collect()
+	}
+
+	fn pools_with_status(status: PoolStatus) -> Vec<LiquidityPoolId> {
+		Self::pools_with_status(status)
+	}
+
+	fn status(pool_id: LiquidityPoolId) -> Option<PoolStatus> {
+		Self::pool_status(pool_id)
+	}
+
+	fn settle_wind_down(pool_id: LiquidityPoolId) {
+		if Self::pool_status(pool_id) == Some(PoolStatus::WindingDown) && T::PoolManager::can_remove(pool_id) {
+			Self::set_status(pool_id, PoolStatus::Disabled);
+		}
 	}
 
 	fn is_owner(pool_id: LiquidityPoolId, who: &T::AccountId) -> bool {
 		Self::is_owner(pool_id, who)
 	}
 
+	fn has_role(pool_id: LiquidityPoolId, who: &T::AccountId, role: PoolRole) -> bool {
+		Self::has_role(pool_id, who, role)
+	}
+
+	fn owner(pool_id: LiquidityPoolId) -> Option<T::AccountId> {
+		Self::owner(pool_id)
+	}
+
 	/// Check if pool exists
 	fn pool_exists(pool_id: LiquidityPoolId) -> bool {
 		<Pools<T, I>>::contains_key(&pool_id)
@@ -347,6 +793,10 @@ impl<T: Config<I>, I: Instance> LiquidityPools<T::AccountId> for Module<T, I> {
 		Self::balance(pool_id)
 	}
 
+	fn locked_liquidity(pool_id: LiquidityPoolId) -> Balance {
+		Self::locked_liquidity(pool_id)
+	}
+
 	/// Deposit some amount of collateral to `pool_id`, from `source`.
 	fn deposit_liquidity(source: &T::AccountId, pool_id: LiquidityPoolId, amount: Balance) -> DispatchResult {
 		Self::do_deposit_liquidity(source, pool_id, amount)
@@ -370,6 +820,78 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		Self::pools(&pool_id).map(|pool| pool.owner)
 	}
 
+	/// LP shares of a liquidity pool held by `who`.
+	pub fn shares_of(pool_id: LiquidityPoolId, who: &T::AccountId) -> Share {
+		match T::ShareCurrencyId::convert(pool_id) {
+			Some(currency_id) => T::ShareCurrency::free_balance(currency_id, who),
+			None => Self::shares(pool_id, who),
+		}
+	}
+
+	/// Identity verification level of a liquidity pool. Returns `Unknown` if not verified or lapsed.
+	pub fn verification_level(pool_id: LiquidityPoolId) -> VerificationLevel {
+		Self::identity_infos(pool_id).map_or(VerificationLevel::Unknown, |(_, _, verification)| {
+			match verification.expires_at {
+				Some(expires_at) if <frame_system::Module<T>>::block_number() >= expires_at => {
+					VerificationLevel::Unknown
+				}
+				_ => verification.level,
+			}
+		})
+	}
+
+	/// Liquidity lock of a pool, if not expired.
+	pub fn active_liquidity_lock(pool_id: LiquidityPoolId) -> Option<LiquidityLock<T::BlockNumber>> {
+		Self::liquidity_lock(pool_id).filter(|lock| <frame_system::Module<T>>::block_number() < lock.until)
+	}
+
+	/// Liquidity amount of LP shares held by the pool owner, including unissued shares.
+	pub fn owner_liquidity(pool_id: LiquidityPoolId) -> Balance {
+		if Self::total_shares(pool_id).is_zero() {
+			return Self::balance(pool_id);
+		}
+		Self::owner(pool_id)
+			.and_then(|owner| Self::amount_of_shares(pool_id, Self::shares_of(pool_id, &owner)).ok())
+			.unwrap_or_default()
+	}
+
+	/// Locked liquidity amount of a pool, which cannot be withdrawn by the pool owner.
+	///
+	/// Capped by the owner liquidity: if losses push it below the lock amount, all the owner liquidity is locked.
+	pub fn locked_liquidity(pool_id: LiquidityPoolId) -> Balance {
+		Self::active_liquidity_lock(pool_id)
+			.map_or(Zero::zero(), |lock| lock.amount.min(Self::owner_liquidity(pool_id)))
+	}
+
+	/// Ensure the owner liquidity left is not less than the locked liquidity, if `who` is the pool owner withdrawing
+	/// `amount`.
+	fn ensure_not_locked(who: &T::AccountId, pool_id: LiquidityPoolId, amount: Balance) -> DispatchResult {
+		if Self::is_owner(pool_id, who) {
+			ensure!(
+				Self::owner_liquidity(pool_id).saturating_sub(amount) >= Self::locked_liquidity(pool_id),
+				Error::<T, I>::LiquidityLocked
+			);
+		}
+		Ok(())
+	}
+
+	/// Liquidity pools of the given `status`.
+	pub fn pools_with_status(status: PoolStatus) -> Vec<LiquidityPoolId> {
+		<PoolsByStatus<I>>::iter_prefix(status)
+			.map(|(pool_id, _)| pool_id)
+			.collect()
+	}
+
+	fn set_status(pool_id: LiquidityPoolId, status: PoolStatus) {
+		if let Some(old) = Self::pool_status(pool_id) {
+			<PoolsByStatus<I>>::remove(old, pool_id);
+		}
+		<PoolStatuses<I>>::insert(pool_id, status);
+		<PoolsByStatus<I>>::insert(status, pool_id, ());
+
+		Self::deposit_event(RawEvent::PoolStatusChanged(pool_id, status));
+	}
+
 	fn set_balance(pool_id: LiquidityPoolId, balance: Balance) {
 		if let Some(mut pool) = Self::pools(pool_id) {
 			pool.balance = balance;
@@ -389,11 +911,24 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		<NextPoolId<I>>::put(next_pool_id);
 		// owner reference
 		<Pools<T, I>>::insert(&pool_id, Pool::new(who.clone(), Default::default()));
+		Self::set_status(pool_id, PoolStatus::Active);
 		Ok(pool_id)
 	}
 
 	fn do_disable_pool(who: &T::AccountId, pool_id: LiquidityPoolId) -> DispatchResult {
 		ensure!(Self::is_owner(pool_id, who), Error::<T, I>::NoPermission);
+		ensure!(
+			Self::pool_status(pool_id) == Some(PoolStatus::Active),
+			Error::<T, I>::PoolNotActive
+		);
+
+		// disabled pools with liabilities, like opened positions, are winding down until settled
+		let status = if T::PoolManager::can_remove(pool_id) {
+			PoolStatus::Disabled
+		} else {
+			PoolStatus::WindingDown
+		};
+		Self::set_status(pool_id, status);
 
 		T::OnDisableLiquidityPool::on_disable(pool_id);
 
@@ -403,6 +938,15 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 	fn do_remove_pool(who: &T::AccountId, pool_id: LiquidityPoolId) -> DispatchResult {
 		ensure!(Self::is_owner(pool_id, who), Error::<T, I>::NoPermission);
 		ensure!(T::PoolManager::can_remove(pool_id), Error::<T, I>::CannotRemovePool);
+		ensure!(
+			Self::active_liquidity_lock(pool_id).is_none(),
+			Error::<T, I>::LiquidityLocked
+		);
+
+		// LP shares held by others must be redeemed first
+		let shares = Self::shares_of(pool_id, who);
+		ensure!(shares == Self::total_shares(pool_id), Error::<T, I>::CannotRemovePool);
+		Self::burn_shares(who, pool_id, shares)?;
 
 		// clear_identity
 		Self::do_clear_identity(who, pool_id)?;
@@ -412,6 +956,12 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		T::LiquidityCurrency::transfer(&Self::account_id(), who, balance)?;
 
 		<Pools<T, I>>::remove(&pool_id);
+		<PendingWithdrawals<T, I>>::remove_prefix(&pool_id);
+		<PendingWithdrawalIds<T, I>>::remove_prefix(&pool_id);
+		<Roles<T, I>>::remove_prefix(&pool_id);
+		<PendingTransfers<T, I>>::remove(&pool_id);
+		<LiquidityLocks<T, I>>::remove(&pool_id);
+		Self::set_status(pool_id, PoolStatus::Removed);
 
 		T::OnRemoveLiquidityPool::on_remove(pool_id);
 
@@ -449,7 +999,12 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		Ok(())
 	}
 
-	fn do_set_identity(who: &T::AccountId, pool_id: LiquidityPoolId, identity_info: IdentityInfo) -> DispatchResult {
+	/// Returns `false` if identity info not changed.
+	fn do_set_identity(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		identity_info: IdentityInfo,
+	) -> result::Result<bool, DispatchError> {
 		ensure!(Self::is_owner(pool_id, &who), Error::<T, I>::NoPermission);
 		ensure!(
 			identity_info.legal_name.len() <= 100
@@ -460,26 +1015,74 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 			Error::<T, I>::IdentityInfoTooLong
 		);
 
-		if let Some((_, deposit_amount, _)) = Self::identity_infos(pool_id) {
-			<IdentityInfos<T, I>>::insert(&pool_id, (identity_info, deposit_amount, false));
+		if let Some((old_identity_info, deposit_amount, verification)) = Self::identity_infos(pool_id) {
+			// verification is only kept if identity info not changed
+			if old_identity_info == identity_info {
+				return Ok(false);
+			}
+			if verification.level != VerificationLevel::Unknown {
+				Self::deposit_event(RawEvent::IdentityUnverified(pool_id));
+			}
+			<IdentityInfos<T, I>>::insert(&pool_id, (identity_info, deposit_amount, Verification::default()));
 		} else {
 			// reserve deposit from owner
 			T::IdentityDepositCurrency::reserve(who, T::IdentityDeposit::get())?;
 
-			<IdentityInfos<T, I>>::insert(&pool_id, (identity_info, T::IdentityDeposit::get(), false));
+			<IdentityInfos<T, I>>::insert(
+				&pool_id,
+				(identity_info, T::IdentityDeposit::get(), Verification::default()),
+			);
 		}
 
-		Ok(())
+		Ok(true)
 	}
 
-	fn do_verify_identity(pool_id: LiquidityPoolId) -> DispatchResult {
+	fn do_verify_identity(
+		pool_id: LiquidityPoolId,
+		level: VerificationLevel,
+		expires_at: Option<T::BlockNumber>,
+	) -> DispatchResult {
+		ensure!(
+			level != VerificationLevel::Unknown,
+			Error::<T, I>::InvalidVerificationLevel
+		);
+		if let Some(expires_at) = expires_at {
+			ensure!(
+				expires_at > <frame_system::Module<T>>::block_number(),
+				Error::<T, I>::InvalidVerificationExpiry
+			);
+		}
+
 		let (identity_info, deposit_amount, _) =
 			Self::identity_infos(pool_id).ok_or(Error::<T, I>::IdentityInfoNotFound)?;
-		<IdentityInfos<T, I>>::insert(&pool_id, (identity_info, deposit_amount, true));
+		<IdentityInfos<T, I>>::insert(
+			&pool_id,
+			(identity_info, deposit_amount, Verification { level, expires_at }),
+		);
 
 		Ok(())
 	}
 
+	fn do_revoke_verification(
+		pool_id: LiquidityPoolId,
+		slash: bool,
+	) -> result::Result<IdentityDepositBalanceOf<T, I>, DispatchError> {
+		let (identity_info, deposit_amount, _) =
+			Self::identity_infos(pool_id).ok_or(Error::<T, I>::IdentityInfoNotFound)?;
+
+		if !slash {
+			<IdentityInfos<T, I>>::insert(&pool_id, (identity_info, deposit_amount, Verification::default()));
+			return Ok(Zero::zero());
+		}
+
+		let owner = Self::owner(pool_id).ok_or(Error::<T, I>::PoolNotFound)?;
+		// slashed deposit is burned
+		let (_, not_slashed) = T::IdentityDepositCurrency::slash_reserved(&owner, deposit_amount);
+		<IdentityInfos<T, I>>::remove(&pool_id);
+
+		Ok(deposit_amount.saturating_sub(not_slashed))
+	}
+
 	fn do_clear_identity(who: &T::AccountId, pool_id: LiquidityPoolId) -> DispatchResult {
 		ensure!(Self::is_owner(pool_id, &who), Error::<T, I>::NoPermission);
 
@@ -491,13 +1094,20 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		Ok(())
 	}
 
-	pub fn do_transfer_liquidity_pool(
-		who: &T::AccountId,
-		pool_id: LiquidityPoolId,
-		to: &T::AccountId,
-	) -> DispatchResult {
+	fn do_transfer_liquidity_pool(who: &T::AccountId, pool_id: LiquidityPoolId, to: &T::AccountId) -> DispatchResult {
 		ensure!(Self::is_owner(pool_id, &who), Error::<T, I>::NoPermission);
-		Self::do_clear_identity(&who, pool_id)?;
+
+		// owner's LP shares go along with the pool, and pending withdrawals of them are cancelled
+		let shares = Self::shares_of(pool_id, who);
+		Self::burn_shares(who, pool_id, shares)?;
+		Self::mint_shares(to, pool_id, shares)?;
+		<PendingWithdrawalIds<T, I>>::take(pool_id, who)
+			.into_iter()
+			.for_each(|withdrawal_id| <PendingWithdrawals<T, I>>::remove(pool_id, withdrawal_id));
+
+		// roles are granted by the previous owner
+		<Roles<T, I>>::remove_prefix(&pool_id);
+		<PendingTransfers<T, I>>::remove(&pool_id);
 
 		let mut pool = Self::pools(pool_id).expect("is owner check ensures pool exist; qed");
 		pool.owner = to.clone();
@@ -506,3 +1116,215 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		Ok(())
 	}
 }
+
+// LP shares
+impl<T: Config<I>, I: Instance> Module<T, I> {
+	/// If no shares were issued, credit existing liquidity to the pool owner as LP shares.
+	fn issue_unissued_shares(pool_id: LiquidityPoolId) -> DispatchResult {
+		let owner = Self::owner(pool_id).ok_or(Error::<T, I>::PoolNotFound)?;
+		let balance = Self::balance(pool_id);
+		if Self::total_shares(pool_id).is_zero() && !balance.is_zero() {
+			Self::mint_shares(&owner, pool_id, balance)?;
+		}
+		Ok(())
+	}
+
+	/// Issue LP shares of `amount` liquidity to `who`. Should be called before the liquidity is deposited.
+	fn issue_shares(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		amount: Balance,
+	) -> result::Result<Share, DispatchError> {
+		Self::issue_unissued_shares(pool_id)?;
+
+		let shares = Self::shares_of_amount(pool_id, amount, false)?;
+		Self::mint_shares(who, pool_id, shares)?;
+
+		Ok(shares)
+	}
+
+	/// LP shares of `amount` liquidity.
+	///
+	/// shares = amount * total_shares / balance
+	fn shares_of_amount(
+		pool_id: LiquidityPoolId,
+		amount: Balance,
+		round_up: bool,
+	) -> result::Result<Share, Error<T, I>> {
+		let total_shares = Self::total_shares(pool_id);
+		if total_shares.is_zero() {
+			return Ok(amount);
+		}
+
+		let balance = Self::balance(pool_id);
+		ensure!(!balance.is_zero(), Error::<T, I>::CannotIssueShares);
+		let shares = multiply_by_rational(amount, total_shares, balance).map_err(|_| Error::<T, I>::SharesOverflow)?;
+		let exact = multiply_by_rational(shares, balance, total_shares).map_err(|_| Error::<T, I>::SharesOverflow)?;
+		if round_up && exact < amount {
+			shares.checked_add(One::one()).ok_or(Error::<T, I>::SharesOverflow)
+		} else {
+			Ok(shares)
+		}
+	}
+
+	/// Liquidity amount of `shares`.
+	///
+	/// amount = shares * balance / total_shares
+	fn amount_of_shares(pool_id: LiquidityPoolId, shares: Share) -> result::Result<Balance, Error<T, I>> {
+		let total_shares = Self::total_shares(pool_id);
+		ensure!(shares <= total_shares, Error::<T, I>::NotEnoughShares);
+		if shares.is_zero() {
+			return Ok(Zero::zero());
+		}
+
+		multiply_by_rational(shares, Self::balance(pool_id), total_shares).map_err(|_| Error::<T, I>::SharesOverflow)
+	}
+
+	fn mint_shares(who: &T::AccountId, pool_id: LiquidityPoolId, shares: Share) -> DispatchResult {
+		if shares.is_zero() {
+			return Ok(());
+		}
+
+		<TotalShares<I>>::try_mutate(pool_id, |total| -> DispatchResult {
+			*total = total.checked_add(shares).ok_or(Error::<T, I>::SharesOverflow)?;
+			Ok(())
+		})?;
+
+		match T::ShareCurrencyId::convert(pool_id) {
+			Some(currency_id) => T::ShareCurrency::deposit(currency_id, who, shares),
+			None => <Shares<T, I>>::try_mutate(pool_id, who, |balance| -> DispatchResult {
+				*balance = balance.checked_add(shares).ok_or(Error::<T, I>::SharesOverflow)?;
+				Ok(())
+			}),
+		}
+	}
+
+	fn burn_shares(who: &T::AccountId, pool_id: LiquidityPoolId, shares: Share) -> DispatchResult {
+		if shares.is_zero() {
+			return Ok(());
+		}
+
+		ensure!(Self::shares_of(pool_id, who) >= shares, Error::<T, I>::NotEnoughShares);
+
+		match T::ShareCurrencyId::convert(pool_id) {
+			Some(currency_id) => T::ShareCurrency::withdraw(currency_id, who, shares)?,
+			None => {
+				let balance = Self::shares(pool_id, who) - shares;
+				if balance.is_zero() {
+					<Shares<T, I>>::remove(pool_id, who);
+				} else {
+					<Shares<T, I>>::insert(pool_id, who, balance);
+				}
+			}
+		}
+
+		let total = Self::total_shares(pool_id).saturating_sub(shares);
+		if total.is_zero() {
+			<TotalShares<I>>::remove(pool_id);
+		} else {
+			<TotalShares<I>>::insert(pool_id, total);
+		}
+
+		Ok(())
+	}
+}
+
+// Withdrawal queue
+impl<T: Config<I>, I: Instance> Module<T, I> {
+	/// Pending withdrawals of `who` in a pool.
+	fn pending_withdrawals_of(
+		pool_id: LiquidityPoolId,
+		who: &T::AccountId,
+	) -> impl Iterator<Item = (WithdrawalId, PendingWithdrawal<T::AccountId, T::BlockNumber>)> {
+		Self::pending_withdrawal_ids(pool_id, who)
+			.into_iter()
+			.filter_map(move |id| Self::pending_withdrawals(pool_id, id).map(|w| (id, w)))
+	}
+
+	/// Remove a pending withdrawal of `who` from the queue.
+	fn remove_pending_withdrawal(who: &T::AccountId, pool_id: LiquidityPoolId, withdrawal_id: WithdrawalId) {
+		<PendingWithdrawals<T, I>>::remove(pool_id, withdrawal_id);
+		<PendingWithdrawalIds<T, I>>::mutate(pool_id, who, |ids| ids.retain(|id| *id != withdrawal_id));
+	}
+
+	/// Pending withdrawals of a pool, with the liquidity amount of their LP shares.
+	pub fn pending_withdrawals_of_pool(
+		pool_id: LiquidityPoolId,
+	) -> Vec<(WithdrawalId, PendingWithdrawal<T::AccountId, T::BlockNumber>, Balance)> {
+		<PendingWithdrawals<T, I>>::iter_prefix(pool_id)
+			.map(|(id, withdrawal)| {
+				let amount = Self::amount_of_shares(pool_id, withdrawal.shares).unwrap_or_default();
+				(id, withdrawal, amount)
+			})
+			.collect()
+	}
+
+	/// Put a withdrawal of `shares` into the queue, claimable after the notice period.
+	fn request_withdrawal(who: &T::AccountId, pool_id: LiquidityPoolId, shares: Share) -> DispatchResult {
+		ensure!(!shares.is_zero(), Error::<T, I>::ZeroShares);
+		ensure!(
+			(Self::pending_withdrawal_ids(pool_id, who).len() as u32) < T::MaxPendingWithdrawals::get(),
+			Error::<T, I>::TooManyPendingWithdrawals
+		);
+
+		let pending_shares =
+			Self::pending_withdrawals_of(pool_id, who).fold(0, |acc: Share, (_, w)| acc.saturating_add(w.shares));
+		ensure!(
+			Self::shares_of(pool_id, who) >= pending_shares.saturating_add(shares),
+			Error::<T, I>::NotEnoughShares
+		);
+
+		let withdrawal_id = Self::next_withdrawal_id();
+		let next_withdrawal_id = withdrawal_id
+			.checked_add(One::one())
+			.ok_or(Error::<T, I>::NoAvailableWithdrawalId)?;
+		<NextWithdrawalId<I>>::put(next_withdrawal_id);
+
+		let claimable_at = <frame_system::Module<T>>::block_number().saturating_add(Self::withdrawal_notice_period());
+		<PendingWithdrawals<T, I>>::insert(
+			pool_id,
+			withdrawal_id,
+			PendingWithdrawal {
+				who: who.clone(),
+				shares,
+				claimable_at,
+			},
+		);
+		<PendingWithdrawalIds<T, I>>::append(pool_id, who, withdrawal_id);
+		Self::deposit_event(RawEvent::WithdrawalRequested(
+			who.clone(),
+			pool_id,
+			withdrawal_id,
+			shares,
+			claimable_at,
+		));
+
+		Ok(())
+	}
+
+	/// Redeem `shares` and withdraw the liquidity amount of them to `who`.
+	fn do_redeem_shares(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		shares: Share,
+	) -> result::Result<Balance, DispatchError> {
+		let amount = Self::amount_of_shares(pool_id, shares)?;
+
+		T::PoolManager::ensure_can_withdraw(pool_id, amount)?;
+
+		let new_balance = Self::balance(pool_id)
+			.checked_sub(amount)
+			.ok_or(Error::<T, I>::NotEnoughBalance)?;
+
+		// check minimum balance
+		if new_balance < T::ExistentialDeposit::get() {
+			return Err(Error::<T, I>::CannotWithdrawExistentialDeposit.into());
+		}
+		Self::ensure_not_locked(who, pool_id, amount)?;
+
+		Self::burn_shares(who, pool_id, shares)?;
+		Self::do_withdraw_liquidity(who, pool_id, amount)?;
+
+		Ok(amount)
+	}
+}
@@ -0,0 +1,32 @@
+//! Storage migrations.
+
+use super::*;
+
+/// Migrate storage from `V1_0_0` to `V2_0_0`.
+///
+/// - Pools created before pool statuses were introduced are backfilled as active.
+/// - Verified flag of identity infos is migrated to `Reasonable` verification, which never lapses.
+pub fn migrate_to_v2<T: Config<I>, I: Instance>() {
+	let pool_ids: Vec<LiquidityPoolId> = <Pools<T, I>>::iter()
+		.map(|(pool_id, _)| pool_id)
+		.filter(|pool_id| !<PoolStatuses<I>>::contains_key(pool_id))
+		.collect();
+	for pool_id in pool_ids {
+		<PoolStatuses<I>>::insert(pool_id, PoolStatus::Active);
+		<PoolsByStatus<I>>::insert(PoolStatus::Active, pool_id, ());
+	}
+
+	<IdentityInfos<T, I>>::translate::<(IdentityInfo, IdentityDepositBalanceOf<T, I>, bool), _>(
+		|_, (identity_info, deposit_amount, verified)| {
+			let verification = if verified {
+				Verification {
+					level: VerificationLevel::Reasonable,
+					expires_at: None,
+				}
+			} else {
+				Verification::default()
+			};
+			Some((identity_info, deposit_amount, verification))
+		},
+	);
+}
@@ -15,6 +15,8 @@ use orml_traits::parameter_type_with_key;
 
 use primitives::{Balance, CurrencyId, LiquidityPoolId};
 
+use std::cell::RefCell;
+
 pub type BlockNumber = u64;
 pub type AccountId = u128;
 
@@ -67,6 +69,8 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = CurrencyId::LAMI;
 	pub const GetLiquidityCurrencyId: CurrencyId = CurrencyId::AUSD;
 	pub const IdentityDeposit: u128 = 1000;
+	pub const TransferExpiry: BlockNumber = 10;
+	pub const MaxPendingWithdrawals: u32 = 3;
 }
 
 impl pallet_balances::Config for Runtime {
@@ -111,10 +115,19 @@ impl orml_tokens::Config for Runtime {
 	type OnDust = orml_tokens::TransferDust<Runtime, TreasuryAccount>;
 }
 
+thread_local! {
+	static CAN_REMOVE: RefCell<bool> = RefCell::new(true);
+}
+
 pub struct PoolManager;
+impl PoolManager {
+	pub fn set_can_remove(can_remove: bool) {
+		CAN_REMOVE.with(|v| *v.borrow_mut() = can_remove);
+	}
+}
 impl BaseLiquidityPoolManager<LiquidityPoolId, Balance> for PoolManager {
 	fn can_remove(_pool_id: LiquidityPoolId) -> bool {
-		true
+		CAN_REMOVE.with(|v| *v.borrow())
 	}
 	fn ensure_can_withdraw(_pool: LiquidityPoolId, _amount: Balance) -> DispatchResult {
 		Ok(())
@@ -131,6 +144,13 @@ impl OnRemoveLiquidityPool for DummyOnRemove {
 	fn on_remove(_: LiquidityPoolId) {}
 }
 
+pub struct MarginPoolShareCurrencyId;
+impl Convert<LiquidityPoolId, Option<CurrencyId>> for MarginPoolShareCurrencyId {
+	fn convert(pool_id: LiquidityPoolId) -> Option<CurrencyId> {
+		Some(CurrencyId::MarginLiquidityPoolShare(pool_id))
+	}
+}
+
 parameter_types! {
 	pub const Instance1ModuleId: ModuleId = ModuleId(*b"test/lp1");
 }
@@ -142,10 +162,14 @@ impl Config for Runtime {
 	type ExistentialDeposit = ExistentialDeposit;
 	type IdentityDeposit = IdentityDeposit;
 	type IdentityDepositCurrency = Balances;
+	type TransferExpiry = TransferExpiry;
+	type MaxPendingWithdrawals = MaxPendingWithdrawals;
 	type ModuleId = Instance1ModuleId;
 	type OnDisableLiquidityPool = DummyOnDisable;
 	type OnRemoveLiquidityPool = DummyOnRemove;
 	type UpdateOrigin = EnsureSignedBy<UpdateOrigin, AccountId>;
+	type ShareCurrency = Currencies;
+	type ShareCurrencyId = NoShareCurrency;
 	type WeightInfo = ();
 }
 
@@ -156,10 +180,14 @@ impl Config<Instance1> for Runtime {
 	type ExistentialDeposit = ExistentialDeposit;
 	type IdentityDeposit = IdentityDeposit;
 	type IdentityDepositCurrency = Balances;
+	type TransferExpiry = TransferExpiry;
+	type MaxPendingWithdrawals = MaxPendingWithdrawals;
 	type ModuleId = Instance1ModuleId;
 	type OnDisableLiquidityPool = DummyOnDisable;
 	type OnRemoveLiquidityPool = DummyOnRemove;
 	type UpdateOrigin = EnsureSignedBy<UpdateOrigin, AccountId>;
+	type ShareCurrency = Currencies;
+	type ShareCurrencyId = MarginPoolShareCurrencyId;
 	type WeightInfo = ();
 }
 pub type Instance1Module = Module<Runtime, Instance1>;
@@ -175,10 +203,14 @@ impl Config<Instance2> for Runtime {
 	type ExistentialDeposit = ExistentialDeposit;
 	type IdentityDeposit = IdentityDeposit;
 	type IdentityDepositCurrency = Balances;
+	type TransferExpiry = TransferExpiry;
+	type MaxPendingWithdrawals = MaxPendingWithdrawals;
 	type ModuleId = Instance2ModuleId;
 	type OnDisableLiquidityPool = DummyOnDisable;
 	type OnRemoveLiquidityPool = DummyOnRemove;
 	type UpdateOrigin = EnsureSignedBy<UpdateOrigin, AccountId>;
+	type ShareCurrency = Currencies;
+	type ShareCurrencyId = NoShareCurrency;
 	type WeightInfo = ();
 }
 pub type Instance2Module = Module<Runtime, Instance2>;
@@ -3,7 +3,8 @@
 use super::*;
 use mock::*;
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, storage, traits::OnRuntimeUpgrade};
+use sp_runtime::traits::BadOrigin;
 use traits::LiquidityPools;
 
 fn get_free_balance(who: &AccountId) -> Balance {
@@ -14,6 +15,13 @@ fn get_reserved_balance(who: &AccountId) -> Balance {
 	<Runtime as Config>::IdentityDepositCurrency::reserved_balance(who)
 }
 
+fn known_good() -> Verification<BlockNumber> {
+	Verification {
+		level: VerificationLevel::KnownGood,
+		expires_at: None,
+	}
+}
+
 #[test]
 fn is_owner_should_work() {
 	new_test_ext().execute_with(|| {
@@ -65,6 +73,114 @@ fn should_remove_pool() {
 	})
 }
 
+#[test]
+fn should_track_pool_status() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance1Module::create_pool(Origin::signed(BOB)));
+		assert_eq!(Instance1Module::pool_status(0), Some(PoolStatus::Active));
+		let mut active_pools = Instance1Module::pools_with_status(PoolStatus::Active);
+		active_pools.sort();
+		assert_eq!(active_pools, vec![0, 1]);
+
+		assert_ok!(Instance1Module::disable_pool(Origin::signed(ALICE), 0));
+		assert_eq!(Instance1Module::pool_status(0), Some(PoolStatus::Disabled));
+		assert_eq!(Instance1Module::pools_with_status(PoolStatus::Active), vec![1]);
+		assert_eq!(Instance1Module::pools_with_status(PoolStatus::Disabled), vec![0]);
+		assert_noop!(
+			Instance1Module::disable_pool(Origin::signed(ALICE), 0),
+			Error::<Runtime, Instance1>::PoolNotActive
+		);
+
+		assert_ok!(Instance1Module::remove_pool(Origin::signed(ALICE), 0));
+		assert_eq!(
+			<Instance1Module as LiquidityPools<AccountId>>::status(0),
+			Some(PoolStatus::Removed)
+		);
+		assert_eq!(
+			Instance1Module::pools_with_status(PoolStatus::Disabled),
+			Vec::<u32>::new()
+		);
+		assert_eq!(<Instance1Module as LiquidityPools<AccountId>>::all(), vec![1]);
+		assert_eq!(<Instance1Module as LiquidityPools<AccountId>>::status(2), None);
+	})
+}
+
+#[test]
+fn should_settle_wind_down() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+		PoolManager::set_can_remove(false);
+		assert_ok!(Instance1Module::disable_pool(Origin::signed(ALICE), 0));
+		assert_eq!(Instance1Module::pool_status(0), Some(PoolStatus::WindingDown));
+
+		<Instance1Module as LiquidityPools<AccountId>>::settle_wind_down(0);
+		assert_eq!(Instance1Module::pool_status(0), Some(PoolStatus::WindingDown));
+
+		PoolManager::set_can_remove(true);
+		<Instance1Module as LiquidityPools<AccountId>>::settle_wind_down(0);
+		assert_eq!(Instance1Module::pool_status(0), Some(PoolStatus::Disabled));
+		assert_eq!(
+			Instance1Module::pools_with_status(PoolStatus::WindingDown),
+			Vec::<u32>::new()
+		);
+		assert_eq!(Instance1Module::pools_with_status(PoolStatus::Disabled), vec![0]);
+	})
+}
+
+#[test]
+fn migrate_to_v2_backfills_pool_statuses() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance1Module::create_pool(Origin::signed(BOB)));
+		assert_ok!(Instance1Module::disable_pool(Origin::signed(BOB), 1));
+		// pool 0 created before pool statuses were introduced
+		<PoolStatuses<Instance1>>::remove(0);
+		<PoolsByStatus<Instance1>>::remove(PoolStatus::Active, 0);
+
+		let identity = IdentityInfo {
+			legal_name: "laminar".as_bytes().to_vec(),
+			display_name: vec![],
+			web: vec![],
+			email: vec![],
+			image_url: vec![],
+		};
+		let deposit: Balance = 1000;
+		storage::unhashed::put(
+			&<IdentityInfos<Runtime, Instance1>>::hashed_key_for(0),
+			&(identity.clone(), deposit, true),
+		);
+		storage::unhashed::put(
+			&<IdentityInfos<Runtime, Instance1>>::hashed_key_for(1),
+			&(identity.clone(), deposit, false),
+		);
+
+		assert_eq!(Instance1Module::storage_version(), Releases::V1_0_0);
+		Instance1Module::on_runtime_upgrade();
+		assert_eq!(Instance1Module::storage_version(), Releases::V2_0_0);
+
+		assert_eq!(Instance1Module::pool_status(0), Some(PoolStatus::Active));
+		assert_eq!(Instance1Module::pools_with_status(PoolStatus::Active), vec![0]);
+		assert_eq!(Instance1Module::pool_status(1), Some(PoolStatus::Disabled));
+
+		assert_eq!(
+			Instance1Module::identity_infos(0),
+			Some((
+				identity.clone(),
+				deposit,
+				Verification {
+					level: VerificationLevel::Reasonable,
+					expires_at: None,
+				}
+			))
+		);
+		assert_eq!(
+			Instance1Module::identity_infos(1),
+			Some((identity, deposit, Verification::default()))
+		);
+	})
+}
+
 #[test]
 fn should_deposit_liquidity() {
 	new_test_ext().execute_with(|| {
@@ -77,6 +193,10 @@ fn should_deposit_liquidity() {
 			Instance1Module::deposit_liquidity(Origin::signed(ALICE), 1, 1000),
 			Error::<Runtime, Instance1>::PoolNotFound
 		);
+		assert_noop!(
+			Instance1Module::deposit_liquidity(Origin::signed(BOB), 0, 1000),
+			Error::<Runtime, Instance1>::NoPermission
+		);
 	})
 }
 
@@ -96,6 +216,97 @@ fn should_withdraw_liquidity() {
 	})
 }
 
+#[test]
+fn should_lock_liquidity() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance1Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+
+		assert_noop!(
+			Instance1Module::lock_liquidity(Origin::signed(BOB), 0, 500, 10),
+			Error::<Runtime, Instance1>::NoPermission
+		);
+		assert_noop!(
+			Instance1Module::lock_liquidity(Origin::signed(ALICE), 0, 5000, 10),
+			Error::<Runtime, Instance1>::NotEnoughShares
+		);
+		assert_noop!(
+			Instance1Module::lock_liquidity(Origin::signed(ALICE), 0, 500, System::block_number()),
+			Error::<Runtime, Instance1>::InvalidLockExpiry
+		);
+		assert_ok!(Instance1Module::lock_liquidity(Origin::signed(ALICE), 0, 500, 10));
+		assert_eq!(Instance1Module::locked_liquidity(0), 500);
+		assert_eq!(<Instance1Module as LiquidityPools<AccountId>>::locked_liquidity(0), 500);
+		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::LiquidityLocked(ALICE, 0, 500, 10));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		assert_noop!(
+			Instance1Module::lock_liquidity(Origin::signed(ALICE), 0, 400, 10),
+			Error::<Runtime, Instance1>::CannotReduceLock
+		);
+		assert_noop!(
+			Instance1Module::lock_liquidity(Origin::signed(ALICE), 0, 500, 9),
+			Error::<Runtime, Instance1>::CannotReduceLock
+		);
+
+		// locked liquidity cannot be withdrawn
+		assert_noop!(
+			Instance1Module::withdraw_liquidity(Origin::signed(ALICE), 0, 600),
+			Error::<Runtime, Instance1>::LiquidityLocked
+		);
+		assert_ok!(Instance1Module::withdraw_liquidity(Origin::signed(ALICE), 0, 400));
+		assert_eq!(Instance1Module::liquidity(0), 600);
+		assert_noop!(
+			Instance1Module::remove_pool(Origin::signed(ALICE), 0),
+			Error::<Runtime, Instance1>::LiquidityLocked
+		);
+
+		// protocol withdrawal is not limited
+		assert_ok!(<Instance1Module as LiquidityPools<AccountId>>::withdraw_liquidity(
+			&BOB, 0, 200
+		));
+		assert_eq!(Instance1Module::liquidity(0), 400);
+		// losses reduce the locked liquidity
+		assert_eq!(Instance1Module::locked_liquidity(0), 400);
+		assert_noop!(
+			Instance1Module::withdraw_liquidity(Origin::signed(ALICE), 0, 1),
+			Error::<Runtime, Instance1>::LiquidityLocked
+		);
+
+		// lock expired
+		System::set_block_number(10);
+		assert_eq!(Instance1Module::locked_liquidity(0), 0);
+		assert_eq!(Instance1Module::active_liquidity_lock(0), None);
+		assert_ok!(Instance1Module::remove_pool(Origin::signed(ALICE), 0));
+		assert_eq!(Instance1Module::liquidity_lock(0), None);
+	})
+}
+
+#[test]
+fn liquidity_lock_should_only_limit_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance1Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_ok!(Instance1Module::deposit_for_shares(Origin::signed(BOB), 0, 1000));
+		assert_eq!(Instance1Module::owner_liquidity(0), 1000);
+
+		assert_noop!(
+			Instance1Module::lock_liquidity(Origin::signed(ALICE), 0, 1500, 10),
+			Error::<Runtime, Instance1>::NotEnoughShares
+		);
+		assert_ok!(Instance1Module::lock_liquidity(Origin::signed(ALICE), 0, 1000, 10));
+
+		// other liquidity providers are not limited
+		assert_ok!(Instance1Module::redeem_shares(Origin::signed(BOB), 0, 1000));
+		assert_eq!(Instance1Module::liquidity(0), 1000);
+
+		assert_noop!(
+			Instance1Module::redeem_shares(Origin::signed(ALICE), 0, 1),
+			Error::<Runtime, Instance1>::LiquidityLocked
+		);
+	})
+}
+
 #[test]
 fn should_fail_withdraw_liquidity() {
 	new_test_ext().execute_with(|| {
@@ -143,11 +354,20 @@ fn should_set_identity() {
 		));
 
 		identity.display_name = "Open finance platform".as_bytes().to_vec();
-		assert_ok!(Instance1Module::set_identity(Origin::signed(ALICE), 0, identity));
+		assert_ok!(Instance1Module::set_identity(
+			Origin::signed(ALICE),
+			0,
+			identity.clone()
+		));
 		assert_eq!(get_free_balance(&ALICE), 99000);
 
 		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::IdentitySet(ALICE, 0));
 		assert!(System::events().iter().any(|record| record.event == event));
+
+		// no event if identity info not changed
+		System::reset_events();
+		assert_ok!(Instance1Module::set_identity(Origin::signed(ALICE), 0, identity));
+		assert!(System::events().is_empty());
 	})
 }
 
@@ -177,27 +397,150 @@ fn should_verify_identity() {
 		// verify
 		assert_eq!(
 			Instance1Module::identity_infos(0),
-			Some((identity.clone(), 1000, false))
+			Some((identity.clone(), 1000, Verification::default()))
+		);
+		assert_ok!(Instance1Module::verify_identity(
+			Origin::signed(UpdateOrigin::get()),
+			0,
+			VerificationLevel::KnownGood,
+			None
+		));
+		assert_eq!(
+			Instance1Module::identity_infos(0),
+			Some((identity.clone(), 1000, known_good()))
 		);
-		assert_ok!(Instance1Module::verify_identity(Origin::signed(UpdateOrigin::get()), 0));
-		assert_eq!(Instance1Module::identity_infos(0), Some((identity.clone(), 1000, true)));
 		assert_eq!(get_reserved_balance(&ALICE), 1000);
+		// set unchanged identity keeps verification
+		assert_ok!(Instance1Module::set_identity(
+			Origin::signed(ALICE),
+			0,
+			identity.clone()
+		));
+		assert_eq!(
+			Instance1Module::identity_infos(0),
+			Some((identity.clone(), 1000, known_good()))
+		);
 		// verify then modify
+		let modified_identity = IdentityInfo {
+			email: "hello@laminar.one".as_bytes().to_vec(),
+			..identity.clone()
+		};
+		assert_ok!(Instance1Module::set_identity(
+			Origin::signed(ALICE),
+			0,
+			modified_identity.clone()
+		));
+		assert_eq!(
+			Instance1Module::identity_infos(0),
+			Some((modified_identity.clone(), 1000, Verification::default()))
+		);
+		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::IdentityUnverified(0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_ok!(Instance1Module::set_identity(
+			Origin::signed(ALICE),
+			0,
+			identity.clone()
+		));
+		assert_ok!(Instance1Module::verify_identity(
+			Origin::signed(UpdateOrigin::get()),
+			0,
+			VerificationLevel::KnownGood,
+			None
+		));
+		assert_eq!(get_reserved_balance(&ALICE), 1000);
+		assert_eq!(
+			Instance1Module::identity_infos(0),
+			Some((identity.clone(), 1000, known_good()))
+		);
+		assert_eq!(get_free_balance(&ALICE), 99000);
+
+		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::IdentityVerified(
+			0,
+			VerificationLevel::KnownGood,
+			None,
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+	})
+}
+
+#[test]
+fn verification_should_lapse_and_be_revoked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+
+		let identity = IdentityInfo {
+			legal_name: "laminar".as_bytes().to_vec(),
+			display_name: vec![],
+			web: "https://laminar.one".as_bytes().to_vec(),
+			email: vec![],
+			image_url: vec![],
+		};
 		assert_ok!(Instance1Module::set_identity(
 			Origin::signed(ALICE),
 			0,
 			identity.clone()
 		));
+		assert_eq!(Instance1Module::verification_level(0), VerificationLevel::Unknown);
+
+		assert_noop!(
+			Instance1Module::verify_identity(Origin::signed(UpdateOrigin::get()), 0, VerificationLevel::Unknown, None),
+			Error::<Runtime, Instance1>::InvalidVerificationLevel
+		);
+		assert_noop!(
+			Instance1Module::verify_identity(
+				Origin::signed(UpdateOrigin::get()),
+				0,
+				VerificationLevel::Reasonable,
+				Some(System::block_number())
+			),
+			Error::<Runtime, Instance1>::InvalidVerificationExpiry
+		);
+
+		// lapse
+		let expires_at = System::block_number() + 10;
+		assert_ok!(Instance1Module::verify_identity(
+			Origin::signed(UpdateOrigin::get()),
+			0,
+			VerificationLevel::Reasonable,
+			Some(expires_at)
+		));
+		assert_eq!(Instance1Module::verification_level(0), VerificationLevel::Reasonable);
+		System::set_block_number(expires_at);
+		assert_eq!(Instance1Module::verification_level(0), VerificationLevel::Unknown);
+
+		// revoke without slash
+		assert_ok!(Instance1Module::verify_identity(
+			Origin::signed(UpdateOrigin::get()),
+			0,
+			VerificationLevel::KnownGood,
+			None
+		));
+		assert_noop!(
+			Instance1Module::revoke_verification(Origin::signed(ALICE), 0, false),
+			BadOrigin
+		);
+		assert_ok!(Instance1Module::revoke_verification(
+			Origin::signed(UpdateOrigin::get()),
+			0,
+			false
+		));
 		assert_eq!(
 			Instance1Module::identity_infos(0),
-			Some((identity.clone(), 1000, false))
+			Some((identity.clone(), 1000, Verification::default()))
 		);
-		assert_ok!(Instance1Module::verify_identity(Origin::signed(UpdateOrigin::get()), 0));
 		assert_eq!(get_reserved_balance(&ALICE), 1000);
-		assert_eq!(Instance1Module::identity_infos(0), Some((identity.clone(), 1000, true)));
+
+		// revoke and slash
+		assert_ok!(Instance1Module::revoke_verification(
+			Origin::signed(UpdateOrigin::get()),
+			0,
+			true
+		));
+		assert_eq!(Instance1Module::identity_infos(0), None);
+		assert_eq!(get_reserved_balance(&ALICE), 0);
 		assert_eq!(get_free_balance(&ALICE), 99000);
 
-		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::IdentityVerified(0));
+		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::IdentityVerificationRevoked(0, 1000));
 		assert!(System::events().iter().any(|record| record.event == event));
 	})
 }
@@ -227,7 +570,7 @@ fn should_clear_identity() {
 		assert_eq!(get_reserved_balance(&ALICE), 1000);
 		assert_eq!(
 			Instance1Module::identity_infos(0),
-			Some((identity.clone(), 1000, false))
+			Some((identity.clone(), 1000, Verification::default()))
 		);
 		assert_ok!(Instance1Module::clear_identity(Origin::signed(ALICE), 0));
 		assert_eq!(get_reserved_balance(&ALICE), 0);
@@ -244,8 +587,16 @@ fn should_clear_identity() {
 			identity.clone()
 		));
 		assert_eq!(get_reserved_balance(&ALICE), 1000);
-		assert_ok!(Instance1Module::verify_identity(Origin::signed(UpdateOrigin::get()), 0));
-		assert_eq!(Instance1Module::identity_infos(0), Some((identity.clone(), 1000, true)));
+		assert_ok!(Instance1Module::verify_identity(
+			Origin::signed(UpdateOrigin::get()),
+			0,
+			VerificationLevel::KnownGood,
+			None
+		));
+		assert_eq!(
+			Instance1Module::identity_infos(0),
+			Some((identity.clone(), 1000, known_good()))
+		);
 		assert_ok!(Instance1Module::clear_identity(Origin::signed(ALICE), 0));
 		assert_eq!(get_reserved_balance(&ALICE), 0);
 
@@ -256,13 +607,25 @@ fn should_clear_identity() {
 			identity.clone()
 		));
 		assert_eq!(get_reserved_balance(&ALICE), 1000);
-		assert_ok!(Instance1Module::verify_identity(Origin::signed(UpdateOrigin::get()), 0));
-		assert_eq!(Instance1Module::identity_infos(0), Some((identity.clone(), 1000, true)));
+		assert_ok!(Instance1Module::verify_identity(
+			Origin::signed(UpdateOrigin::get()),
+			0,
+			VerificationLevel::KnownGood,
+			None
+		));
+		assert_eq!(
+			Instance1Module::identity_infos(0),
+			Some((identity.clone(), 1000, known_good()))
+		);
 		assert_ok!(Instance1Module::remove_pool(Origin::signed(ALICE), 0));
 		assert_eq!(get_reserved_balance(&ALICE), 0);
 		assert_eq!(get_free_balance(&ALICE), 100000);
 
-		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::IdentityVerified(0));
+		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::IdentityVerified(
+			0,
+			VerificationLevel::KnownGood,
+			None,
+		));
 		assert!(System::events().iter().any(|record| record.event == event));
 	})
 }
@@ -291,6 +654,16 @@ fn should_transfer_liquidity_pool() {
 		assert_eq!(get_free_balance(&ALICE), 99000);
 
 		assert_ok!(Instance1Module::transfer_liquidity_pool(Origin::signed(ALICE), 0, BOB));
+		let event =
+			mock::Event::base_liquidity_pools_Instance1(RawEvent::LiquidityPoolTransferProposed(ALICE, 0, BOB, 11));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(Instance1Module::is_owner(0, &ALICE));
+		assert_eq!(get_free_balance(&ALICE), 99000);
+
+		assert_ok!(Instance1Module::accept_liquidity_pool_transfer(Origin::signed(BOB), 0));
+		assert!(Instance1Module::is_owner(0, &BOB));
+		assert_eq!(Instance1Module::pending_transfer(0), None);
+		assert_eq!(Instance1Module::identity_infos(0), None);
 		assert_eq!(get_free_balance(&ALICE), 100000);
 
 		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::LiquidityPoolTransferred(ALICE, 0, BOB));
@@ -311,6 +684,52 @@ fn should_transfer_liquidity_pool() {
 	})
 }
 
+#[test]
+fn liquidity_pool_transfer_should_be_accepted_by_proposed_owner_before_expiry() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+		assert_noop!(
+			Instance1Module::transfer_liquidity_pool(Origin::signed(BOB), 0, BOB),
+			Error::<Runtime, Instance1>::NoPermission
+		);
+		assert_noop!(
+			Instance1Module::transfer_liquidity_pool(Origin::signed(ALICE), 0, ALICE),
+			Error::<Runtime, Instance1>::TransferToOwner
+		);
+		assert_noop!(
+			Instance1Module::accept_liquidity_pool_transfer(Origin::signed(BOB), 0),
+			Error::<Runtime, Instance1>::TransferNotFound
+		);
+
+		assert_ok!(Instance1Module::transfer_liquidity_pool(Origin::signed(ALICE), 0, BOB));
+		assert_noop!(
+			Instance1Module::accept_liquidity_pool_transfer(Origin::signed(3), 0),
+			Error::<Runtime, Instance1>::NoPermission
+		);
+
+		// cancel
+		assert_ok!(Instance1Module::cancel_liquidity_pool_transfer(
+			Origin::signed(ALICE),
+			0
+		));
+		let event = mock::Event::base_liquidity_pools_Instance1(RawEvent::LiquidityPoolTransferCancelled(ALICE, 0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_noop!(
+			Instance1Module::accept_liquidity_pool_transfer(Origin::signed(BOB), 0),
+			Error::<Runtime, Instance1>::TransferNotFound
+		);
+
+		// expire
+		assert_ok!(Instance1Module::transfer_liquidity_pool(Origin::signed(ALICE), 0, BOB));
+		System::set_block_number(12);
+		assert_noop!(
+			Instance1Module::accept_liquidity_pool_transfer(Origin::signed(BOB), 0),
+			Error::<Runtime, Instance1>::TransferExpired
+		);
+		assert!(Instance1Module::is_owner(0, &ALICE));
+	})
+}
+
 #[test]
 fn multi_instances_have_independent_storage() {
 	new_test_ext().execute_with(|| {
@@ -337,3 +756,287 @@ fn multi_instances_have_independent_storage() {
 		assert_eq!(LiquidityCurrency::free_balance(&Instance2Module::account_id()), 0);
 	})
 }
+
+#[test]
+fn deposit_for_shares_and_redeem_shares_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance2Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance2Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_eq!(Instance2Module::shares_of(0, &ALICE), 1000);
+
+		assert_ok!(Instance2Module::deposit_for_shares(Origin::signed(BOB), 0, 1000));
+		let event = mock::Event::base_liquidity_pools_Instance2(RawEvent::SharesIssued(BOB, 0, 1000, 1000));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(Instance2Module::shares(0, BOB), 1000);
+		assert_eq!(Instance2Module::total_shares(0), 2000);
+		assert_eq!(Instance2Module::liquidity(0), 2000);
+
+		// pool profit
+		assert_ok!(<Instance2Module as LiquidityPools<AccountId>>::deposit_liquidity(
+			&ALICE, 0, 1000
+		));
+
+		assert_noop!(
+			Instance2Module::redeem_shares(Origin::signed(BOB), 0, 1001),
+			Error::<Runtime, Instance2>::NotEnoughShares
+		);
+		assert_ok!(Instance2Module::redeem_shares(Origin::signed(BOB), 0, 1000));
+		let event = mock::Event::base_liquidity_pools_Instance2(RawEvent::SharesRedeemed(BOB, 0, 1000, 1500));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(Instance2Module::shares(0, BOB), 0);
+		assert_eq!(Instance2Module::total_shares(0), 1000);
+		assert_eq!(Instance2Module::liquidity(0), 1500);
+		assert_eq!(LiquidityCurrency::free_balance(&BOB), 100_500);
+	})
+}
+
+#[test]
+fn minted_shares_are_transferable() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance1Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_ok!(Instance1Module::deposit_for_shares(Origin::signed(BOB), 0, 500));
+
+		let share_currency_id = CurrencyId::MarginLiquidityPoolShare(0);
+		assert_eq!(Tokens::free_balance(share_currency_id, &BOB), 500);
+		assert_eq!(Instance1Module::shares(0, BOB), 0);
+
+		assert_ok!(Tokens::transfer(Origin::signed(BOB), 3, share_currency_id, 200));
+		assert_eq!(Instance1Module::shares_of(0, &3), 200);
+		assert_ok!(Instance1Module::redeem_shares(Origin::signed(3), 0, 200));
+		assert_eq!(LiquidityCurrency::free_balance(&3), 200);
+		assert_eq!(Instance1Module::shares_of(0, &BOB), 300);
+		assert_eq!(Instance1Module::total_shares(0), 1300);
+	})
+}
+
+#[test]
+fn cannot_remove_pool_with_shares_of_others() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance1Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance1Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_ok!(Instance1Module::deposit_for_shares(Origin::signed(BOB), 0, 500));
+		assert_noop!(
+			Instance1Module::remove_pool(Origin::signed(ALICE), 0),
+			Error::<Runtime, Instance1>::CannotRemovePool
+		);
+		assert_noop!(
+			Instance1Module::withdraw_liquidity(Origin::signed(ALICE), 0, 1100),
+			Error::<Runtime, Instance1>::NotEnoughShares
+		);
+
+		assert_ok!(Instance1Module::redeem_shares(Origin::signed(BOB), 0, 500));
+		assert_ok!(Instance1Module::remove_pool(Origin::signed(ALICE), 0));
+		assert_eq!(Instance1Module::total_shares(0), 0);
+		assert_eq!(LiquidityCurrency::free_balance(&ALICE), 100_000);
+	})
+}
+
+#[test]
+fn withdrawal_notice_period_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance2Module::set_withdrawal_notice_period(
+			Origin::signed(UpdateOrigin::get()),
+			10
+		));
+		assert_ok!(Instance2Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance2Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_ok!(Instance2Module::deposit_for_shares(Origin::signed(BOB), 0, 1000));
+
+		assert_ok!(Instance2Module::redeem_shares(Origin::signed(BOB), 0, 600));
+		let event = mock::Event::base_liquidity_pools_Instance2(RawEvent::WithdrawalRequested(BOB, 0, 0, 600, 11));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_noop!(
+			Instance2Module::redeem_shares(Origin::signed(BOB), 0, 401),
+			Error::<Runtime, Instance2>::NotEnoughShares
+		);
+
+		// still counted as pool liquidity until claimed
+		assert_eq!(Instance2Module::liquidity(0), 2000);
+		assert_eq!(Instance2Module::shares(0, BOB), 1000);
+		assert_eq!(
+			Instance2Module::pending_withdrawals_of_pool(0),
+			vec![(
+				0,
+				PendingWithdrawal {
+					who: BOB,
+					shares: 600,
+					claimable_at: 11
+				},
+				600
+			)]
+		);
+
+		assert_noop!(
+			Instance2Module::claim_withdrawal(Origin::signed(BOB), 0, 0),
+			Error::<Runtime, Instance2>::WithdrawalNotClaimable
+		);
+		System::set_block_number(11);
+		assert_noop!(
+			Instance2Module::claim_withdrawal(Origin::signed(ALICE), 0, 0),
+			Error::<Runtime, Instance2>::NoPermission
+		);
+		assert_ok!(Instance2Module::claim_withdrawal(Origin::signed(BOB), 0, 0));
+		let event = mock::Event::base_liquidity_pools_Instance2(RawEvent::WithdrawalClaimed(BOB, 0, 0, 600, 600));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(Instance2Module::pending_withdrawals(0, 0), None);
+		assert_eq!(Instance2Module::pending_withdrawal_ids(0, BOB), vec![]);
+		assert_eq!(Instance2Module::shares(0, BOB), 400);
+		assert_eq!(Instance2Module::liquidity(0), 1400);
+		assert_eq!(LiquidityCurrency::free_balance(&BOB), 99_600);
+		assert_noop!(
+			Instance2Module::claim_withdrawal(Origin::signed(BOB), 0, 0),
+			Error::<Runtime, Instance2>::WithdrawalNotFound
+		);
+	})
+}
+
+#[test]
+fn cancel_withdrawal_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance2Module::set_withdrawal_notice_period(
+			Origin::signed(UpdateOrigin::get()),
+			10
+		));
+		assert_ok!(Instance2Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance2Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_ok!(Instance2Module::withdraw_liquidity(Origin::signed(ALICE), 0, 500));
+		assert_eq!(Instance2Module::liquidity(0), 1000);
+
+		assert_noop!(
+			Instance2Module::cancel_withdrawal(Origin::signed(BOB), 0, 0),
+			Error::<Runtime, Instance2>::NoPermission
+		);
+		assert_ok!(Instance2Module::cancel_withdrawal(Origin::signed(ALICE), 0, 0));
+		let event = mock::Event::base_liquidity_pools_Instance2(RawEvent::WithdrawalCancelled(ALICE, 0, 0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(Instance2Module::pending_withdrawals(0, 0), None);
+		assert_eq!(Instance2Module::pending_withdrawal_ids(0, ALICE), vec![]);
+		assert_eq!(Instance2Module::shares_of(0, &ALICE), 1000);
+	})
+}
+
+#[test]
+fn pending_withdrawals_are_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance2Module::set_withdrawal_notice_period(
+			Origin::signed(UpdateOrigin::get()),
+			10
+		));
+		assert_ok!(Instance2Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance2Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_ok!(Instance2Module::deposit_for_shares(Origin::signed(BOB), 0, 1000));
+
+		assert_noop!(
+			Instance2Module::redeem_shares(Origin::signed(BOB), 0, 0),
+			Error::<Runtime, Instance2>::ZeroShares
+		);
+
+		assert_ok!(Instance2Module::redeem_shares(Origin::signed(BOB), 0, 100));
+		assert_ok!(Instance2Module::redeem_shares(Origin::signed(BOB), 0, 100));
+		assert_ok!(Instance2Module::redeem_shares(Origin::signed(BOB), 0, 100));
+		assert_eq!(Instance2Module::pending_withdrawal_ids(0, BOB), vec![0, 1, 2]);
+		assert_noop!(
+			Instance2Module::redeem_shares(Origin::signed(BOB), 0, 100),
+			Error::<Runtime, Instance2>::TooManyPendingWithdrawals
+		);
+
+		// other accounts are not affected
+		assert_ok!(Instance2Module::withdraw_liquidity(Origin::signed(ALICE), 0, 100));
+
+		assert_ok!(Instance2Module::cancel_withdrawal(Origin::signed(BOB), 0, 1));
+		assert_eq!(Instance2Module::pending_withdrawal_ids(0, BOB), vec![0, 2]);
+		assert_ok!(Instance2Module::redeem_shares(Origin::signed(BOB), 0, 100));
+	})
+}
+
+#[test]
+fn withdrawal_notice_period_saturates() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance2Module::set_withdrawal_notice_period(
+			Origin::signed(UpdateOrigin::get()),
+			BlockNumber::max_value()
+		));
+		assert_ok!(Instance2Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance2Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		System::set_block_number(2);
+		assert_ok!(Instance2Module::withdraw_liquidity(Origin::signed(ALICE), 0, 500));
+		assert_eq!(
+			Instance2Module::pending_withdrawals(0, 0).map(|w| w.claimable_at),
+			Some(BlockNumber::max_value())
+		);
+	})
+}
+
+#[test]
+fn grant_and_revoke_role_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance2Module::create_pool(Origin::signed(ALICE)));
+		assert!(Instance2Module::has_role(0, &ALICE, PoolRole::Operator));
+		assert!(!Instance2Module::has_role(0, &BOB, PoolRole::Operator));
+
+		assert_noop!(
+			Instance2Module::grant_role(Origin::signed(BOB), 0, BOB, PoolRole::Operator),
+			Error::<Runtime, Instance2>::NoPermission
+		);
+		assert_ok!(Instance2Module::grant_role(
+			Origin::signed(ALICE),
+			0,
+			BOB,
+			PoolRole::Operator
+		));
+		let event =
+			mock::Event::base_liquidity_pools_Instance2(RawEvent::RoleGranted(ALICE, 0, BOB, PoolRole::Operator));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(Instance2Module::has_role(0, &BOB, PoolRole::Operator));
+		assert!(!Instance2Module::has_role(0, &BOB, PoolRole::Treasurer));
+
+		// roles don't grant withdrawal
+		assert_ok!(Instance2Module::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_noop!(
+			Instance2Module::withdraw_liquidity(Origin::signed(BOB), 0, 100),
+			Error::<Runtime, Instance2>::NoPermission
+		);
+
+		assert_ok!(Instance2Module::revoke_role(Origin::signed(ALICE), 0, BOB));
+		assert!(!Instance2Module::has_role(0, &BOB, PoolRole::Operator));
+		assert_noop!(
+			Instance2Module::revoke_role(Origin::signed(ALICE), 0, BOB),
+			Error::<Runtime, Instance2>::RoleNotFound
+		);
+	})
+}
+
+#[test]
+fn treasurer_should_deposit_liquidity() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Instance2Module::create_pool(Origin::signed(ALICE)));
+		assert_ok!(Instance2Module::grant_role(
+			Origin::signed(ALICE),
+			0,
+			BOB,
+			PoolRole::Operator
+		));
+		assert_noop!(
+			Instance2Module::deposit_liquidity(Origin::signed(BOB), 0, 1000),
+			Error::<Runtime, Instance2>::NoPermission
+		);
+
+		assert_ok!(Instance2Module::grant_role(
+			Origin::signed(ALICE),
+			0,
+			BOB,
+			PoolRole::Treasurer
+		));
+		assert_ok!(Instance2Module::deposit_liquidity(Origin::signed(BOB), 0, 1000));
+		assert_eq!(Instance2Module::liquidity(0), 1000);
+		assert_eq!(Instance2Module::owner_liquidity(0), 1000);
+		assert_eq!(Instance2Module::shares(0, BOB), 0);
+
+		// treasurer could not withdraw
+		assert_noop!(
+			Instance2Module::withdraw_liquidity(Origin::signed(BOB), 0, 100),
+			Error::<Runtime, Instance2>::NoPermission
+		);
+	})
+}
@@ -1,4 +1,4 @@
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! Weights partly estimated by hand. Regenerate with the benchmark CLI before release.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -70,10 +70,68 @@ impl crate::WeightInfo for () {
 	fn on_initialize(r: u32, w: u32) -> Weight {
 		(245_763_000 as Weight)
 			.saturating_add((35_620_000 as Weight).saturating_mul(r as Weight))
-			.saturating_add((92_617_000 as Weight).saturating_mul(w as Weight))
+			.saturating_add((61_204_000 as Weight).saturating_mul(w as Weight))
 			.saturating_add(DbWeight::get().reads(11 as Weight))
 			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
-			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(w as Weight)))
-			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(w as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(w as Weight)))
+	}
+	fn set_wind_down_deadline() -> Weight {
+		(35_812_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_min_locked_liquidity_for_high_leverage() -> Weight {
+		(16_804_000 as Weight).saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_relative_spread() -> Weight {
+		(87_250_000 as Weight)
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_relative_max_spread() -> Weight {
+		(64_120_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_spread_tiers(t: u32) -> Weight {
+		(88_102_000 as Weight)
+			.saturating_add((1_284_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_volatility_factor() -> Weight {
+		(52_306_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn configure_pool(c: u32) -> Weight {
+		(61_457_000 as Weight)
+			.saturating_add((261_402_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((8 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn set_trading_pair_min_leveraged_amount() -> Weight {
+		(58_214_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_max_leveraged_amount() -> Weight {
+		(61_930_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_leverage_bounds() -> Weight {
+		(55_402_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn set_pool_swap_rate() -> Weight {
+		(36_820_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
 	}
 }
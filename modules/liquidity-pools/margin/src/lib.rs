@@ -3,31 +3,33 @@
 use codec::{Decode, Encode};
 use frame_support::{
 	decl_error, decl_event, decl_module, decl_storage, ensure,
-	storage::IterableStorageMap,
+	storage::{IterableStorageDoubleMap, IterableStorageMap},
 	traits::{EnsureOrigin, Get, UnixTime},
 	weights::Weight,
 	Parameter,
 };
 use frame_system::ensure_signed;
 use primitives::{
-	arithmetic::fixed_i128_mul_signum, AccumulateConfig, Balance, Leverage, Leverages, LiquidityPoolId, Price,
-	SwapRate, TradingPair,
+	arithmetic::{fixed_i128_mul_signum, fixed_u128_from_bps, BPS_PER_UNIT},
+	AccumulateConfig, Balance, Leverage, Leverages, LiquidityPoolId, PoolRole, PoolStatus, PositionLeverage, Price,
+	SpreadBps, SpreadMode, SpreadTier, Spreads, SwapRate, SwapRateAccumulation, TradingPair,
 };
 use sp_arithmetic::{FixedI128, FixedPointNumber};
 use sp_runtime::{
 	traits::{AtLeast32Bit, Saturating, Zero},
 	DispatchResult, ModuleId, RuntimeDebug,
 };
-use sp_std::{cmp::max, prelude::*, result};
+use sp_std::{cmp::max, collections::btree_set::BTreeSet, prelude::*, result};
 
 use orml_utilities::with_transaction_result;
 
 use traits::{
 	LiquidityPools, MarginProtocolLiquidityPools, MarginProtocolLiquidityPoolsManager, OnDisableLiquidityPool,
-	OnRemoveLiquidityPool, OpenPositionError,
+	OnRemoveLiquidityPool, OpenPositionError, VolatilityProvider,
 };
 
 mod default_weight;
+mod migrations;
 mod mock;
 mod tests;
 
@@ -44,6 +46,17 @@ pub trait WeightInfo {
 	fn liquidity_pool_disable_trading_pair() -> Weight;
 	fn set_default_min_leveraged_amount() -> Weight;
 	fn set_min_leveraged_amount() -> Weight;
+	fn set_wind_down_deadline() -> Weight;
+	fn set_min_locked_liquidity_for_high_leverage() -> Weight;
+	fn set_relative_spread() -> Weight;
+	fn set_relative_max_spread() -> Weight;
+	fn set_spread_tiers(t: u32) -> Weight;
+	fn set_volatility_factor() -> Weight;
+	fn configure_pool(c: u32) -> Weight;
+	fn set_trading_pair_min_leveraged_amount() -> Weight;
+	fn set_max_leveraged_amount() -> Weight;
+	fn set_leverage_bounds() -> Weight;
+	fn set_pool_swap_rate() -> Weight;
 	fn on_initialize(r: u32, w: u32) -> Weight;
 }
 
@@ -58,6 +71,12 @@ pub struct MarginTradingPairOption<Moment> {
 	/// The max spread. The minimum of max spread and pool's spread would be used in trading.
 	pub max_spread: Option<Price>,
 
+	/// The max spread in basis points of the oracle price. If both max spreads are set, the lower
+	/// one is used.
+	///
+	/// DEFAULT-NOTE: `None`, no relative cap.
+	pub relative_max_spread: Option<SpreadBps>,
+
 	/// Swap rate.
 	///
 	/// DEFAULT-NOTE: zero rate if not set.
@@ -80,6 +99,28 @@ pub struct MarginPoolOption {
 	///
 	/// DEFAULT-NOTE: no min requirement for this pool.
 	pub min_leveraged_amount: Balance,
+
+	/// Leverages allowed in all enabled trading pairs of this pool, in addition to enabled fixed
+	/// leverages of each pair.
+	///
+	/// DEFAULT-NOTE: `None`, only enabled fixed leverages are allowed.
+	pub leverage_bounds: Option<LeverageBounds>,
+}
+
+/// Min and max leverage multiplier, inclusive.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone, Copy)]
+pub struct LeverageBounds {
+	/// Min leverage multiplier.
+	pub min: u8,
+
+	/// Max leverage multiplier.
+	pub max: u8,
+}
+
+impl LeverageBounds {
+	pub fn contains(&self, multiplier: u8) -> bool {
+		self.min <= multiplier && multiplier <= self.max
+	}
 }
 
 /// Trading pair option in a pool of margin.
@@ -100,10 +141,88 @@ pub struct MarginPoolTradingPairOption {
 	/// DEFAULT-NOTE: `None`, pool owner must set spread.
 	pub ask_spread: Option<Price>,
 
+	/// Spread mode. In relative mode, `relative_bid_spread` and `relative_ask_spread` are used.
+	///
+	/// DEFAULT-NOTE: absolute.
+	pub spread_mode: SpreadMode,
+
+	/// Bid spread in basis points of the oracle price.
+	///
+	/// DEFAULT-NOTE: `None`, pool owner must set spread.
+	pub relative_bid_spread: Option<SpreadBps>,
+
+	/// Ask spread in basis points of the oracle price.
+	///
+	/// DEFAULT-NOTE: `None`, pool owner must set spread.
+	pub relative_ask_spread: Option<SpreadBps>,
+
+	/// Spread add-ons by leveraged amount, sorted by threshold. The add-on of the highest tier a
+	/// trade reaches is added to both bid and ask spread.
+	///
+	/// DEFAULT-NOTE: no tiers, no add-on.
+	pub spread_tiers: Vec<SpreadTier>,
+
+	/// Volatility factor `k`. If set, `k * volatility * price` is added to both bid and ask spread.
+	///
+	/// DEFAULT-NOTE: `None`, spread not adjusted by volatility.
+	pub volatility_factor: Option<Price>,
+
 	/// Enabled leverages.
 	///
 	/// DEFAULT-NOTE: No leverage.
 	pub enabled_trades: Leverages,
+
+	/// Min leveraged amount in USD to open a position of this pair. The pool-wide min leveraged
+	/// amount still applies.
+	///
+	/// DEFAULT-NOTE: no min requirement for this pair.
+	pub min_leveraged_amount: Balance,
+
+	/// Max leveraged amount in USD to open a position, by leverage.
+	///
+	/// DEFAULT-NOTE: no max limit for any leverage.
+	pub max_leveraged_amounts: Vec<(Leverage, Balance)>,
+
+	/// Swap rate adjustment of this pair in the pool, added to the trading pair's swap rate after
+	/// the pool's additional swap rate applied.
+	///
+	/// DEFAULT-NOTE: zero, no adjustment for this pair.
+	pub swap_rate_adjustment: SwapRate,
+}
+
+/// Storage version of the module.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum Releases {
+	V1_0_0,
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// Config of a trading pair in a margin pool, applied all at once by `configure_pool`.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone)]
+pub struct MarginPoolTradingPairConfig {
+	/// The trading pair.
+	pub pair: TradingPair,
+
+	/// Bid and ask spread, absolute or relative.
+	pub spread: Spreads,
+
+	/// Spread tiers, sorted by threshold.
+	pub spread_tiers: Vec<SpreadTier>,
+
+	/// Volatility factor, `None` to not adjust spread by volatility.
+	pub volatility_factor: Option<Price>,
+
+	/// Enabled leverages.
+	pub enabled_trades: Leverages,
+
+	/// Is enabled in pool.
+	pub enabled: bool,
 }
 
 pub const MODULE_ID: ModuleId = ModuleId(*b"lami/mlp");
@@ -131,6 +250,26 @@ pub trait Config: frame_system::Config {
 	/// Type used for expressing timestamp.
 	type Moment: AtLeast32Bit + Parameter + Default + Copy + From<u64>;
 
+	/// Minimum number of blocks from now to the wind-down deadline.
+	type MinWindDownPeriod: Get<Self::BlockNumber>;
+
+	/// Leverages of this value or higher require `MinLockedLiquidityForHighLeverage` in the pool.
+	type HighLeverage: Get<u8>;
+
+	/// Maximum number of spread tiers of a trading pair in a pool.
+	type MaxSpreadTiers: Get<u32>;
+
+	/// Maximum number of trading pair configs in a `configure_pool` call.
+	type MaxConfigs: Get<u32>;
+
+	/// Maximum number of swap rate accumulations kept in the history of a trading pair in a pool.
+	/// If lowered, accumulations beyond the new size are no longer read, and stay in storage until
+	/// the pool is removed.
+	type MaxSwapRateHistory: Get<u32>;
+
+	/// Price volatility estimates, to adjust spreads.
+	type VolatilityProvider: VolatilityProvider;
+
 	/// Weight information for the extrinsics in this module.
 	type WeightInfo: WeightInfo;
 }
@@ -151,11 +290,36 @@ decl_storage! {
 		/// The accumulated swap rate of trading pairs in liquidity pools.
 		pub AccumulatedSwapRates get(fn accumulated_swap_rate): double_map hasher(twox_64_concat) LiquidityPoolId, hasher(twox_64_concat) TradingPair => SwapRate;
 
+		/// Recent swap rate accumulations of trading pairs in liquidity pools, as ring buffers of
+		/// `MaxSwapRateHistory` size keyed by `(pair, index)`.
+		///
+		/// Getter is implemented manually to return accumulations in order.
+		pub SwapRateHistory: double_map hasher(twox_64_concat) LiquidityPoolId, hasher(twox_64_concat) (TradingPair, u32) => Option<SwapRateAccumulation<T::Moment>>;
+
+		/// Index in `SwapRateHistory` of the next swap rate accumulation of trading pairs in
+		/// liquidity pools.
+		pub SwapRateHistoryHead get(fn swap_rate_history_head): double_map hasher(twox_64_concat) LiquidityPoolId, hasher(twox_64_concat) TradingPair => u32;
+
 		/// The default minimum leveraged amount allowed to open a position.
 		pub DefaultMinLeveragedAmount get(fn default_min_leveraged_amount) config(): Balance;
 
 		/// The last time swap rate was accumulated.
 		pub LastAccumulateTime get(fn last_accumulate_time): T::Moment;
+
+		/// Wind-down deadlines of winding down liquidity pools, after which remaining positions could be force
+		/// closed at oracle price.
+		///
+		/// Returns `None` if no deadline set. Whether a pool is winding down is tracked by its status in base
+		/// liquidity pools.
+		pub WindDownDeadlines get(fn wind_down_deadline): map hasher(twox_64_concat) LiquidityPoolId => Option<T::BlockNumber>;
+
+		/// The minimum locked liquidity of a pool to enable high leverages.
+		///
+		/// DEFAULT-NOTE: zero, no requirement.
+		pub MinLockedLiquidityForHighLeverage get(fn min_locked_liquidity_for_high_leverage): Balance;
+
+		/// Storage version of the module.
+		StorageVersion get(fn storage_version) build(|_: &GenesisConfig<T>| Releases::V2_0_0): Releases;
 	}
 
 	add_extra_genesis {
@@ -167,6 +331,7 @@ decl_storage! {
 					enabled: true,
 					swap_rate: swap_rate.clone(),
 					max_spread: Some(*max_spread),
+					relative_max_spread: None,
 					accumulate_config: Some(accumulate_config.clone()),
 				});
 			})
@@ -178,6 +343,7 @@ decl_event!(
 	pub enum Event<T> where
 		<T as frame_system::Config>::AccountId,
 		<T as Config>::Moment,
+		<T as frame_system::Config>::BlockNumber,
 	{
 		/// Spread set: \[who, pool_id, pair, bid, ask\]
 		SpreadSet(AccountId, LiquidityPoolId, TradingPair, Price, Price),
@@ -217,6 +383,42 @@ decl_event!(
 
 		/// Pool min leveraged amount set: \[pool_id, min_leveraged_amount\]
 		MinLeveragedAmountSet(LiquidityPoolId, Balance),
+
+		/// Pool started winding down: \[pool_id\]
+		LiquidityPoolWindingDown(LiquidityPoolId),
+
+		/// Wind-down deadline set: \[who, pool_id, deadline\]
+		WindDownDeadlineSet(AccountId, LiquidityPoolId, BlockNumber),
+
+		/// Min locked liquidity for high leverage set: \[amount\]
+		MinLockedLiquidityForHighLeverageSet(Balance),
+
+		/// Relative spread set: \[who, pool_id, pair, bid_bps, ask_bps\]
+		RelativeSpreadSet(AccountId, LiquidityPoolId, TradingPair, SpreadBps, SpreadBps),
+
+		/// Relative max spread updated: \[pair, spread_bps\]
+		RelativeMaxSpreadUpdated(TradingPair, Option<SpreadBps>),
+
+		/// Spread tiers set: \[who, pool_id, pair, tiers\]
+		SpreadTiersSet(AccountId, LiquidityPoolId, TradingPair, Vec<SpreadTier>),
+
+		/// Volatility factor set: \[who, pool_id, pair, factor\]
+		VolatilityFactorSet(AccountId, LiquidityPoolId, TradingPair, Option<Price>),
+
+		/// Pool configured: \[who, pool_id, pairs, min_leveraged_amount\]
+		PoolConfigured(AccountId, LiquidityPoolId, Vec<TradingPair>, Option<Balance>),
+
+		/// Trading pair min leveraged amount set: \[who, pool_id, pair, amount\]
+		TradingPairMinLeveragedAmountSet(AccountId, LiquidityPoolId, TradingPair, Balance),
+
+		/// Max leveraged amount set: \[who, pool_id, pair, leverage, amount\]
+		MaxLeveragedAmountSet(AccountId, LiquidityPoolId, TradingPair, Leverage, Option<Balance>),
+
+		/// Leverage bounds set: \[who, pool_id, bounds\]
+		LeverageBoundsSet(AccountId, LiquidityPoolId, Option<LeverageBounds>),
+
+		/// Pool swap rate updated, with the effective swap rate: \[who, pool_id, pair, swap_rate\]
+		PoolSwapRateUpdated(AccountId, LiquidityPoolId, TradingPair, SwapRate),
 	}
 );
 
@@ -226,9 +428,25 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		const MinWindDownPeriod: T::BlockNumber = T::MinWindDownPeriod::get();
+		const HighLeverage: u8 = T::HighLeverage::get();
+		const MaxSpreadTiers: u32 = T::MaxSpreadTiers::get();
+		const MaxConfigs: u32 = T::MaxConfigs::get();
+		const MaxSwapRateHistory: u32 = T::MaxSwapRateHistory::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() == Releases::V1_0_0 {
+				migrations::migrate_to_v2::<T>();
+				StorageVersion::put(Releases::V2_0_0);
+				T::MaximumBlockWeight::get()
+			} else {
+				0
+			}
+		}
+
 		/// Set bid and ask spread for `pair` in `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::set_spread()]
 		pub fn set_spread(origin, #[compact] pool_id: LiquidityPoolId, pair: TradingPair, #[compact] bid: Price, #[compact] ask: Price) {
 			with_transaction_result(|| {
@@ -239,9 +457,82 @@ decl_module! {
 			})?;
 		}
 
+		/// Set bid and ask spread for `pair` in `pool_id`, in basis points of the oracle price.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_relative_spread()]
+		pub fn set_relative_spread(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			pair: TradingPair,
+			#[compact] bid: SpreadBps,
+			#[compact] ask: SpreadBps
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_set_relative_spread(&who, pool_id, pair, bid, ask)?;
+				Self::deposit_event(RawEvent::RelativeSpreadSet(who, pool_id, pair, bid, ask));
+				Ok(())
+			})?;
+		}
+
+		/// Set spread tiers for `pair` in `pool_id`. Tiers must be sorted by threshold, with no
+		/// duplicates.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_spread_tiers(tiers.len() as u32)]
+		pub fn set_spread_tiers(origin, #[compact] pool_id: LiquidityPoolId, pair: TradingPair, tiers: Vec<SpreadTier>) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_set_spread_tiers(&who, pool_id, pair, tiers.clone())?;
+				Self::deposit_event(RawEvent::SpreadTiersSet(who, pool_id, pair, tiers));
+				Ok(())
+			})?;
+		}
+
+		/// Set volatility factor for `pair` in `pool_id`, to add `factor * volatility * price` to
+		/// spreads. `None` to stop adjusting spreads by volatility.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_volatility_factor()]
+		pub fn set_volatility_factor(origin, #[compact] pool_id: LiquidityPoolId, pair: TradingPair, factor: Option<Price>) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(
+					Self::has_role(pool_id, &who, PoolRole::Operator),
+					Error::<T>::NoPermission
+				);
+				ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
+				PoolTradingPairOptions::mutate(pool_id, pair, |o| o.volatility_factor = factor);
+				Self::deposit_event(RawEvent::VolatilityFactorSet(who, pool_id, pair, factor));
+				Ok(())
+			})?;
+		}
+
+		/// Configure `pool_id` atomically: spreads, spread tiers, volatility factor, enabled leverages
+		/// and enabled state of each trading pair in `configs`, and optionally the min leveraged amount.
+		/// Nothing is applied if any config is invalid. At most `MaxConfigs` configs are allowed.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::configure_pool(configs.len() as u32)]
+		pub fn configure_pool(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			configs: Vec<MarginPoolTradingPairConfig>,
+			min_leveraged_amount: Option<Balance>
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_configure_pool(&who, pool_id, &configs, min_leveraged_amount)?;
+				let pairs = configs.into_iter().map(|c| c.pair).collect();
+				Self::deposit_event(RawEvent::PoolConfigured(who, pool_id, pairs, min_leveraged_amount));
+				Ok(())
+			})?;
+		}
+
 		/// Set enabled leverages for `pair` in `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::set_enabled_leverages()]
 		pub fn set_enabled_leverages(origin, #[compact] pool_id: LiquidityPoolId, pair: TradingPair, enabled: Leverages) {
 			with_transaction_result(|| {
@@ -273,12 +564,12 @@ decl_module! {
 
 		/// Set additional swap rate for `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::set_additional_swap_rate()]
 		pub fn set_additional_swap_rate(origin, #[compact] pool_id: LiquidityPoolId, rate: FixedI128) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				ensure!(Self::is_owner(pool_id, &who), Error::<T>::NoPermission);
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Operator), Error::<T>::NoPermission);
 
 				PoolOptions::mutate(&pool_id, |o| o.additional_swap_rate = rate);
 
@@ -301,6 +592,22 @@ decl_module! {
 			})?;
 		}
 
+		/// Set maximum spread for `pair`, in basis points of the oracle price, or remove it if `None`.
+		///
+		/// May only be called from `UpdateOrigin`.
+		#[weight = T::WeightInfo::set_relative_max_spread()]
+		pub fn set_relative_max_spread(origin, pair: TradingPair, max_spread: Option<SpreadBps>) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				if let Some(max_spread) = max_spread {
+					ensure!(max_spread < BPS_PER_UNIT, Error::<T>::RelativeSpreadTooHigh);
+				}
+				<TradingPairOptions<T>>::mutate(&pair, |o| o.relative_max_spread = max_spread);
+				Self::deposit_event(RawEvent::RelativeMaxSpreadUpdated(pair, max_spread));
+				Ok(())
+			})?;
+		}
+
 		/// Set swap rate accumulation configuration.
 		///
 		/// May only be called from `UpdateOrigin`.
@@ -350,13 +657,14 @@ decl_module! {
 
 		/// Enable `pair` in `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::liquidity_pool_enable_trading_pair()]
 		pub fn liquidity_pool_enable_trading_pair(origin, #[compact] pool_id: LiquidityPoolId, pair: TradingPair) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				ensure!(Self::is_owner(pool_id, &who), Error::<T>::NoPermission);
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Operator), Error::<T>::NoPermission);
 				ensure!(Self::is_trading_pair_enabled(pair), Error::<T>::TradingPairNotEnabled);
+				ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
 
 				<T::PoolManager as MarginProtocolLiquidityPoolsManager>::ensure_can_enable_trading_pair(pool_id, pair)?;
 
@@ -370,12 +678,12 @@ decl_module! {
 
 		/// Disable `pair` in `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::liquidity_pool_disable_trading_pair()]
 		pub fn liquidity_pool_disable_trading_pair(origin, #[compact] pool_id: LiquidityPoolId, pair: TradingPair) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				ensure!(Self::is_owner(pool_id, &who), Error::<T>::NoPermission);
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Operator), Error::<T>::NoPermission);
 
 				PoolTradingPairOptions::mutate(&pool_id, &pair, |o| o.enabled = false);
 
@@ -398,14 +706,24 @@ decl_module! {
 			})?;
 		}
 
+		/// Set the minimum locked liquidity of a pool to enable leverages of `HighLeverage` or higher.
+		///
+		/// May only be called from `UpdateOrigin`.
+		#[weight = T::WeightInfo::set_min_locked_liquidity_for_high_leverage()]
+		pub fn set_min_locked_liquidity_for_high_leverage(origin, #[compact] amount: Balance) {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			MinLockedLiquidityForHighLeverage::put(amount);
+			Self::deposit_event(RawEvent::MinLockedLiquidityForHighLeverageSet(amount));
+		}
+
 		/// Set minimum leveraged amount to open a position in `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::set_min_leveraged_amount()]
 		pub fn set_min_leveraged_amount(origin, #[compact] pool_id: LiquidityPoolId, #[compact] amount: Balance) {
 			with_transaction_result(|| {
 				let who = ensure_signed(origin)?;
-				ensure!(Self::is_owner(pool_id, &who), Error::<T>::NoPermission);
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Operator), Error::<T>::NoPermission);
 
 				PoolOptions::mutate(&pool_id, |o| o.min_leveraged_amount = amount);
 
@@ -415,6 +733,132 @@ decl_module! {
 			})?;
 		}
 
+		/// Set minimum leveraged amount to open a position of `pair` in `pool_id`.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_trading_pair_min_leveraged_amount()]
+		pub fn set_trading_pair_min_leveraged_amount(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			pair: TradingPair,
+			#[compact] amount: Balance
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Operator), Error::<T>::NoPermission);
+				ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
+
+				PoolTradingPairOptions::mutate(pool_id, pair, |o| o.min_leveraged_amount = amount);
+
+				Self::deposit_event(RawEvent::TradingPairMinLeveragedAmountSet(who, pool_id, pair, amount));
+
+				Ok(())
+			})?;
+		}
+
+		/// Set maximum leveraged amount to open a position of `pair` with `leverage` in `pool_id`,
+		/// or remove the limit if `None`.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_max_leveraged_amount()]
+		pub fn set_max_leveraged_amount(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			pair: TradingPair,
+			leverage: Leverage,
+			amount: Option<Balance>
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Operator), Error::<T>::NoPermission);
+				ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
+
+				PoolTradingPairOptions::mutate(pool_id, pair, |o| {
+					o.max_leveraged_amounts.retain(|(l, _)| *l != leverage);
+					if let Some(amount) = amount {
+						o.max_leveraged_amounts.push((leverage, amount));
+					}
+				});
+
+				Self::deposit_event(RawEvent::MaxLeveragedAmountSet(who, pool_id, pair, leverage, amount));
+
+				Ok(())
+			})?;
+		}
+
+		/// Set leverage bounds of `pool_id`, to allow leverages of any multiplier within bounds, or
+		/// only enabled fixed leverages if `None`.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_leverage_bounds()]
+		pub fn set_leverage_bounds(origin, #[compact] pool_id: LiquidityPoolId, bounds: Option<LeverageBounds>) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Operator), Error::<T>::NoPermission);
+
+				if let Some(bounds) = bounds {
+					ensure!(bounds.min > 0 && bounds.min <= bounds.max, Error::<T>::InvalidLeverageBounds);
+					ensure!(
+						bounds.max < T::HighLeverage::get() || Self::has_enough_locked_liquidity(pool_id),
+						Error::<T>::NotEnoughLockedLiquidity
+					);
+				}
+				PoolOptions::mutate(pool_id, |o| o.leverage_bounds = bounds);
+
+				Self::deposit_event(RawEvent::LeverageBoundsSet(who, pool_id, bounds));
+
+				Ok(())
+			})?;
+		}
+
+		/// Set swap rate adjustment of `pair` in `pool_id`, added to the trading pair's swap rate
+		/// adjusted by the pool's additional swap rate. Zero adjustment to remove.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_pool_swap_rate()]
+		pub fn set_pool_swap_rate(origin, #[compact] pool_id: LiquidityPoolId, pair: TradingPair, adjustment: SwapRate) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(Self::has_role(pool_id, &who, PoolRole::Operator), Error::<T>::NoPermission);
+				ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
+
+				ensure!(adjustment.long.saturating_abs() <= T::MaxSwapRate::get(), Error::<T>::SwapRateTooHigh);
+				ensure!(adjustment.short.saturating_abs() <= T::MaxSwapRate::get(), Error::<T>::SwapRateTooHigh);
+				PoolTradingPairOptions::mutate(pool_id, pair, |o| o.swap_rate_adjustment = adjustment);
+
+				let swap_rate = Self::pool_swap_rate(pool_id, pair);
+				Self::deposit_event(RawEvent::PoolSwapRateUpdated(who, pool_id, pair, swap_rate));
+
+				Ok(())
+			})?;
+		}
+
+		/// Set the wind-down deadline of `pool_id`, after which remaining positions could be force closed at
+		/// oracle price.
+		///
+		/// May only be called from the pool owner, when the pool is winding down. The deadline must be at least
+		/// `MinWindDownPeriod` blocks from now.
+		#[weight = T::WeightInfo::set_wind_down_deadline()]
+		pub fn set_wind_down_deadline(origin, #[compact] pool_id: LiquidityPoolId, deadline: T::BlockNumber) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(Self::is_owner(pool_id, &who), Error::<T>::NoPermission);
+
+				ensure!(Self::is_winding_down(pool_id), Error::<T>::PoolNotWindingDown);
+				let now = <frame_system::Module<T>>::block_number();
+				ensure!(
+					deadline >= now.saturating_add(T::MinWindDownPeriod::get()),
+					Error::<T>::WindDownDeadlineTooEarly
+				);
+
+				<WindDownDeadlines<T>>::insert(pool_id, deadline);
+
+				Self::deposit_event(RawEvent::WindDownDeadlineSet(who, pool_id, deadline));
+
+				Ok(())
+			})?;
+		}
+
 		fn on_initialize() -> Weight {
 			let now_as_mins: T::Moment = (T::UnixTime::now().as_secs() / ONE_MINUTE).into();
 			// Truncate seconds, keep minutes
@@ -432,12 +876,12 @@ decl_module! {
 						&& now_as_mins % frequency_as_mins == offset_as_mins
 						&& <LastAccumulateTime<T>>::get() != now_as_secs
 					{
-						write_count += 1;
 						<LastAccumulateTime<T>>::set(now_as_secs);
-						Self::accumulate_rates(pair);
+						write_count += Self::accumulate_rates(pair, now_as_secs);
 					}
 				}
 			});
+			// `read_count` pairs to accumulate, `write_count` swap rate accumulations in pools
 			T::WeightInfo::on_initialize(read_count, write_count)
 		}
 	}
@@ -457,6 +901,39 @@ decl_error! {
 
 		/// `frequency` of accumulate config is too low.
 		FrequencyTooLow,
+
+		/// Pool is winding down.
+		PoolWindingDown,
+
+		/// Pool is not winding down.
+		PoolNotWindingDown,
+
+		/// Wind-down deadline is too early.
+		WindDownDeadlineTooEarly,
+
+		/// Not enough locked liquidity in pool to enable high leverages.
+		NotEnoughLockedLiquidity,
+
+		/// Relative spread must be lower than `100%`.
+		RelativeSpreadTooHigh,
+
+		/// Too many spread tiers.
+		TooManySpreadTiers,
+
+		/// Spread tiers are not sorted by threshold.
+		InvalidSpreadTiers,
+
+		/// Spread is higher than max allowed.
+		SpreadTooHigh,
+
+		/// A trading pair is configured more than once.
+		DuplicatedTradingPair,
+
+		/// Leverage bounds must be non-zero, and min no higher than max.
+		InvalidLeverageBounds,
+
+		/// Too many configs in `configure_pool`.
+		TooManyConfigs,
 	}
 }
 
@@ -468,10 +945,56 @@ impl<T: Config> Module<T> {
 		Self::trading_pair_options(pair).max_spread
 	}
 
+	/// The lower one of max spread and relative max spread at oracle `price`.
+	pub fn max_spread_at_price(pair: TradingPair, price: Price) -> Option<Price> {
+		let option = Self::trading_pair_options(pair);
+		let relative_max_spread = option.relative_max_spread.map(|bps| fixed_u128_from_bps(price, bps));
+		match (option.max_spread, relative_max_spread) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		}
+	}
+
 	pub fn accumulate_config(pair: TradingPair) -> Option<AccumulateConfig<T::Moment>> {
 		Self::trading_pair_options(pair).accumulate_config
 	}
 
+	/// The next time swap rates of `pair` would be accumulated, truncated to minutes. `None` if
+	/// never.
+	pub fn next_accumulate_time(pair: TradingPair) -> Option<T::Moment> {
+		let accumulate_config = Self::accumulate_config(pair)?;
+		let frequency_as_mins = accumulate_config.frequency / ONE_MINUTE.into();
+		let offset_as_mins = accumulate_config.offset / ONE_MINUTE.into();
+		if frequency_as_mins.is_zero() || offset_as_mins >= frequency_as_mins {
+			return None;
+		}
+
+		let now_as_mins: T::Moment = (T::UnixTime::now().as_secs() / ONE_MINUTE).into();
+		let mut next_as_mins = now_as_mins - now_as_mins % frequency_as_mins + offset_as_mins;
+		// skip if passed or already accumulated in this minute
+		if next_as_mins < now_as_mins
+			|| (next_as_mins == now_as_mins && <LastAccumulateTime<T>>::get() == now_as_mins * ONE_MINUTE.into())
+		{
+			next_as_mins += frequency_as_mins;
+		}
+		Some(next_as_mins * ONE_MINUTE.into())
+	}
+
+	/// Recent swap rate accumulations of `pair` in `pool_id`, oldest first. Only the latest
+	/// `MaxSwapRateHistory` ones are kept.
+	pub fn swap_rate_history(pool_id: LiquidityPoolId, pair: TradingPair) -> Vec<SwapRateAccumulation<T::Moment>> {
+		let max_history = T::MaxSwapRateHistory::get();
+		if max_history == 0 {
+			return vec![];
+		}
+		// entries from the head on are the oldest, if the buffer has wrapped around
+		let head = Self::swap_rate_history_head(pool_id, pair) % max_history;
+		(head..max_history)
+			.chain(0..head)
+			.filter_map(|index| <SwapRateHistory<T>>::get(pool_id, (pair, index)))
+			.collect()
+	}
+
 	pub fn swap_rate_of_pair(pair: TradingPair) -> SwapRate {
 		Self::trading_pair_options(pair).swap_rate
 	}
@@ -486,6 +1009,40 @@ impl<T: Config> Module<T> {
 		Self::pool_options(pool_id).additional_swap_rate
 	}
 
+	/// The effective swap rate of `pair` in `pool_id`, capped by `MaxSwapRate`.
+	///
+	/// The trading pair's swap rate adjusted by the pool's additional swap rate, plus the pool's swap
+	/// rate adjustment of the pair.
+	pub fn pool_swap_rate(pool_id: LiquidityPoolId, pair: TradingPair) -> SwapRate {
+		let swap_rate = Self::swap_rate_of_pair(pair);
+		let additional_swap_rate = Self::additional_swap_rate(pool_id);
+		let adjustment = Self::pool_trading_pair_options(pool_id, pair).swap_rate_adjustment;
+		// adjust_swap = swap - abs(swap) * additional_swap_rate + adjustment
+		let adjust = |rate: FixedI128, adjustment: FixedI128| {
+			rate.saturating_sub(rate.saturating_abs().saturating_mul(additional_swap_rate))
+				.saturating_add(adjustment)
+		};
+		let swap_rate = SwapRate {
+			long: adjust(swap_rate.long, adjustment.long),
+			short: adjust(swap_rate.short, adjustment.short),
+		};
+
+		let max_swap = T::MaxSwapRate::get();
+		let cap = |rate: FixedI128| {
+			if rate.saturating_abs() <= max_swap {
+				rate
+			} else if rate.is_positive() {
+				max_swap
+			} else {
+				fixed_i128_mul_signum(max_swap, -1)
+			}
+		};
+		SwapRate {
+			long: cap(swap_rate.long),
+			short: cap(swap_rate.short),
+		}
+	}
+
 	/// Min leveraged amount. `max(min_leveraged_amount, default_min_leveraged_amount)` will be
 	/// used.
 	pub fn min_leveraged_amount(pool_id: LiquidityPoolId) -> Balance {
@@ -495,6 +1052,22 @@ impl<T: Config> Module<T> {
 
 	// Pool trading pair margin option
 
+	/// Max leveraged amount to open a position of `pair` with `leverage` in `pool_id`, or `None`
+	/// if no limit. The limit of the highest fixed leverage of the same side, no higher than
+	/// `leverage`, applies.
+	pub fn max_leveraged_amount(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		leverage: PositionLeverage,
+	) -> Option<Balance> {
+		PoolTradingPairOptions::get(pool_id, pair)
+			.max_leveraged_amounts
+			.into_iter()
+			.filter(|(l, _)| l.is_long() == leverage.is_long() && l.value() <= leverage.value())
+			.max_by_key(|(l, _)| l.value())
+			.map(|(_, amount)| amount)
+	}
+
 	/// `PoolTradingPairOptions` getter. Bid/ask spread is capped by max spread.
 	pub fn pool_trading_pair_options(pool_id: LiquidityPoolId, pair: TradingPair) -> MarginPoolTradingPairOption {
 		let mut option = PoolTradingPairOptions::get(pool_id, pair);
@@ -505,6 +1078,121 @@ impl<T: Config> Module<T> {
 		option
 	}
 
+	/// Fixed leverages allowed for `pair` in `pool_id`, either enabled or within leverage bounds
+	/// of the pool.
+	pub fn enabled_leverages(pool_id: LiquidityPoolId, pair: TradingPair) -> Vec<Leverage> {
+		let enabled = PoolTradingPairOptions::get(pool_id, pair).enabled_trades;
+		let bounds = Self::pool_options(pool_id).leverage_bounds;
+		(0u8..16)
+			.filter_map(|index| Leverage::decode(&mut &[index][..]).ok())
+			.filter(|leverage| enabled.contains(*leverage) || bounds.map_or(false, |b| b.contains(leverage.value())))
+			.collect()
+	}
+
+	/// Trading pairs enabled in `pool_id` and open for trading.
+	pub fn enabled_trading_pairs_of_pool(pool_id: LiquidityPoolId) -> Vec<TradingPair> {
+		PoolTradingPairOptions::iter_prefix(pool_id)
+			.filter(|(pair, option)| option.enabled && Self::is_trading_pair_enabled(*pair))
+			.map(|(pair, _)| pair)
+			.collect()
+	}
+
+	/// Active pools with `pair` enabled and open for trading.
+	pub fn pools_of_trading_pair(pair: TradingPair) -> Vec<LiquidityPoolId> {
+		if !Self::is_trading_pair_enabled(pair) {
+			return vec![];
+		}
+		T::BaseLiquidityPools::pools_with_status(PoolStatus::Active)
+			.into_iter()
+			.filter(|pool_id| PoolTradingPairOptions::get(pool_id, pair).enabled)
+			.collect()
+	}
+
+	/// Spread add-on of `leveraged_amount` trades by `tiers`, in basis points.
+	pub fn spread_add_on(tiers: &[SpreadTier], leveraged_amount: Balance) -> SpreadBps {
+		tiers
+			.iter()
+			.rev()
+			.find(|t| leveraged_amount >= t.threshold)
+			.map_or(0, |t| t.add_on)
+	}
+
+	/// Volatility of `pair` price, the sum of base and quote currency volatility. Untracked
+	/// currencies are taken as zero.
+	pub fn pair_volatility(pair: TradingPair) -> Price {
+		let base = T::VolatilityProvider::volatility(pair.base).unwrap_or_else(Zero::zero);
+		let quote = T::VolatilityProvider::volatility(pair.quote).unwrap_or_else(Zero::zero);
+		base.saturating_add(quote)
+	}
+
+	/// Bid and ask spread of `pair` in `pool_id` at oracle `price` for `leveraged_amount` trades,
+	/// capped by max spread.
+	pub fn spreads_at_price(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		price: Price,
+		leveraged_amount: Balance,
+	) -> (Option<Price>, Option<Price>) {
+		let option = PoolTradingPairOptions::get(pool_id, pair);
+		let (bid_spread, ask_spread) = match option.spread_mode {
+			SpreadMode::Absolute => (option.bid_spread, option.ask_spread),
+			SpreadMode::Relative => (
+				option.relative_bid_spread.map(|bps| fixed_u128_from_bps(price, bps)),
+				option.relative_ask_spread.map(|bps| fixed_u128_from_bps(price, bps)),
+			),
+		};
+		let tier_add_on = fixed_u128_from_bps(price, Self::spread_add_on(&option.spread_tiers, leveraged_amount));
+		let volatility_add_on = option.volatility_factor.map_or_else(Zero::zero, |k| {
+			k.saturating_mul(Self::pair_volatility(pair)).saturating_mul(price)
+		});
+		let add_on = tier_add_on.saturating_add(volatility_add_on);
+		let (bid_spread, ask_spread) = (
+			bid_spread.map(|s| s.saturating_add(add_on)),
+			ask_spread.map(|s| s.saturating_add(add_on)),
+		);
+		match Self::max_spread_at_price(pair, price) {
+			Some(max_spread) => (
+				bid_spread.map(|s| s.min(max_spread)),
+				ask_spread.map(|s| s.min(max_spread)),
+			),
+			None => (bid_spread, ask_spread),
+		}
+	}
+
+	/// Bid and ask spread of `pair` in `pool_id` used in trading. Zero spread after wind-down
+	/// deadline, to close remaining positions at oracle price.
+	pub fn effective_spreads(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		price: Price,
+		leveraged_amount: Balance,
+	) -> (Option<Price>, Option<Price>) {
+		if Self::is_wind_down_expired(pool_id) {
+			return (Some(Price::zero()), Some(Price::zero()));
+		}
+		Self::spreads_at_price(pool_id, pair, price, leveraged_amount)
+	}
+
+	pub fn is_winding_down(pool_id: LiquidityPoolId) -> bool {
+		T::BaseLiquidityPools::status(pool_id) == Some(PoolStatus::WindingDown)
+	}
+
+	pub fn is_high_leverage(leverage: PositionLeverage) -> bool {
+		leverage.value() >= T::HighLeverage::get()
+	}
+
+	/// Return `true` if liquidity locked in `pool_id` is enough for high leverages.
+	pub fn has_enough_locked_liquidity(pool_id: LiquidityPoolId) -> bool {
+		T::BaseLiquidityPools::locked_liquidity(pool_id) >= Self::min_locked_liquidity_for_high_leverage()
+	}
+
+	/// Return `true` if `pool_id` is winding down and the deadline has passed.
+	pub fn is_wind_down_expired(pool_id: LiquidityPoolId) -> bool {
+		Self::is_winding_down(pool_id)
+			&& Self::wind_down_deadline(pool_id)
+				.map_or(false, |deadline| <frame_system::Module<T>>::block_number() >= deadline)
+	}
+
 	pub fn is_pool_trading_pair_enabled(pool_id: LiquidityPoolId, pair: TradingPair) -> bool {
 		PoolTradingPairOptions::get(pool_id, pair).enabled
 	}
@@ -518,6 +1206,18 @@ impl<T: Config> Module<T> {
 			.enabled_trades
 			.contains(leverage)
 	}
+
+	/// Return `true` if `leverage` is an enabled fixed leverage of `pair`, or within leverage
+	/// bounds of `pool_id`.
+	pub fn is_leverage_allowed(pool_id: LiquidityPoolId, pair: TradingPair, leverage: PositionLeverage) -> bool {
+		let is_enabled = leverage
+			.as_leverage()
+			.map_or(false, |l| Self::is_pool_trading_pair_leverage_enabled(pool_id, pair, l));
+		is_enabled
+			|| Self::pool_options(pool_id)
+				.leverage_bounds
+				.map_or(false, |bounds| bounds.contains(leverage.value()))
+	}
 }
 
 impl<T: Config> LiquidityPools<T::AccountId> for Module<T> {
@@ -525,10 +1225,33 @@ impl<T: Config> LiquidityPools<T::AccountId> for Module<T> {
 		T::BaseLiquidityPools::all()
 	}
 
+	fn pools_with_status(status: PoolStatus) -> Vec<LiquidityPoolId> {
+		T::BaseLiquidityPools::pools_with_status(status)
+	}
+
+	fn status(pool_id: LiquidityPoolId) -> Option<PoolStatus> {
+		T::BaseLiquidityPools::status(pool_id)
+	}
+
+	fn settle_wind_down(pool_id: LiquidityPoolId) {
+		T::BaseLiquidityPools::settle_wind_down(pool_id);
+		if !Self::is_winding_down(pool_id) {
+			<WindDownDeadlines<T>>::remove(pool_id);
+		}
+	}
+
 	fn is_owner(pool_id: LiquidityPoolId, who: &T::AccountId) -> bool {
 		T::BaseLiquidityPools::is_owner(pool_id, who)
 	}
 
+	fn has_role(pool_id: LiquidityPoolId, who: &T::AccountId, role: PoolRole) -> bool {
+		T::BaseLiquidityPools::has_role(pool_id, who, role)
+	}
+
+	fn owner(pool_id: LiquidityPoolId) -> Option<T::AccountId> {
+		T::BaseLiquidityPools::owner(pool_id)
+	}
+
 	/// Check if pool exists
 	fn pool_exists(pool_id: LiquidityPoolId) -> bool {
 		T::BaseLiquidityPools::pool_exists(pool_id)
@@ -539,6 +1262,10 @@ impl<T: Config> LiquidityPools<T::AccountId> for Module<T> {
 		T::BaseLiquidityPools::liquidity(pool_id)
 	}
 
+	fn locked_liquidity(pool_id: LiquidityPoolId) -> Balance {
+		T::BaseLiquidityPools::locked_liquidity(pool_id)
+	}
+
 	/// Deposit some amount of collateral to `pool_id`, from `source`.
 	fn deposit_liquidity(source: &T::AccountId, pool_id: LiquidityPoolId, amount: Balance) -> DispatchResult {
 		T::BaseLiquidityPools::deposit_liquidity(source, pool_id, amount)
@@ -551,29 +1278,30 @@ impl<T: Config> LiquidityPools<T::AccountId> for Module<T> {
 }
 
 impl<T: Config> MarginProtocolLiquidityPools<T::AccountId> for Module<T> {
-	fn bid_spread(pool_id: LiquidityPoolId, pair: TradingPair) -> Option<Price> {
-		Self::pool_trading_pair_options(pool_id, pair).bid_spread
+	fn bid_spread(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		price: Price,
+		leveraged_amount: Balance,
+	) -> Option<Price> {
+		Self::effective_spreads(pool_id, pair, price, leveraged_amount).0
 	}
 
-	fn ask_spread(pool_id: LiquidityPoolId, pair: TradingPair) -> Option<Price> {
-		Self::pool_trading_pair_options(pool_id, pair).ask_spread
+	fn ask_spread(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		price: Price,
+		leveraged_amount: Balance,
+	) -> Option<Price> {
+		Self::effective_spreads(pool_id, pair, price, leveraged_amount).1
 	}
 
 	fn swap_rate(pool_id: LiquidityPoolId, pair: TradingPair, is_long: bool) -> FixedI128 {
-		let max_swap = T::MaxSwapRate::get();
-		let swap_rate = Self::swap_rate_of_pair(pair);
-		let additional_swap_rate = Self::additional_swap_rate(pool_id);
-
-		let swap_rate = if is_long { swap_rate.long } else { swap_rate.short };
-		// adjust_swap = swap - abs(swap) * additional_swap_rate
-		let adjust_swap = swap_rate.saturating_sub(swap_rate.saturating_abs().saturating_mul(additional_swap_rate));
-
-		if adjust_swap.saturating_abs() <= max_swap {
-			adjust_swap
-		} else if adjust_swap.is_positive() {
-			max_swap
+		let swap_rate = Self::pool_swap_rate(pool_id, pair);
+		if is_long {
+			swap_rate.long
 		} else {
-			fixed_i128_mul_signum(max_swap, -1)
+			swap_rate.short
 		}
 	}
 
@@ -589,12 +1317,20 @@ impl<T: Config> MarginProtocolLiquidityPools<T::AccountId> for Module<T> {
 	fn ensure_can_open_position(
 		pool_id: LiquidityPoolId,
 		pair: TradingPair,
-		leverage: Leverage,
+		leverage: PositionLeverage,
 		leveraged_amount: Balance,
 	) -> result::Result<(), OpenPositionError> {
-		if !Self::is_pool_trading_pair_leverage_enabled(pool_id, pair, leverage) {
+		match T::BaseLiquidityPools::status(pool_id) {
+			Some(PoolStatus::Active) => {}
+			Some(PoolStatus::WindingDown) => return Err(OpenPositionError::PoolWindingDown),
+			_ => return Err(OpenPositionError::PoolNotActive),
+		}
+		if !Self::is_leverage_allowed(pool_id, pair, leverage) {
 			return Err(OpenPositionError::LeverageNotAllowedInPool);
 		}
+		if Self::is_high_leverage(leverage) && !Self::has_enough_locked_liquidity(pool_id) {
+			return Err(OpenPositionError::NotEnoughLockedLiquidity);
+		}
 		if !Self::is_trading_pair_enabled(pair) {
 			return Err(OpenPositionError::TradingPairNotEnabled);
 		}
@@ -604,6 +1340,12 @@ impl<T: Config> MarginProtocolLiquidityPools<T::AccountId> for Module<T> {
 		if leveraged_amount < Self::min_leveraged_amount(pool_id) {
 			return Err(OpenPositionError::BelowMinLeveragedAmount);
 		}
+		if leveraged_amount < PoolTradingPairOptions::get(pool_id, pair).min_leveraged_amount {
+			return Err(OpenPositionError::BelowTradingPairMinLeveragedAmount);
+		}
+		if Self::max_leveraged_amount(pool_id, pair, leverage).map_or(false, |max| leveraged_amount > max) {
+			return Err(OpenPositionError::AboveMaxLeveragedAmount);
+		}
 		Ok(())
 	}
 }
@@ -617,30 +1359,151 @@ impl<T: Config> Module<T> {
 		bid: Price,
 		ask: Price,
 	) -> DispatchResult {
-		ensure!(Self::is_owner(pool_id, who), Error::<T>::NoPermission);
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
+		// spreads are frozen for closing positions while winding down
+		ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
 		PoolTradingPairOptions::mutate(pool_id, pair, |o| {
+			o.spread_mode = SpreadMode::Absolute;
 			o.bid_spread = Some(bid);
 			o.ask_spread = Some(ask);
 		});
 		Ok(())
 	}
 
+	fn do_set_relative_spread(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		bid: SpreadBps,
+		ask: SpreadBps,
+	) -> DispatchResult {
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
+		ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
+		ensure!(
+			bid < BPS_PER_UNIT && ask < BPS_PER_UNIT,
+			Error::<T>::RelativeSpreadTooHigh
+		);
+		PoolTradingPairOptions::mutate(pool_id, pair, |o| {
+			o.spread_mode = SpreadMode::Relative;
+			o.relative_bid_spread = Some(bid);
+			o.relative_ask_spread = Some(ask);
+		});
+		Ok(())
+	}
+
+	fn do_set_spread_tiers(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		tiers: Vec<SpreadTier>,
+	) -> DispatchResult {
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
+		ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
+		ensure!(
+			tiers.len() as u32 <= T::MaxSpreadTiers::get(),
+			Error::<T>::TooManySpreadTiers
+		);
+		ensure!(
+			tiers.windows(2).all(|w| w[0].threshold < w[1].threshold),
+			Error::<T>::InvalidSpreadTiers
+		);
+		ensure!(
+			tiers.iter().all(|t| t.add_on < BPS_PER_UNIT),
+			Error::<T>::RelativeSpreadTooHigh
+		);
+		PoolTradingPairOptions::mutate(pool_id, pair, |o| o.spread_tiers = tiers);
+		Ok(())
+	}
+
 	fn do_set_enabled_leverages(
 		who: &T::AccountId,
 		pool_id: LiquidityPoolId,
 		pair: TradingPair,
 		enabled: Leverages,
 	) -> DispatchResult {
-		ensure!(Self::is_owner(pool_id, who), Error::<T>::NoPermission);
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
+		let has_high_leverage = (0u8..16)
+			.filter_map(|index| Leverage::decode(&mut &[index][..]).ok())
+			.any(|leverage| enabled.contains(leverage) && Self::is_high_leverage(leverage.into()));
+		ensure!(
+			!has_high_leverage || Self::has_enough_locked_liquidity(pool_id),
+			Error::<T>::NotEnoughLockedLiquidity
+		);
 		PoolTradingPairOptions::mutate(pool_id, pair, |o| o.enabled_trades = enabled);
 		Ok(())
 	}
+
+	fn do_configure_pool(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		configs: &[MarginPoolTradingPairConfig],
+		min_leveraged_amount: Option<Balance>,
+	) -> DispatchResult {
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
+		ensure!(!Self::is_winding_down(pool_id), Error::<T>::PoolWindingDown);
+
+		ensure!(configs.len() as u32 <= T::MaxConfigs::get(), Error::<T>::TooManyConfigs);
+
+		let mut pairs = BTreeSet::new();
+		for config in configs {
+			let pair = config.pair;
+			ensure!(pairs.insert(pair), Error::<T>::DuplicatedTradingPair);
+			if config.enabled {
+				ensure!(Self::is_trading_pair_enabled(pair), Error::<T>::TradingPairNotEnabled);
+				<T::PoolManager as MarginProtocolLiquidityPoolsManager>::ensure_can_enable_trading_pair(pool_id, pair)?;
+			}
+
+			match config.spread {
+				Spreads::Absolute { bid, ask } => {
+					if let Some(max_spread) = Self::max_spread(pair) {
+						ensure!(bid <= max_spread && ask <= max_spread, Error::<T>::SpreadTooHigh);
+					}
+					Self::do_set_spread(who, pool_id, pair, bid, ask)?;
+				}
+				Spreads::Relative { bid, ask } => Self::do_set_relative_spread(who, pool_id, pair, bid, ask)?,
+			}
+			Self::do_set_spread_tiers(who, pool_id, pair, config.spread_tiers.clone())?;
+			Self::do_set_enabled_leverages(who, pool_id, pair, config.enabled_trades)?;
+			PoolTradingPairOptions::mutate(pool_id, pair, |o| {
+				o.volatility_factor = config.volatility_factor;
+				o.enabled = config.enabled;
+			});
+		}
+
+		if let Some(amount) = min_leveraged_amount {
+			PoolOptions::mutate(pool_id, |o| o.min_leveraged_amount = amount);
+		}
+
+		Ok(())
+	}
 }
 
 // Private methods
 impl<T: Config> Module<T> {
-	fn accumulate_rates(pair: TradingPair) {
-		for pool_id in T::BaseLiquidityPools::all() {
+	/// Accumulate swap rates of `pair` in live pools, return the count of pools accumulated.
+	fn accumulate_rates(pair: TradingPair, now: T::Moment) -> u32 {
+		// disabled and removed pools have no opened positions to accumulate swap rates for
+		let live_pools = T::BaseLiquidityPools::pools_with_status(PoolStatus::Active)
+			.into_iter()
+			.chain(T::BaseLiquidityPools::pools_with_status(PoolStatus::WindingDown));
+		let mut count = 0;
+		for pool_id in live_pools {
+			count += 1;
 			let long_rate = Self::swap_rate(pool_id, pair, true);
 			let short_rate = Self::swap_rate(pool_id, pair, false);
 
@@ -649,14 +1512,43 @@ impl<T: Config> Module<T> {
 			accumulated.short = accumulated.short.saturating_add(short_rate);
 			AccumulatedSwapRates::insert(pool_id, pair, accumulated.clone());
 
+			let max_history = T::MaxSwapRateHistory::get();
+			if max_history > 0 {
+				let index = Self::swap_rate_history_head(pool_id, pair) % max_history;
+				<SwapRateHistory<T>>::insert(
+					pool_id,
+					(pair, index),
+					SwapRateAccumulation {
+						time: now,
+						rate: SwapRate {
+							long: long_rate,
+							short: short_rate,
+						},
+						accumulated: accumulated.clone(),
+					},
+				);
+				SwapRateHistoryHead::insert(pool_id, pair, (index + 1) % max_history);
+			}
+
 			Self::deposit_event(RawEvent::AccumulatedSwapRateUpdated(pool_id, pair, accumulated))
 		}
+		count
 	}
 }
 
 impl<T: Config> OnDisableLiquidityPool for Module<T> {
+	/// Trading pairs are disabled in the pool, while spreads are kept for closing positions if winding down.
 	fn on_disable(pool_id: LiquidityPoolId) {
-		PoolTradingPairOptions::remove_prefix(&pool_id);
+		let pairs: Vec<TradingPair> = PoolTradingPairOptions::iter_prefix(pool_id)
+			.map(|(pair, _)| pair)
+			.collect();
+		pairs
+			.into_iter()
+			.for_each(|pair| PoolTradingPairOptions::mutate(pool_id, pair, |o| o.enabled = false));
+
+		if Self::is_winding_down(pool_id) {
+			Self::deposit_event(RawEvent::LiquidityPoolWindingDown(pool_id));
+		}
 	}
 }
 
@@ -664,6 +1556,9 @@ impl<T: Config> OnRemoveLiquidityPool for Module<T> {
 	fn on_remove(pool_id: LiquidityPoolId) {
 		PoolTradingPairOptions::remove_prefix(&pool_id);
 		AccumulatedSwapRates::remove_prefix(&pool_id);
+		<SwapRateHistory<T>>::remove_prefix(&pool_id);
+		SwapRateHistoryHead::remove_prefix(&pool_id);
 		PoolOptions::remove(&pool_id);
+		<WindDownDeadlines<T>>::remove(&pool_id);
 	}
 }
@@ -0,0 +1,75 @@
+//! Storage migrations.
+
+use super::*;
+
+/// `MarginTradingPairOption` in `V1_0_0`.
+#[derive(Decode)]
+#[allow(dead_code)]
+pub struct OldMarginTradingPairOption<Moment> {
+	pub enabled: bool,
+	pub max_spread: Option<Price>,
+	pub swap_rate: SwapRate,
+	pub accumulate_config: Option<AccumulateConfig<Moment>>,
+}
+
+/// `MarginPoolOption` in `V1_0_0`.
+#[derive(Decode)]
+#[allow(dead_code)]
+pub struct OldMarginPoolOption {
+	pub additional_swap_rate: FixedI128,
+	pub min_leveraged_amount: Balance,
+}
+
+/// `MarginPoolTradingPairOption` in `V1_0_0`.
+#[derive(Decode)]
+#[allow(dead_code)]
+pub struct OldMarginPoolTradingPairOption {
+	pub enabled: bool,
+	pub bid_spread: Option<Price>,
+	pub ask_spread: Option<Price>,
+	pub enabled_trades: Leverages,
+}
+
+/// Migrate storage from `V1_0_0` to `V2_0_0`.
+///
+/// - Trading pair options gained `relative_max_spread`, which is not set.
+/// - Pool options gained `leverage_bounds`, which is not set.
+/// - Pool trading pair options gained relative spreads, in absolute spread mode, and spread tiers,
+///   volatility factor, min leveraged amount, max leveraged amounts and swap rate adjustment, which
+///   are not set.
+pub fn migrate_to_v2<T: Config>() {
+	<TradingPairOptions<T>>::translate::<OldMarginTradingPairOption<T::Moment>, _>(|_, old| {
+		Some(MarginTradingPairOption {
+			enabled: old.enabled,
+			max_spread: old.max_spread,
+			relative_max_spread: None,
+			swap_rate: old.swap_rate,
+			accumulate_config: old.accumulate_config,
+		})
+	});
+
+	PoolOptions::translate::<OldMarginPoolOption, _>(|_, old| {
+		Some(MarginPoolOption {
+			additional_swap_rate: old.additional_swap_rate,
+			min_leveraged_amount: old.min_leveraged_amount,
+			leverage_bounds: None,
+		})
+	});
+
+	PoolTradingPairOptions::translate::<OldMarginPoolTradingPairOption, _>(|_, _, old| {
+		Some(MarginPoolTradingPairOption {
+			enabled: old.enabled,
+			bid_spread: old.bid_spread,
+			ask_spread: old.ask_spread,
+			spread_mode: SpreadMode::Absolute,
+			relative_bid_spread: None,
+			relative_ask_spread: None,
+			spread_tiers: vec![],
+			volatility_factor: None,
+			min_leveraged_amount: Zero::zero(),
+			max_leveraged_amounts: vec![],
+			enabled_trades: old.enabled_trades,
+			swap_rate_adjustment: Default::default(),
+		})
+	});
+}
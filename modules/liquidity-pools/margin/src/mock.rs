@@ -15,7 +15,9 @@ use orml_currencies::Currency;
 use orml_traits::parameter_type_with_key;
 
 use primitives::{Balance, CurrencyId, LiquidityPoolId};
-use traits::{BaseLiquidityPoolManager, MarginProtocolLiquidityPoolsManager};
+use traits::{BaseLiquidityPoolManager, MarginProtocolLiquidityPoolsManager, VolatilityProvider};
+
+use std::cell::RefCell;
 
 pub type BlockNumber = u64;
 pub type AccountId = u64;
@@ -75,6 +77,11 @@ parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = CurrencyId::LAMI;
 	pub const GetLiquidityCurrencyId: CurrencyId = CurrencyId::AUSD;
 	pub MaxSwap: FixedI128 = FixedI128::saturating_from_integer(2);
+	pub const MinWindDownPeriod: BlockNumber = 10;
+	pub const HighLeverage: u8 = 50;
+	pub const MaxSpreadTiers: u32 = 3;
+	pub const MaxConfigs: u32 = 2;
+	pub const MaxSwapRateHistory: u32 = 2;
 }
 
 impl pallet_balances::Config for Runtime {
@@ -119,10 +126,19 @@ impl orml_tokens::Config for Runtime {
 	type OnDust = orml_tokens::TransferDust<Runtime, TreasuryAccount>;
 }
 
+thread_local! {
+	static CAN_REMOVE: RefCell<bool> = RefCell::new(true);
+}
+
 pub struct PoolManager;
+impl PoolManager {
+	pub fn set_can_remove(can_remove: bool) {
+		CAN_REMOVE.with(|v| *v.borrow_mut() = can_remove);
+	}
+}
 impl BaseLiquidityPoolManager<LiquidityPoolId, Balance> for PoolManager {
 	fn can_remove(_pool_id: LiquidityPoolId) -> bool {
-		true
+		CAN_REMOVE.with(|v| *v.borrow())
 	}
 	fn ensure_can_withdraw(_pool: LiquidityPoolId, _amount: Balance) -> DispatchResult {
 		Ok(())
@@ -132,6 +148,8 @@ impl BaseLiquidityPoolManager<LiquidityPoolId, Balance> for PoolManager {
 parameter_types! {
 	pub const MarginLiquidityPoolsModuleId: ModuleId = MODULE_ID;
 	pub const IdentityDeposit: Balance = 1000;
+	pub const TransferExpiry: BlockNumber = 10;
+	pub const MaxPendingWithdrawals: u32 = 3;
 }
 
 pub type MarginInstance = module_base_liquidity_pools::Instance1;
@@ -143,10 +161,14 @@ impl module_base_liquidity_pools::Config<MarginInstance> for Runtime {
 	type ExistentialDeposit = ExistentialDeposit;
 	type IdentityDeposit = IdentityDeposit;
 	type IdentityDepositCurrency = pallet_balances::Module<Self>;
+	type TransferExpiry = TransferExpiry;
+	type MaxPendingWithdrawals = MaxPendingWithdrawals;
 	type ModuleId = MarginLiquidityPoolsModuleId;
 	type OnDisableLiquidityPool = ModuleLiquidityPools;
 	type OnRemoveLiquidityPool = ModuleLiquidityPools;
 	type UpdateOrigin = EnsureSignedBy<UpdateOrigin, AccountId>;
+	type ShareCurrency = orml_tokens::Module<Runtime>;
+	type ShareCurrencyId = module_base_liquidity_pools::NoShareCurrency;
 	type WeightInfo = ();
 }
 pub type BaseLiquidityPools = module_base_liquidity_pools::Module<Runtime, MarginInstance>;
@@ -169,6 +191,25 @@ impl pallet_timestamp::Config for Runtime {
 }
 pub type Timestamp = pallet_timestamp::Module<Runtime>;
 
+thread_local! {
+	static VOLATILITY: RefCell<Option<Price>> = RefCell::new(None);
+}
+
+pub struct MockVolatility;
+impl MockVolatility {
+	pub fn set_volatility(volatility: Option<Price>) {
+		VOLATILITY.with(|v| *v.borrow_mut() = volatility);
+	}
+}
+impl VolatilityProvider for MockVolatility {
+	fn volatility(currency_id: CurrencyId) -> Option<Price> {
+		match currency_id {
+			CurrencyId::AUSD => None,
+			_ => VOLATILITY.with(|v| *v.borrow()),
+		}
+	}
+}
+
 impl Config for Runtime {
 	type Event = ();
 	type BaseLiquidityPools = module_base_liquidity_pools::Module<Runtime, MarginInstance>;
@@ -177,6 +218,12 @@ impl Config for Runtime {
 	type MaxSwapRate = MaxSwap;
 	type UnixTime = Timestamp;
 	type Moment = u64;
+	type MinWindDownPeriod = MinWindDownPeriod;
+	type HighLeverage = HighLeverage;
+	type MaxSpreadTiers = MaxSpreadTiers;
+	type MaxConfigs = MaxConfigs;
+	type MaxSwapRateHistory = MaxSwapRateHistory;
+	type VolatilityProvider = MockVolatility;
 	type WeightInfo = ();
 }
 pub type ModuleLiquidityPools = Module<Runtime>;
@@ -3,9 +3,12 @@
 use super::*;
 use mock::*;
 
-use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use frame_support::{
+	assert_noop, assert_ok, storage,
+	traits::{OnInitialize, OnRuntimeUpgrade},
+};
 
-use primitives::{CurrencyId, Leverage, Leverages};
+use primitives::{CurrencyId, Leverage, Leverages, PoolRole, PoolStatus, Side};
 use traits::{LiquidityPools, MarginProtocolLiquidityPools};
 
 fn swap_rate(pair: TradingPair, is_long: bool) -> FixedI128 {
@@ -74,14 +77,138 @@ fn should_disable_pool() {
 				enabled: false,
 				bid_spread: None,
 				ask_spread: None,
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				spread_tiers: vec![],
+				volatility_factor: None,
+				min_leveraged_amount: 0,
+				max_leveraged_amounts: vec![],
+				swap_rate_adjustment: Default::default(),
 				enabled_trades: Leverage::ShortTen | Leverage::LongFive,
 			}
 		);
+		PoolManager::set_can_remove(false);
 		assert_ok!(BaseLiquidityPools::disable_pool(Origin::signed(ALICE), 0));
 		assert_eq!(
 			ModuleLiquidityPools::pool_trading_pair_options(0, pair),
-			Default::default()
+			MarginPoolTradingPairOption {
+				enabled: false,
+				bid_spread: None,
+				ask_spread: None,
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				spread_tiers: vec![],
+				volatility_factor: None,
+				min_leveraged_amount: 0,
+				max_leveraged_amounts: vec![],
+				swap_rate_adjustment: Default::default(),
+				enabled_trades: Leverage::ShortTen | Leverage::LongFive,
+			}
 		);
+		assert_eq!(ModuleLiquidityPools::is_winding_down(0), true);
+		assert_eq!(ModuleLiquidityPools::wind_down_deadline(0), None);
+	})
+}
+
+#[test]
+fn should_wind_down_disabled_pool() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_spread(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Price::from_inner(80),
+			Price::from_inner(60)
+		));
+
+		assert_noop!(
+			ModuleLiquidityPools::set_wind_down_deadline(Origin::signed(ALICE), 0, 20),
+			Error::<Runtime>::PoolNotWindingDown
+		);
+
+		PoolManager::set_can_remove(false);
+		assert_ok!(BaseLiquidityPools::disable_pool(Origin::signed(ALICE), 0));
+		assert_eq!(ModuleLiquidityPools::is_winding_down(0), true);
+
+		assert_noop!(
+			ModuleLiquidityPools::set_spread(
+				Origin::signed(ALICE),
+				0,
+				pair,
+				Price::from_inner(10),
+				Price::from_inner(10)
+			),
+			Error::<Runtime>::PoolWindingDown
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_trading_pair_min_leveraged_amount(Origin::signed(ALICE), 0, pair, 10),
+			Error::<Runtime>::PoolWindingDown
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_max_leveraged_amount(
+				Origin::signed(ALICE),
+				0,
+				pair,
+				Leverage::LongFive,
+				Some(10)
+			),
+			Error::<Runtime>::PoolWindingDown
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_pool_swap_rate(Origin::signed(ALICE), 0, pair, SwapRate::default()),
+			Error::<Runtime>::PoolWindingDown
+		);
+
+		let now = System::block_number();
+		assert_noop!(
+			ModuleLiquidityPools::set_wind_down_deadline(Origin::signed(BOB), 0, now + 10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_wind_down_deadline(Origin::signed(ALICE), 0, now + 9),
+			Error::<Runtime>::WindDownDeadlineTooEarly
+		);
+		assert_ok!(ModuleLiquidityPools::set_wind_down_deadline(
+			Origin::signed(ALICE),
+			0,
+			now + 10
+		));
+
+		// last configured spreads are kept until the deadline
+		assert_eq!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::bid_spread(0, pair, Price::one(), 0),
+			Some(Price::from_inner(80))
+		);
+		assert_eq!(ModuleLiquidityPools::is_wind_down_expired(0), false);
+
+		System::set_block_number(now + 10);
+		assert_eq!(ModuleLiquidityPools::is_wind_down_expired(0), true);
+		assert_eq!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::bid_spread(0, pair, Price::one(), 0),
+			Some(Price::zero())
+		);
+		assert_eq!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ask_spread(0, pair, Price::one(), 0),
+			Some(Price::zero())
+		);
+
+		// still winding down while liabilities left
+		<ModuleLiquidityPools as LiquidityPools<AccountId>>::settle_wind_down(0);
+		assert_eq!(ModuleLiquidityPools::is_winding_down(0), true);
+
+		PoolManager::set_can_remove(true);
+		<ModuleLiquidityPools as LiquidityPools<AccountId>>::settle_wind_down(0);
+		assert_eq!(BaseLiquidityPools::pool_status(0), Some(PoolStatus::Disabled));
+		assert_eq!(ModuleLiquidityPools::is_winding_down(0), false);
+		assert_eq!(ModuleLiquidityPools::wind_down_deadline(0), None);
+		assert_eq!(ModuleLiquidityPools::is_wind_down_expired(0), false);
 	})
 }
 
@@ -131,22 +258,86 @@ fn should_set_spread() {
 			enabled: false,
 			bid_spread: Some(Price::from_inner(80)),
 			ask_spread: Some(Price::from_inner(60)),
+			spread_mode: SpreadMode::Absolute,
+			relative_bid_spread: None,
+			relative_ask_spread: None,
+			spread_tiers: vec![],
+			volatility_factor: None,
+			min_leveraged_amount: 0,
+			max_leveraged_amounts: vec![],
+			swap_rate_adjustment: Default::default(),
 			enabled_trades: Leverages::none(),
 		};
 
 		assert_eq!(ModuleLiquidityPools::pool_trading_pair_options(0, pair), pool_option);
 
 		assert_eq!(
-			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::bid_spread(0, pair),
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::bid_spread(0, pair, Price::one(), 0),
 			Some(Price::from_inner(80))
 		);
 		assert_eq!(
-			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ask_spread(0, pair),
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ask_spread(0, pair, Price::one(), 0),
 			Some(Price::from_inner(60))
 		);
 	})
 }
 
+#[test]
+fn operator_should_set_spread() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_noop!(
+			ModuleLiquidityPools::set_spread(
+				Origin::signed(BOB),
+				0,
+				pair,
+				Price::from_inner(80),
+				Price::from_inner(60)
+			),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(BaseLiquidityPools::grant_role(
+			Origin::signed(ALICE),
+			0,
+			BOB,
+			PoolRole::Treasurer
+		));
+		assert_noop!(
+			ModuleLiquidityPools::set_spread(
+				Origin::signed(BOB),
+				0,
+				pair,
+				Price::from_inner(80),
+				Price::from_inner(60)
+			),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(BaseLiquidityPools::grant_role(
+			Origin::signed(ALICE),
+			0,
+			BOB,
+			PoolRole::Operator
+		));
+		assert_ok!(ModuleLiquidityPools::set_spread(
+			Origin::signed(BOB),
+			0,
+			pair,
+			Price::from_inner(80),
+			Price::from_inner(60)
+		));
+		assert_eq!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::bid_spread(0, pair, Price::one(), 0),
+			Some(Price::from_inner(80))
+		);
+	})
+}
+
 #[test]
 fn should_set_max_spread() {
 	new_test_ext().execute_with(|| {
@@ -182,6 +373,14 @@ fn should_set_max_spread() {
 				enabled: false,
 				bid_spread: Some(Price::from_inner(30)),
 				ask_spread: Some(Price::from_inner(30)),
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				spread_tiers: vec![],
+				volatility_factor: None,
+				min_leveraged_amount: 0,
+				max_leveraged_amounts: vec![],
+				swap_rate_adjustment: Default::default(),
 				enabled_trades: Leverages::none(),
 			}
 		);
@@ -200,6 +399,14 @@ fn should_set_max_spread() {
 				enabled: false,
 				bid_spread: Some(Price::from_inner(30)),
 				ask_spread: Some(Price::from_inner(28)),
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				spread_tiers: vec![],
+				volatility_factor: None,
+				min_leveraged_amount: 0,
+				max_leveraged_amounts: vec![],
+				swap_rate_adjustment: Default::default(),
 				enabled_trades: Leverages::none(),
 			}
 		);
@@ -218,6 +425,14 @@ fn should_set_max_spread() {
 				enabled: false,
 				bid_spread: Some(Price::from_inner(28)),
 				ask_spread: Some(Price::from_inner(29)),
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				spread_tiers: vec![],
+				volatility_factor: None,
+				min_leveraged_amount: 0,
+				max_leveraged_amounts: vec![],
+				swap_rate_adjustment: Default::default(),
 				enabled_trades: Leverages::none(),
 			}
 		);
@@ -234,12 +449,254 @@ fn should_set_max_spread() {
 				enabled: false,
 				bid_spread: Some(Price::from_inner(20)),
 				ask_spread: Some(Price::from_inner(20)),
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				spread_tiers: vec![],
+				volatility_factor: None,
+				min_leveraged_amount: 0,
+				max_leveraged_amounts: vec![],
+				swap_rate_adjustment: Default::default(),
 				enabled_trades: Leverages::none(),
 			}
 		);
 	});
 }
 
+#[test]
+fn should_set_relative_spread() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let price = Price::saturating_from_integer(2);
+		let bid_spread =
+			|| <ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::bid_spread(0, pair, price, 0);
+		let ask_spread =
+			|| <ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ask_spread(0, pair, price, 0);
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+
+		assert_noop!(
+			ModuleLiquidityPools::set_relative_spread(Origin::signed(BOB), 0, pair, 10, 20),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_relative_spread(Origin::signed(ALICE), 0, pair, 10_000, 20),
+			Error::<Runtime>::RelativeSpreadTooHigh
+		);
+
+		// 0.1% bid and 0.2% ask of oracle price
+		assert_ok!(ModuleLiquidityPools::set_relative_spread(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			10,
+			20
+		));
+		assert_eq!(
+			ModuleLiquidityPools::pool_trading_pair_options(0, pair).spread_mode,
+			SpreadMode::Relative
+		);
+		assert_eq!(bid_spread(), Some(Price::saturating_from_rational(2, 1000)));
+		assert_eq!(ask_spread(), Some(Price::saturating_from_rational(4, 1000)));
+
+		// capped by relative max spread
+		assert_ok!(ModuleLiquidityPools::set_relative_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			Some(15)
+		));
+		assert_eq!(bid_spread(), Some(Price::saturating_from_rational(2, 1000)));
+		assert_eq!(ask_spread(), Some(Price::saturating_from_rational(3, 1000)));
+
+		// relative max spread removed
+		assert_ok!(ModuleLiquidityPools::set_relative_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			None
+		));
+		assert_eq!(ask_spread(), Some(Price::saturating_from_rational(4, 1000)));
+		assert_ok!(ModuleLiquidityPools::set_relative_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			Some(15)
+		));
+
+		// the lower one of max spreads is used
+		assert_ok!(ModuleLiquidityPools::set_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			Price::saturating_from_rational(25, 10000)
+		));
+		assert_eq!(ask_spread(), Some(Price::saturating_from_rational(25, 10000)));
+
+		// back to absolute spreads
+		assert_ok!(ModuleLiquidityPools::set_spread(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Price::from_inner(80),
+			Price::from_inner(60)
+		));
+		assert_eq!(bid_spread(), Some(Price::from_inner(80)));
+		assert_eq!(ask_spread(), Some(Price::from_inner(60)));
+	})
+}
+
+#[test]
+fn should_set_spread_tiers() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let tier = |threshold, add_on| SpreadTier { threshold, add_on };
+		let price = Price::saturating_from_integer(2);
+		let bid_spread = |leveraged_amount| {
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::bid_spread(
+				0,
+				pair,
+				price,
+				leveraged_amount,
+			)
+		};
+		let ask_spread = |leveraged_amount| {
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ask_spread(
+				0,
+				pair,
+				price,
+				leveraged_amount,
+			)
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_spread(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Price::saturating_from_rational(1, 1000),
+			Price::saturating_from_rational(2, 1000)
+		));
+
+		assert_noop!(
+			ModuleLiquidityPools::set_spread_tiers(Origin::signed(BOB), 0, pair, vec![tier(100, 10)]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_spread_tiers(
+				Origin::signed(ALICE),
+				0,
+				pair,
+				vec![tier(100, 10), tier(200, 20), tier(300, 30), tier(400, 40)]
+			),
+			Error::<Runtime>::TooManySpreadTiers
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_spread_tiers(Origin::signed(ALICE), 0, pair, vec![tier(200, 10), tier(200, 20)]),
+			Error::<Runtime>::InvalidSpreadTiers
+		);
+
+		let tiers = vec![tier(100, 10), tier(1000, 25)];
+		assert_ok!(ModuleLiquidityPools::set_spread_tiers(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			tiers.clone()
+		));
+		assert_eq!(
+			ModuleLiquidityPools::pool_trading_pair_options(0, pair).spread_tiers,
+			tiers
+		);
+
+		// below the first tier, no add-on
+		assert_eq!(bid_spread(99), Some(Price::saturating_from_rational(1, 1000)));
+		assert_eq!(ask_spread(99), Some(Price::saturating_from_rational(2, 1000)));
+		// 0.1% add-on of oracle price
+		assert_eq!(bid_spread(100), Some(Price::saturating_from_rational(3, 1000)));
+		assert_eq!(ask_spread(999), Some(Price::saturating_from_rational(4, 1000)));
+		// 0.25% add-on of oracle price
+		assert_eq!(bid_spread(1000), Some(Price::saturating_from_rational(6, 1000)));
+		assert_eq!(ask_spread(1_000_000), Some(Price::saturating_from_rational(7, 1000)));
+
+		// still capped by max spread
+		assert_ok!(ModuleLiquidityPools::set_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			Price::saturating_from_rational(5, 1000)
+		));
+		assert_eq!(ask_spread(1000), Some(Price::saturating_from_rational(5, 1000)));
+	})
+}
+
+#[test]
+fn should_set_volatility_factor() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let price = Price::saturating_from_integer(2);
+		let spreads = || ModuleLiquidityPools::spreads_at_price(0, pair, price, 0);
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_spread(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Price::saturating_from_rational(1, 1000),
+			Price::saturating_from_rational(2, 1000)
+		));
+
+		let factor = Price::saturating_from_rational(1, 2);
+		assert_noop!(
+			ModuleLiquidityPools::set_volatility_factor(Origin::signed(BOB), 0, pair, Some(factor)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(ModuleLiquidityPools::set_volatility_factor(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Some(factor)
+		));
+		assert_eq!(
+			ModuleLiquidityPools::pool_trading_pair_options(0, pair).volatility_factor,
+			Some(factor)
+		);
+
+		// volatility not tracked, no add-on
+		assert_eq!(
+			spreads(),
+			(
+				Some(Price::saturating_from_rational(1, 1000)),
+				Some(Price::saturating_from_rational(2, 1000))
+			)
+		);
+
+		// add-on = 0.5 * 1% * 2 = 0.01
+		MockVolatility::set_volatility(Some(Price::saturating_from_rational(1, 100)));
+		assert_eq!(
+			spreads(),
+			(
+				Some(Price::saturating_from_rational(11, 1000)),
+				Some(Price::saturating_from_rational(12, 1000))
+			)
+		);
+
+		// still capped by max spread
+		assert_ok!(ModuleLiquidityPools::set_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			Price::saturating_from_rational(5, 1000)
+		));
+		assert_eq!(
+			spreads(),
+			(
+				Some(Price::saturating_from_rational(5, 1000)),
+				Some(Price::saturating_from_rational(5, 1000))
+			)
+		);
+	})
+}
+
 #[test]
 fn should_set_enabled_trades() {
 	new_test_ext().execute_with(|| {
@@ -264,6 +721,14 @@ fn should_set_enabled_trades() {
 			enabled: false,
 			bid_spread: None,
 			ask_spread: None,
+			spread_mode: SpreadMode::Absolute,
+			relative_bid_spread: None,
+			relative_ask_spread: None,
+			spread_tiers: vec![],
+			volatility_factor: None,
+			min_leveraged_amount: 0,
+			max_leveraged_amounts: vec![],
+			swap_rate_adjustment: Default::default(),
 			enabled_trades: Leverage::ShortTen | Leverage::LongFive,
 		};
 
@@ -271,6 +736,139 @@ fn should_set_enabled_trades() {
 	})
 }
 
+#[test]
+fn should_configure_pool() {
+	new_test_ext().execute_with(|| {
+		let eur = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let jpy = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FJPY,
+		};
+		let tiers = vec![SpreadTier {
+			threshold: 1000,
+			add_on: 10,
+		}];
+		let config = |pair, enabled| MarginPoolTradingPairConfig {
+			pair,
+			spread: Spreads::Absolute {
+				bid: Price::saturating_from_rational(1, 1000),
+				ask: Price::saturating_from_rational(2, 1000),
+			},
+			spread_tiers: tiers.clone(),
+			volatility_factor: Some(Price::saturating_from_integer(2)),
+			enabled_trades: Leverage::ShortTen | Leverage::LongFive,
+			enabled,
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::enable_trading_pair(
+			Origin::signed(UpdateOrigin::get()),
+			eur
+		));
+
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(Origin::signed(BOB), 0, vec![config(eur, true)], None),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(
+				Origin::signed(ALICE),
+				0,
+				vec![config(eur, true), config(eur, false)],
+				None
+			),
+			Error::<Runtime>::DuplicatedTradingPair
+		);
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(
+				Origin::signed(ALICE),
+				0,
+				vec![config(eur, true), config(jpy, false), config(jpy, false)],
+				None
+			),
+			Error::<Runtime>::TooManyConfigs
+		);
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(
+				Origin::signed(ALICE),
+				0,
+				vec![MarginPoolTradingPairConfig {
+					spread_tiers: vec![tiers[0].clone(), tiers[0].clone()],
+					..config(eur, true)
+				}],
+				None
+			),
+			Error::<Runtime>::InvalidSpreadTiers
+		);
+		// nothing applied if any pair is invalid
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(
+				Origin::signed(ALICE),
+				0,
+				vec![config(eur, true), config(jpy, true)],
+				Some(100)
+			),
+			Error::<Runtime>::TradingPairNotEnabled
+		);
+
+		assert_ok!(ModuleLiquidityPools::set_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			jpy,
+			Price::saturating_from_rational(1, 1000)
+		));
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(Origin::signed(ALICE), 0, vec![config(jpy, false)], None),
+			Error::<Runtime>::SpreadTooHigh
+		);
+
+		assert_ok!(ModuleLiquidityPools::configure_pool(
+			Origin::signed(ALICE),
+			0,
+			vec![config(eur, true)],
+			Some(100)
+		));
+		assert_eq!(
+			ModuleLiquidityPools::pool_trading_pair_options(0, eur),
+			MarginPoolTradingPairOption {
+				enabled: true,
+				bid_spread: Some(Price::saturating_from_rational(1, 1000)),
+				ask_spread: Some(Price::saturating_from_rational(2, 1000)),
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				spread_tiers: tiers.clone(),
+				volatility_factor: Some(Price::saturating_from_integer(2)),
+				min_leveraged_amount: 0,
+				max_leveraged_amounts: vec![],
+				swap_rate_adjustment: Default::default(),
+				enabled_trades: Leverage::ShortTen | Leverage::LongFive,
+			}
+		);
+		assert_eq!(ModuleLiquidityPools::pool_options(0).min_leveraged_amount, 100);
+
+		// relative spreads
+		assert_ok!(ModuleLiquidityPools::configure_pool(
+			Origin::signed(ALICE),
+			0,
+			vec![MarginPoolTradingPairConfig {
+				spread: Spreads::Relative { bid: 10, ask: 20 },
+				spread_tiers: vec![],
+				volatility_factor: None,
+				..config(eur, true)
+			}],
+			None
+		));
+		let option = ModuleLiquidityPools::pool_trading_pair_options(0, eur);
+		assert_eq!(option.spread_mode, SpreadMode::Relative);
+		assert_eq!(option.relative_bid_spread, Some(10));
+		assert_eq!(option.relative_ask_spread, Some(20));
+		assert_eq!(option.spread_tiers, vec![]);
+		assert_eq!(option.volatility_factor, None);
+	})
+}
+
 #[test]
 fn should_set_swap_rate() {
 	new_test_ext().execute_with(|| {
@@ -373,13 +971,242 @@ fn should_get_swap() {
 			rate
 		));
 		assert_eq!(
-			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::swap_rate(0, pair, true),
-			fixed_i128_mul_signum(MaxSwap::get(), -1)
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::swap_rate(0, pair, true),
+			fixed_i128_mul_signum(MaxSwap::get(), -1)
+		);
+		assert_eq!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::swap_rate(0, pair, false),
+			FixedI128::saturating_from_integer(-1)
+		);
+	});
+}
+
+#[test]
+fn should_use_pool_swap_rate() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::LAMI,
+			quote: CurrencyId::AUSD,
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_swap_rate(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			SwapRate {
+				long: FixedI128::saturating_from_integer(-1),
+				short: FixedI128::saturating_from_integer(1),
+			}
+		));
+		assert_ok!(ModuleLiquidityPools::set_additional_swap_rate(
+			Origin::signed(ALICE),
+			0,
+			FixedI128::saturating_from_integer(1)
+		));
+
+		let adjustment = SwapRate {
+			long: FixedI128::saturating_from_rational(1, 2),
+			short: FixedI128::saturating_from_rational(1, 4),
+		};
+		assert_noop!(
+			ModuleLiquidityPools::set_pool_swap_rate(Origin::signed(BOB), 0, pair, adjustment.clone()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_pool_swap_rate(
+				Origin::signed(ALICE),
+				0,
+				pair,
+				SwapRate {
+					long: FixedI128::saturating_from_integer(-3),
+					short: FixedI128::zero(),
+				}
+			),
+			Error::<Runtime>::SwapRateTooHigh
+		);
+
+		// adjustment is added to the trading pair swap rate adjusted by additional swap rate
+		assert_ok!(ModuleLiquidityPools::set_pool_swap_rate(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			adjustment.clone()
+		));
+		let expected = SwapRate {
+			long: FixedI128::saturating_from_rational(-3, 2),
+			short: FixedI128::saturating_from_rational(1, 4),
+		};
+		assert_eq!(ModuleLiquidityPools::pool_swap_rate(0, pair), expected);
+		assert_eq!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::swap_rate(0, pair, true),
+			expected.long
+		);
+		assert_eq!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::swap_rate(0, pair, false),
+			expected.short
+		);
+
+		// adjusted swap rate is still capped by max swap rate
+		assert_ok!(ModuleLiquidityPools::set_pool_swap_rate(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			SwapRate {
+				long: FixedI128::saturating_from_integer(-2),
+				short: FixedI128::zero(),
+			}
+		));
+		assert_eq!(
+			ModuleLiquidityPools::pool_swap_rate(0, pair).long,
+			FixedI128::saturating_from_integer(-2)
+		);
+
+		// remove adjustment
+		assert_ok!(ModuleLiquidityPools::set_pool_swap_rate(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			SwapRate::default()
+		));
+		assert_eq!(
+			ModuleLiquidityPools::pool_swap_rate(0, pair),
+			SwapRate {
+				long: FixedI128::saturating_from_integer(-2),
+				short: FixedI128::zero(),
+			}
+		);
+	});
+}
+
+#[test]
+fn should_get_pool_market_data() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_enabled_leverages(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Leverage::ShortTen | Leverage::LongFive,
+		));
+		assert_eq!(
+			ModuleLiquidityPools::enabled_leverages(0, pair),
+			vec![Leverage::LongFive, Leverage::ShortTen]
+		);
+		// fixed leverages within bounds are allowed too
+		assert_ok!(ModuleLiquidityPools::set_leverage_bounds(
+			Origin::signed(ALICE),
+			0,
+			Some(LeverageBounds { min: 3, max: 5 })
+		));
+		assert_eq!(
+			ModuleLiquidityPools::enabled_leverages(0, pair),
+			vec![
+				Leverage::LongThree,
+				Leverage::LongFive,
+				Leverage::ShortThree,
+				Leverage::ShortFive,
+				Leverage::ShortTen
+			]
+		);
+
+		assert_eq!(ModuleLiquidityPools::enabled_trading_pairs_of_pool(0), vec![]);
+		assert_eq!(ModuleLiquidityPools::pools_of_trading_pair(pair), vec![]);
+		assert_ok!(ModuleLiquidityPools::enable_trading_pair(
+			Origin::signed(UpdateOrigin::get()),
+			pair
+		));
+		assert_ok!(ModuleLiquidityPools::liquidity_pool_enable_trading_pair(
+			Origin::signed(ALICE),
+			0,
+			pair
+		));
+		assert_eq!(ModuleLiquidityPools::enabled_trading_pairs_of_pool(0), vec![pair]);
+		assert_eq!(ModuleLiquidityPools::pools_of_trading_pair(pair), vec![0]);
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_eq!(ModuleLiquidityPools::pools_of_trading_pair(pair), vec![0]);
+		assert_ok!(ModuleLiquidityPools::disable_trading_pair(
+			Origin::signed(UpdateOrigin::get()),
+			pair
+		));
+		assert_eq!(ModuleLiquidityPools::enabled_trading_pairs_of_pool(0), vec![]);
+		assert_eq!(ModuleLiquidityPools::pools_of_trading_pair(pair), vec![]);
+
+		assert_eq!(ModuleLiquidityPools::next_accumulate_time(pair), None);
+		assert_ok!(ModuleLiquidityPools::set_accumulate_config(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			10 * ONE_MINUTE,
+			3 * ONE_MINUTE
+		));
+		assert_eq!(ModuleLiquidityPools::next_accumulate_time(pair), Some(3 * ONE_MINUTE));
+
+		execute_time(4 * ONE_MINUTE);
+		assert_eq!(ModuleLiquidityPools::next_accumulate_time(pair), Some(13 * ONE_MINUTE));
+
+		// accumulated in this minute
+		execute_time(13 * ONE_MINUTE);
+		assert_eq!(ModuleLiquidityPools::next_accumulate_time(pair), Some(23 * ONE_MINUTE));
+	});
+}
+
+#[test]
+fn should_record_swap_rate_history() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let rate = SwapRate {
+			long: FixedI128::saturating_from_rational(-1, 10),
+			short: FixedI128::saturating_from_rational(1, 10),
+		};
+		assert_ok!(ModuleLiquidityPools::set_accumulate_config(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			1 * ONE_MINUTE,
+			0
+		));
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_swap_rate(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			rate.clone()
+		));
+
+		let accumulation = |minutes: i128| SwapRateAccumulation {
+			time: minutes as u64 * ONE_MINUTE,
+			rate: rate.clone(),
+			accumulated: SwapRate {
+				long: FixedI128::saturating_from_rational(-minutes, 10),
+				short: FixedI128::saturating_from_rational(minutes, 10),
+			},
+		};
+
+		execute_time(1 * ONE_MINUTE);
+		assert_eq!(ModuleLiquidityPools::swap_rate_history(0, pair), vec![accumulation(1)]);
+
+		// only the latest `MaxSwapRateHistory` accumulations are kept
+		execute_time(2 * ONE_MINUTE);
+		execute_time(3 * ONE_MINUTE);
+		assert_eq!(
+			ModuleLiquidityPools::swap_rate_history(0, pair),
+			vec![accumulation(2), accumulation(3)]
 		);
+		assert_eq!(ModuleLiquidityPools::swap_rate_history_head(0, pair), 1);
+
+		execute_time(4 * ONE_MINUTE);
 		assert_eq!(
-			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::swap_rate(0, pair, false),
-			FixedI128::saturating_from_integer(-1)
+			ModuleLiquidityPools::swap_rate_history(0, pair),
+			vec![accumulation(3), accumulation(4)]
 		);
+		assert_eq!(ModuleLiquidityPools::swap_rate_history_head(0, pair), 0);
+
+		assert_ok!(BaseLiquidityPools::remove_pool(Origin::signed(ALICE), 0));
+		assert_eq!(ModuleLiquidityPools::swap_rate_history(0, pair), vec![]);
+		assert_eq!(ModuleLiquidityPools::swap_rate_history_head(0, pair), 0);
 	});
 }
 
@@ -474,7 +1301,7 @@ fn ensure_can_open_position() {
 			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ensure_can_open_position(
 				0,
 				pair,
-				Leverage::ShortFive,
+				Leverage::ShortFive.into(),
 				0
 			),
 			OpenPositionError::LeverageNotAllowedInPool,
@@ -497,10 +1324,186 @@ fn ensure_can_open_position() {
 			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ensure_can_open_position(
 				0,
 				pair,
-				Leverage::ShortFive,
+				Leverage::ShortFive.into(),
+				0
+			)
+		);
+
+		assert_ok!(BaseLiquidityPools::disable_pool(Origin::signed(ALICE), 0));
+		assert_noop!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ensure_can_open_position(
+				0,
+				pair,
+				Leverage::ShortFive.into(),
 				0
+			),
+			OpenPositionError::PoolNotActive,
+		);
+	});
+}
+
+#[test]
+fn should_check_trading_pair_leveraged_amount_limits() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let ensure_can_open_position = |leverage: Leverage, leveraged_amount| {
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ensure_can_open_position(
+				0,
+				pair,
+				leverage.into(),
+				leveraged_amount,
+			)
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::enable_trading_pair(
+			Origin::signed(UpdateOrigin::get()),
+			pair
+		));
+		assert_ok!(ModuleLiquidityPools::liquidity_pool_enable_trading_pair(
+			Origin::signed(ALICE),
+			0,
+			pair
+		));
+		assert_ok!(ModuleLiquidityPools::set_enabled_leverages(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Leverage::LongTwo | Leverage::LongTwenty,
+		));
+
+		assert_noop!(
+			ModuleLiquidityPools::set_trading_pair_min_leveraged_amount(Origin::signed(BOB), 0, pair, 10),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(ModuleLiquidityPools::set_trading_pair_min_leveraged_amount(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			10
+		));
+		assert_ok!(ModuleLiquidityPools::set_max_leveraged_amount(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Leverage::LongTwenty,
+			Some(100)
+		));
+		assert_eq!(
+			ModuleLiquidityPools::max_leveraged_amount(0, pair, Leverage::LongTwenty.into()),
+			Some(100)
+		);
+		assert_eq!(
+			ModuleLiquidityPools::max_leveraged_amount(0, pair, Leverage::LongTwo.into()),
+			None
+		);
+
+		assert_eq!(
+			ensure_can_open_position(Leverage::LongTwo, 9),
+			Err(OpenPositionError::BelowTradingPairMinLeveragedAmount)
+		);
+		assert_ok!(ensure_can_open_position(Leverage::LongTwo, 1_000));
+		assert_ok!(ensure_can_open_position(Leverage::LongTwenty, 100));
+		assert_eq!(
+			ensure_can_open_position(Leverage::LongTwenty, 101),
+			Err(OpenPositionError::AboveMaxLeveragedAmount)
+		);
+
+		// remove max limit
+		assert_ok!(ModuleLiquidityPools::set_max_leveraged_amount(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Leverage::LongTwenty,
+			None
+		));
+		assert_ok!(ensure_can_open_position(Leverage::LongTwenty, 1_000));
+	});
+}
+
+#[test]
+fn should_allow_leverages_within_bounds() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let ensure_can_open_position = |leverage, leveraged_amount| {
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ensure_can_open_position(
+				0,
+				pair,
+				leverage,
+				leveraged_amount,
 			)
+		};
+		let long_seven = PositionLeverage::new(Side::Long, 7);
+		let short_seven = PositionLeverage::new(Side::Short, 7);
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::enable_trading_pair(
+			Origin::signed(UpdateOrigin::get()),
+			pair
+		));
+		assert_ok!(ModuleLiquidityPools::liquidity_pool_enable_trading_pair(
+			Origin::signed(ALICE),
+			0,
+			pair
+		));
+		assert_ok!(ModuleLiquidityPools::set_enabled_leverages(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Leverage::LongTwo.into(),
+		));
+		assert_eq!(
+			ensure_can_open_position(long_seven, 0),
+			Err(OpenPositionError::LeverageNotAllowedInPool)
+		);
+
+		assert_noop!(
+			ModuleLiquidityPools::set_leverage_bounds(
+				Origin::signed(ALICE),
+				0,
+				Some(LeverageBounds { min: 0, max: 10 })
+			),
+			Error::<Runtime>::InvalidLeverageBounds
+		);
+		assert_noop!(
+			ModuleLiquidityPools::set_leverage_bounds(
+				Origin::signed(ALICE),
+				0,
+				Some(LeverageBounds { min: 10, max: 5 })
+			),
+			Error::<Runtime>::InvalidLeverageBounds
+		);
+		assert_ok!(ModuleLiquidityPools::set_leverage_bounds(
+			Origin::signed(ALICE),
+			0,
+			Some(LeverageBounds { min: 4, max: 10 })
+		));
+
+		assert_ok!(ensure_can_open_position(long_seven, 0));
+		assert_ok!(ensure_can_open_position(short_seven, 0));
+		assert_ok!(ensure_can_open_position(Leverage::LongTwo.into(), 0));
+		assert_eq!(
+			ensure_can_open_position(PositionLeverage::new(Side::Long, 15), 0),
+			Err(OpenPositionError::LeverageNotAllowedInPool)
+		);
+
+		// the limit of the highest fixed leverage below applies
+		assert_ok!(ModuleLiquidityPools::set_max_leveraged_amount(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Leverage::LongFive,
+			Some(100)
+		));
+		assert_eq!(
+			ensure_can_open_position(long_seven, 101),
+			Err(OpenPositionError::AboveMaxLeveragedAmount)
 		);
+		assert_ok!(ensure_can_open_position(short_seven, 101));
 	});
 }
 
@@ -569,6 +1572,39 @@ fn should_update_accumulated_rate() {
 	});
 }
 
+#[test]
+fn should_not_accumulate_rate_of_disabled_pool() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let rate = SwapRate {
+			long: FixedI128::saturating_from_rational(-23, 1000), // -2.3%
+			short: FixedI128::saturating_from_rational(23, 1000), // 2.3%
+		};
+
+		assert_ok!(ModuleLiquidityPools::set_accumulate_config(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			1 * ONE_MINUTE,
+			0
+		));
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_swap_rate(
+			Origin::signed(UpdateOrigin::get()),
+			pair,
+			rate.clone()
+		));
+		assert_ok!(BaseLiquidityPools::disable_pool(Origin::signed(ALICE), 0));
+		assert_eq!(BaseLiquidityPools::pool_status(0), Some(PoolStatus::Disabled));
+
+		execute_time(1 * ONE_MINUTE);
+		assert_eq!(accumulated_rate(pair, true), FixedI128::zero());
+		assert_eq!(accumulated_rate(pair, false), FixedI128::zero());
+	});
+}
+
 #[test]
 fn should_enable_disable_trading_pairs() {
 	new_test_ext().execute_with(|| {
@@ -618,6 +1654,85 @@ fn liquidity_provider_should_enable_disable_trading_pairs() {
 	})
 }
 
+#[test]
+fn high_leverage_should_require_locked_liquidity() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(BaseLiquidityPools::deposit_liquidity(Origin::signed(ALICE), 0, 1000));
+		assert_ok!(ModuleLiquidityPools::enable_trading_pair(
+			Origin::signed(UpdateOrigin::get()),
+			pair
+		));
+		assert_ok!(ModuleLiquidityPools::liquidity_pool_enable_trading_pair(
+			Origin::signed(ALICE),
+			0,
+			pair
+		));
+
+		assert_ok!(ModuleLiquidityPools::set_min_locked_liquidity_for_high_leverage(
+			Origin::signed(UpdateOrigin::get()),
+			500
+		));
+		assert_eq!(ModuleLiquidityPools::min_locked_liquidity_for_high_leverage(), 500);
+
+		assert_noop!(
+			ModuleLiquidityPools::set_enabled_leverages(
+				Origin::signed(ALICE),
+				0,
+				pair,
+				Leverage::LongTwenty | Leverage::LongFifty
+			),
+			Error::<Runtime>::NotEnoughLockedLiquidity
+		);
+		assert_ok!(ModuleLiquidityPools::set_enabled_leverages(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Leverage::LongTwenty.into()
+		));
+
+		assert_ok!(BaseLiquidityPools::lock_liquidity(Origin::signed(ALICE), 0, 500, 10));
+		assert_ok!(ModuleLiquidityPools::set_enabled_leverages(
+			Origin::signed(ALICE),
+			0,
+			pair,
+			Leverage::LongTwenty | Leverage::LongFifty
+		));
+		assert_ok!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ensure_can_open_position(
+				0,
+				pair,
+				Leverage::LongFifty.into(),
+				0
+			)
+		);
+
+		// lock expired
+		System::set_block_number(10);
+		assert_noop!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ensure_can_open_position(
+				0,
+				pair,
+				Leverage::LongFifty.into(),
+				0
+			),
+			OpenPositionError::NotEnoughLockedLiquidity,
+		);
+		assert_ok!(
+			<ModuleLiquidityPools as MarginProtocolLiquidityPools<AccountId>>::ensure_can_open_position(
+				0,
+				pair,
+				Leverage::LongTwenty.into(),
+				0
+			)
+		);
+	})
+}
+
 #[test]
 fn should_set_default_min_leveraged_amount() {
 	new_test_ext().execute_with(|| {
@@ -674,3 +1789,79 @@ fn should_set_min_leveraged_amount() {
 		);
 	})
 }
+
+#[test]
+fn migrate_to_v2_should_work() {
+	new_test_ext().execute_with(|| {
+		let pair = TradingPair {
+			base: CurrencyId::AUSD,
+			quote: CurrencyId::FEUR,
+		};
+		let swap_rate = SwapRate {
+			long: FixedI128::saturating_from_integer(-1),
+			short: FixedI128::saturating_from_integer(1),
+		};
+		let accumulate_config = AccumulateConfig {
+			frequency: 60u64,
+			offset: 1,
+		};
+		storage::unhashed::put(
+			&<TradingPairOptions<Runtime>>::hashed_key_for(pair),
+			&(
+				true,
+				Some(Price::from_inner(100)),
+				swap_rate.clone(),
+				Some(accumulate_config.clone()),
+			),
+		);
+		storage::unhashed::put(
+			&PoolOptions::hashed_key_for(0),
+			&(FixedI128::saturating_from_integer(1), 10 as Balance),
+		);
+		storage::unhashed::put(
+			&PoolTradingPairOptions::hashed_key_for(0, pair),
+			&(
+				true,
+				Some(Price::from_inner(1)),
+				Some(Price::from_inner(2)),
+				Leverage::LongTwo | Leverage::ShortTwo,
+			),
+		);
+
+		assert_eq!(ModuleLiquidityPools::storage_version(), Releases::V1_0_0);
+		ModuleLiquidityPools::on_runtime_upgrade();
+		assert_eq!(ModuleLiquidityPools::storage_version(), Releases::V2_0_0);
+
+		assert_eq!(
+			ModuleLiquidityPools::trading_pair_options(pair),
+			MarginTradingPairOption {
+				enabled: true,
+				max_spread: Some(Price::from_inner(100)),
+				relative_max_spread: None,
+				swap_rate,
+				accumulate_config: Some(accumulate_config),
+			}
+		);
+		assert_eq!(
+			ModuleLiquidityPools::pool_options(0),
+			MarginPoolOption {
+				additional_swap_rate: FixedI128::saturating_from_integer(1),
+				min_leveraged_amount: 10,
+				leverage_bounds: None,
+			}
+		);
+		let option = PoolTradingPairOptions::get(0, pair);
+		assert!(option.enabled);
+		assert_eq!(option.bid_spread, Some(Price::from_inner(1)));
+		assert_eq!(option.ask_spread, Some(Price::from_inner(2)));
+		assert_eq!(option.spread_mode, SpreadMode::Absolute);
+		assert_eq!(option.relative_bid_spread, None);
+		assert_eq!(option.relative_ask_spread, None);
+		assert_eq!(option.spread_tiers, vec![]);
+		assert_eq!(option.volatility_factor, None);
+		assert_eq!(option.min_leveraged_amount, 0);
+		assert_eq!(option.max_leveraged_amounts, vec![]);
+		assert_eq!(option.swap_rate_adjustment, SwapRate::default());
+		assert_eq!(option.enabled_trades, Leverage::LongTwo | Leverage::ShortTwo);
+	})
+}
@@ -1,4 +1,4 @@
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! Weights partly estimated by hand. Regenerate with the benchmark CLI before release.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -32,4 +32,25 @@ impl crate::WeightInfo for () {
 			.saturating_add(DbWeight::get().reads(4 as Weight))
 			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
+	fn set_relative_spread() -> Weight {
+		(80_910_000 as Weight)
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_relative_max_spread() -> Weight {
+		(50_732_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_volatility_factor() -> Weight {
+		(48_530_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn configure_pool(c: u32) -> Weight {
+		(18_236_000 as Weight)
+			.saturating_add((108_916_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 }
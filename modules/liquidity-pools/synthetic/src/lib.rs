@@ -1,14 +1,28 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::EnsureOrigin, weights::Weight};
+use frame_support::{
+	decl_error, decl_event, decl_module, decl_storage, ensure,
+	storage::IterableStorageDoubleMap,
+	traits::{EnsureOrigin, Get},
+	weights::Weight,
+};
 use frame_system::ensure_signed;
 use orml_utilities::with_transaction_result;
-use primitives::{Balance, CurrencyId, LiquidityPoolId, Price};
-use sp_runtime::{DispatchResult, ModuleId, Permill, RuntimeDebug};
-use sp_std::prelude::*;
-use traits::{LiquidityPools, OnDisableLiquidityPool, OnRemoveLiquidityPool, SyntheticProtocolLiquidityPools};
+use primitives::{
+	arithmetic::{fixed_u128_from_bps, BPS_PER_UNIT},
+	Balance, CurrencyId, LiquidityPoolId, PoolRole, PoolStatus, Price, SpreadBps, SpreadMode, Spreads,
+};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	DispatchResult, ModuleId, Permill, RuntimeDebug,
+};
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+use traits::{
+	LiquidityPools, OnDisableLiquidityPool, OnRemoveLiquidityPool, SyntheticProtocolLiquidityPools, VolatilityProvider,
+};
 
 mod default_weight;
+mod migrations;
 mod mock;
 mod tests;
 
@@ -18,6 +32,10 @@ pub trait WeightInfo {
 	fn set_min_additional_collateral_ratio() -> Weight;
 	fn set_synthetic_enabled() -> Weight;
 	fn set_max_spread() -> Weight;
+	fn set_relative_spread() -> Weight;
+	fn set_relative_max_spread() -> Weight;
+	fn set_volatility_factor() -> Weight;
+	fn configure_pool(c: u32) -> Weight;
 }
 
 use codec::{Decode, Encode};
@@ -35,6 +53,26 @@ pub struct SyntheticPoolCurrencyOption {
 	/// DEFAULT-NOTE: `None`, pool owner must set spread.
 	pub ask_spread: Option<Price>,
 
+	/// Spread mode. In relative mode, `relative_bid_spread` and `relative_ask_spread` are used.
+	///
+	/// DEFAULT-NOTE: absolute.
+	pub spread_mode: SpreadMode,
+
+	/// Bid spread in basis points of the oracle price.
+	///
+	/// DEFAULT-NOTE: `None`, pool owner must set spread.
+	pub relative_bid_spread: Option<SpreadBps>,
+
+	/// Ask spread in basis points of the oracle price.
+	///
+	/// DEFAULT-NOTE: `None`, pool owner must set spread.
+	pub relative_ask_spread: Option<SpreadBps>,
+
+	/// Volatility factor `k`. If set, `k * volatility * price` is added to both bid and ask spread.
+	///
+	/// DEFAULT-NOTE: `None`, spread not adjusted by volatility.
+	pub volatility_factor: Option<Price>,
+
 	/// Additional collateral ratio.
 	///
 	/// DEFAULT-NOTE: `None`. If not set or smaller than min additional swap rate, min value will be
@@ -47,6 +85,38 @@ pub struct SyntheticPoolCurrencyOption {
 	pub synthetic_enabled: bool,
 }
 
+/// Storage version of the module.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum Releases {
+	V1_0_0,
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// Config of a currency in a synthetic pool, applied all at once by `configure_pool`.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone)]
+pub struct SyntheticPoolCurrencyConfig {
+	/// The synthetic currency.
+	pub currency_id: CurrencyId,
+
+	/// Bid and ask spread, absolute or relative.
+	pub spread: Spreads,
+
+	/// Volatility factor, `None` to not adjust spread by volatility.
+	pub volatility_factor: Option<Price>,
+
+	/// Additional collateral ratio.
+	pub additional_collateral_ratio: Option<Permill>,
+
+	/// Is enabled to mint in the pool.
+	pub synthetic_enabled: bool,
+}
+
 pub const MODULE_ID: ModuleId = ModuleId(*b"lami/slp");
 
 pub trait Config: frame_system::Config {
@@ -59,6 +129,12 @@ pub trait Config: frame_system::Config {
 	/// Required origin for updating protocol options.
 	type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
+	/// Price volatility estimates, to adjust spreads.
+	type VolatilityProvider: VolatilityProvider;
+
+	/// Maximum number of currency configs in a `configure_pool` call.
+	type MaxConfigs: Get<u32>;
+
 	/// Weight information for the extrinsics in this module.
 	type WeightInfo: WeightInfo;
 }
@@ -73,6 +149,13 @@ decl_storage! {
 
 		/// Maximum spread of a currency.
 		pub MaxSpread get(fn max_spread): map hasher(twox_64_concat) CurrencyId => Option<Price>;
+
+		/// Maximum spread of a currency, in basis points of the oracle price. If both max spreads
+		/// are set, the lower one is used.
+		pub RelativeMaxSpread get(fn relative_max_spread): map hasher(twox_64_concat) CurrencyId => Option<SpreadBps>;
+
+		/// Storage version of the module.
+		StorageVersion get(fn storage_version) build(|_: &GenesisConfig| Releases::V2_0_0): Releases;
 	}
 }
 
@@ -94,6 +177,18 @@ decl_event!(
 
 		/// Max spread updated: \[currency_id, spread\]
 		MaxSpreadUpdated(CurrencyId, Price),
+
+		/// Relative spread set: \[who, pool_id, currency_id, bid_bps, ask_bps\]
+		RelativeSpreadSet(AccountId, LiquidityPoolId, CurrencyId, SpreadBps, SpreadBps),
+
+		/// Relative max spread updated: \[currency_id, spread_bps\]
+		RelativeMaxSpreadUpdated(CurrencyId, Option<SpreadBps>),
+
+		/// Volatility factor set: \[who, pool_id, currency_id, factor\]
+		VolatilityFactorSet(AccountId, LiquidityPoolId, CurrencyId, Option<Price>),
+
+		/// Pool configured: \[who, pool_id, currency_ids\]
+		PoolConfigured(AccountId, LiquidityPoolId, Vec<CurrencyId>),
 	}
 );
 
@@ -103,9 +198,21 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		const MaxConfigs: u32 = T::MaxConfigs::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() == Releases::V1_0_0 {
+				migrations::migrate_to_v2::<T>();
+				StorageVersion::put(Releases::V2_0_0);
+				T::MaximumBlockWeight::get()
+			} else {
+				0
+			}
+		}
+
 		/// Set bid and ask spread of `currency_id` in `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::set_spread()]
 		pub fn set_spread(
 			origin,
@@ -122,9 +229,67 @@ decl_module! {
 			})?;
 		}
 
+		/// Set bid and ask spread of `currency_id` in `pool_id`, in basis points of the oracle price.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_relative_spread()]
+		pub fn set_relative_spread(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			currency_id: CurrencyId,
+			#[compact] bid: SpreadBps,
+			#[compact] ask: SpreadBps
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_set_relative_spread(&who, pool_id, currency_id, bid, ask)?;
+				Self::deposit_event(RawEvent::RelativeSpreadSet(who, pool_id, currency_id, bid, ask));
+				Ok(())
+			})?;
+		}
+
+		/// Set volatility factor of `currency_id` in `pool_id`, to add `factor * volatility * price`
+		/// to spreads. `None` to stop adjusting spreads by volatility.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::set_volatility_factor()]
+		pub fn set_volatility_factor(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			currency_id: CurrencyId,
+			factor: Option<Price>
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				ensure!(
+					Self::has_role(pool_id, &who, PoolRole::Operator),
+					Error::<T>::NoPermission
+				);
+				PoolCurrencyOptions::mutate(pool_id, currency_id, |o| o.volatility_factor = factor);
+				Self::deposit_event(RawEvent::VolatilityFactorSet(who, pool_id, currency_id, factor));
+				Ok(())
+			})?;
+		}
+
+		/// Configure `pool_id` atomically: spreads, volatility factor, additional collateral ratio and
+		/// enabled state of each currency in `configs`. Nothing is applied if any config is invalid. At
+		/// most `MaxConfigs` configs are allowed.
+		///
+		/// May only be called from the pool owner or operator.
+		#[weight = T::WeightInfo::configure_pool(configs.len() as u32)]
+		pub fn configure_pool(origin, #[compact] pool_id: LiquidityPoolId, configs: Vec<SyntheticPoolCurrencyConfig>) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_configure_pool(&who, pool_id, &configs)?;
+				let currency_ids = configs.into_iter().map(|c| c.currency_id).collect();
+				Self::deposit_event(RawEvent::PoolConfigured(who, pool_id, currency_ids));
+				Ok(())
+			})?;
+		}
+
 		/// Set additional collateral ratio of `currency_id` in `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::set_additional_collateral_ratio()]
 		pub fn set_additional_collateral_ratio(
 			origin,
@@ -155,7 +320,7 @@ decl_module! {
 
 		/// Enable or disable synthetic of `currency_id` in `pool_id`.
 		///
-		/// May only be called from the pool owner.
+		/// May only be called from the pool owner or operator.
 		#[weight = T::WeightInfo::set_synthetic_enabled()]
 		pub fn set_synthetic_enabled(
 			origin,
@@ -183,6 +348,25 @@ decl_module! {
 				Ok(())
 			})?;
 		}
+
+		/// Set max spread of `currency_id`, in basis points of the oracle price, or remove it if `None`.
+		///
+		/// May only be called from `UpdateOrigin`.
+		#[weight = T::WeightInfo::set_relative_max_spread()]
+		pub fn set_relative_max_spread(origin, currency_id: CurrencyId, max_spread: Option<SpreadBps>) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				match max_spread {
+					Some(max_spread) => {
+						ensure!(max_spread < BPS_PER_UNIT, Error::<T>::SpreadTooHigh);
+						RelativeMaxSpread::insert(currency_id, max_spread);
+					}
+					None => RelativeMaxSpread::remove(currency_id),
+				}
+				Self::deposit_event(RawEvent::RelativeMaxSpreadUpdated(currency_id, max_spread));
+				Ok(())
+			})?;
+		}
 	}
 }
 
@@ -194,6 +378,12 @@ decl_error! {
 
 		/// Spread is higher than max allowed.
 		SpreadTooHigh,
+
+		/// A currency is configured more than once.
+		DuplicatedCurrency,
+
+		/// Too many configs in `configure_pool`.
+		TooManyConfigs,
 	}
 }
 
@@ -202,10 +392,30 @@ impl<T: Config> LiquidityPools<T::AccountId> for Module<T> {
 		T::BaseLiquidityPools::all()
 	}
 
+	fn pools_with_status(status: PoolStatus) -> Vec<LiquidityPoolId> {
+		T::BaseLiquidityPools::pools_with_status(status)
+	}
+
+	fn status(pool_id: LiquidityPoolId) -> Option<PoolStatus> {
+		T::BaseLiquidityPools::status(pool_id)
+	}
+
+	fn settle_wind_down(pool_id: LiquidityPoolId) {
+		T::BaseLiquidityPools::settle_wind_down(pool_id)
+	}
+
 	fn is_owner(pool_id: LiquidityPoolId, who: &T::AccountId) -> bool {
 		T::BaseLiquidityPools::is_owner(pool_id, who)
 	}
 
+	fn has_role(pool_id: LiquidityPoolId, who: &T::AccountId, role: PoolRole) -> bool {
+		T::BaseLiquidityPools::has_role(pool_id, who, role)
+	}
+
+	fn owner(pool_id: LiquidityPoolId) -> Option<T::AccountId> {
+		T::BaseLiquidityPools::owner(pool_id)
+	}
+
 	/// Check if pool exists
 	fn pool_exists(pool_id: LiquidityPoolId) -> bool {
 		T::BaseLiquidityPools::pool_exists(pool_id)
@@ -216,6 +426,10 @@ impl<T: Config> LiquidityPools<T::AccountId> for Module<T> {
 		T::BaseLiquidityPools::liquidity(pool_id)
 	}
 
+	fn locked_liquidity(pool_id: LiquidityPoolId) -> Balance {
+		T::BaseLiquidityPools::locked_liquidity(pool_id)
+	}
+
 	/// Deposit some amount of collateral to `pool_id`, from `source`.
 	fn deposit_liquidity(source: &T::AccountId, pool_id: LiquidityPoolId, amount: Balance) -> DispatchResult {
 		T::BaseLiquidityPools::deposit_liquidity(source, pool_id, amount)
@@ -228,12 +442,12 @@ impl<T: Config> LiquidityPools<T::AccountId> for Module<T> {
 }
 
 impl<T: Config> SyntheticProtocolLiquidityPools<T::AccountId> for Module<T> {
-	fn bid_spread(pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Option<Price> {
-		Self::pool_currency_options(pool_id, currency_id).bid_spread
+	fn bid_spread(pool_id: LiquidityPoolId, currency_id: CurrencyId, price: Price) -> Option<Price> {
+		Self::spreads_at_price(pool_id, currency_id, price).0
 	}
 
-	fn ask_spread(pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Option<Price> {
-		Self::pool_currency_options(pool_id, currency_id).ask_spread
+	fn ask_spread(pool_id: LiquidityPoolId, currency_id: CurrencyId, price: Price) -> Option<Price> {
+		Self::spreads_at_price(pool_id, currency_id, price).1
 	}
 
 	fn additional_collateral_ratio(pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Permill {
@@ -243,8 +457,10 @@ impl<T: Config> SyntheticProtocolLiquidityPools<T::AccountId> for Module<T> {
 			.map_or(min_ratio, |ratio| ratio.max(min_ratio))
 	}
 
+	/// Only active pools could mint, disabled and winding down pools are only for redeeming.
 	fn can_mint(pool_id: LiquidityPoolId, currency_id: CurrencyId) -> bool {
-		Self::pool_currency_options(pool_id, currency_id).synthetic_enabled
+		T::BaseLiquidityPools::status(pool_id) == Some(PoolStatus::Active)
+			&& Self::pool_currency_options(pool_id, currency_id).synthetic_enabled
 	}
 }
 
@@ -259,6 +475,47 @@ impl<T: Config> Module<T> {
 		}
 		option
 	}
+
+	/// The lower one of max spread and relative max spread at oracle `price`.
+	pub fn max_spread_at_price(currency_id: CurrencyId, price: Price) -> Option<Price> {
+		let relative_max_spread = Self::relative_max_spread(currency_id).map(|bps| fixed_u128_from_bps(price, bps));
+		match (Self::max_spread(currency_id), relative_max_spread) {
+			(Some(a), Some(b)) => Some(a.min(b)),
+			(a, b) => a.or(b),
+		}
+	}
+
+	/// Bid and ask spread of `currency_id` in `pool_id` at oracle `price`, adjusted by volatility
+	/// and capped by max spread.
+	pub fn spreads_at_price(
+		pool_id: LiquidityPoolId,
+		currency_id: CurrencyId,
+		price: Price,
+	) -> (Option<Price>, Option<Price>) {
+		let option = PoolCurrencyOptions::get(pool_id, currency_id);
+		let (bid_spread, ask_spread) = match option.spread_mode {
+			SpreadMode::Absolute => (option.bid_spread, option.ask_spread),
+			SpreadMode::Relative => (
+				option.relative_bid_spread.map(|bps| fixed_u128_from_bps(price, bps)),
+				option.relative_ask_spread.map(|bps| fixed_u128_from_bps(price, bps)),
+			),
+		};
+		let add_on = option.volatility_factor.map_or_else(Zero::zero, |k| {
+			let volatility = T::VolatilityProvider::volatility(currency_id).unwrap_or_else(Zero::zero);
+			k.saturating_mul(volatility).saturating_mul(price)
+		});
+		let (bid_spread, ask_spread) = (
+			bid_spread.map(|s| s.saturating_add(add_on)),
+			ask_spread.map(|s| s.saturating_add(add_on)),
+		);
+		match Self::max_spread_at_price(currency_id, price) {
+			Some(max_spread) => (
+				bid_spread.map(|s| s.min(max_spread)),
+				ask_spread.map(|s| s.min(max_spread)),
+			),
+			None => (bid_spread, ask_spread),
+		}
+	}
 }
 
 // Dispatchable calls implementation
@@ -270,13 +527,17 @@ impl<T: Config> Module<T> {
 		bid: Price,
 		ask: Price,
 	) -> DispatchResult {
-		ensure!(Self::is_owner(pool_id, who), Error::<T>::NoPermission);
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
 
 		if let Some(max_spread) = Self::max_spread(&currency_id) {
 			ensure!(ask <= max_spread && bid <= max_spread, Error::<T>::SpreadTooHigh);
 		}
 
 		PoolCurrencyOptions::mutate(pool_id, currency_id, |o| {
+			o.spread_mode = SpreadMode::Absolute;
 			o.bid_spread = Some(bid);
 			o.ask_spread = Some(ask);
 		});
@@ -284,13 +545,40 @@ impl<T: Config> Module<T> {
 		Ok(())
 	}
 
+	fn do_set_relative_spread(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		currency_id: CurrencyId,
+		bid: SpreadBps,
+		ask: SpreadBps,
+	) -> DispatchResult {
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
+
+		let max_spread = Self::relative_max_spread(&currency_id).unwrap_or(BPS_PER_UNIT - 1);
+		ensure!(ask <= max_spread && bid <= max_spread, Error::<T>::SpreadTooHigh);
+
+		PoolCurrencyOptions::mutate(pool_id, currency_id, |o| {
+			o.spread_mode = SpreadMode::Relative;
+			o.relative_bid_spread = Some(bid);
+			o.relative_ask_spread = Some(ask);
+		});
+
+		Ok(())
+	}
+
 	fn do_set_additional_collateral_ratio(
 		who: &T::AccountId,
 		pool_id: LiquidityPoolId,
 		currency_id: CurrencyId,
 		ratio: Option<Permill>,
 	) -> DispatchResult {
-		ensure!(Self::is_owner(pool_id, who), Error::<T>::NoPermission);
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
 		PoolCurrencyOptions::mutate(pool_id, currency_id, |o| o.additional_collateral_ratio = ratio);
 		Ok(())
 	}
@@ -301,10 +589,35 @@ impl<T: Config> Module<T> {
 		currency_id: CurrencyId,
 		enabled: bool,
 	) -> DispatchResult {
-		ensure!(Self::is_owner(pool_id, who), Error::<T>::NoPermission);
+		ensure!(
+			Self::has_role(pool_id, who, PoolRole::Operator),
+			Error::<T>::NoPermission
+		);
 		PoolCurrencyOptions::mutate(pool_id, currency_id, |o| o.synthetic_enabled = enabled);
 		Ok(())
 	}
+
+	fn do_configure_pool(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		configs: &[SyntheticPoolCurrencyConfig],
+	) -> DispatchResult {
+		ensure!(configs.len() as u32 <= T::MaxConfigs::get(), Error::<T>::TooManyConfigs);
+
+		let mut currency_ids = BTreeSet::new();
+		for config in configs {
+			let currency_id = config.currency_id;
+			ensure!(currency_ids.insert(currency_id), Error::<T>::DuplicatedCurrency);
+			match config.spread {
+				Spreads::Absolute { bid, ask } => Self::do_set_spread(who, pool_id, currency_id, bid, ask)?,
+				Spreads::Relative { bid, ask } => Self::do_set_relative_spread(who, pool_id, currency_id, bid, ask)?,
+			}
+			PoolCurrencyOptions::mutate(pool_id, currency_id, |o| o.volatility_factor = config.volatility_factor);
+			Self::do_set_additional_collateral_ratio(who, pool_id, currency_id, config.additional_collateral_ratio)?;
+			Self::do_set_synthetic_enabled(who, pool_id, currency_id, config.synthetic_enabled)?;
+		}
+		Ok(())
+	}
 }
 
 impl<T: Config> OnDisableLiquidityPool for Module<T> {
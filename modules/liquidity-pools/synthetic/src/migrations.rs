@@ -0,0 +1,28 @@
+//! Storage migrations.
+
+use super::*;
+
+/// `SyntheticPoolCurrencyOption` in `V1_0_0`.
+#[derive(Decode)]
+#[allow(dead_code)]
+pub struct OldSyntheticPoolCurrencyOption {
+	pub bid_spread: Option<Price>,
+	pub ask_spread: Option<Price>,
+	pub additional_collateral_ratio: Option<Permill>,
+	pub synthetic_enabled: bool,
+}
+
+/// Migrate storage from `V1_0_0` to `V2_0_0`.
+///
+/// Pool currency options gained relative spreads, in absolute spread mode.
+pub fn migrate_to_v2<T: Config>() {
+	PoolCurrencyOptions::translate::<OldSyntheticPoolCurrencyOption, _>(|_, _, old| {
+		Some(SyntheticPoolCurrencyOption {
+			bid_spread: old.bid_spread,
+			ask_spread: old.ask_spread,
+			additional_collateral_ratio: old.additional_collateral_ratio,
+			synthetic_enabled: old.synthetic_enabled,
+			..Default::default()
+		})
+	});
+}
@@ -129,6 +129,9 @@ impl BaseLiquidityPoolManager<LiquidityPoolId, Balance> for PoolManager {
 parameter_types! {
 	pub const SyntheticLiquidityPoolsModuleId: ModuleId = MODULE_ID;
 	pub const IdentityDeposit: Balance = 1000;
+	pub const TransferExpiry: BlockNumber = 10;
+	pub const MaxPendingWithdrawals: u32 = 3;
+	pub const MaxConfigs: u32 = 2;
 }
 
 pub type SyntheticInstance = module_base_liquidity_pools::Instance1;
@@ -140,10 +143,14 @@ impl module_base_liquidity_pools::Config<SyntheticInstance> for Runtime {
 	type ExistentialDeposit = ExistentialDeposit;
 	type IdentityDeposit = IdentityDeposit;
 	type IdentityDepositCurrency = pallet_balances::Module<Self>;
+	type TransferExpiry = TransferExpiry;
+	type MaxPendingWithdrawals = MaxPendingWithdrawals;
 	type ModuleId = SyntheticLiquidityPoolsModuleId;
 	type OnDisableLiquidityPool = ModuleLiquidityPools;
 	type OnRemoveLiquidityPool = ModuleLiquidityPools;
 	type UpdateOrigin = EnsureSignedBy<UpdateOrigin, AccountId>;
+	type ShareCurrency = orml_tokens::Module<Runtime>;
+	type ShareCurrencyId = module_base_liquidity_pools::NoShareCurrency;
 	type WeightInfo = ();
 }
 pub type BaseLiquidityPools = module_base_liquidity_pools::Module<Runtime, SyntheticInstance>;
@@ -152,6 +159,8 @@ impl Config for Runtime {
 	type Event = ();
 	type BaseLiquidityPools = module_base_liquidity_pools::Module<Runtime, SyntheticInstance>;
 	type UpdateOrigin = EnsureSignedBy<UpdateOrigin, AccountId>;
+	type VolatilityProvider = ();
+	type MaxConfigs = MaxConfigs;
 	type WeightInfo = ();
 }
 pub type ModuleLiquidityPools = Module<Runtime>;
@@ -3,8 +3,8 @@
 use super::*;
 use mock::*;
 
-use frame_support::{assert_noop, assert_ok};
-use sp_runtime::Permill;
+use frame_support::{assert_noop, assert_ok, storage, traits::OnRuntimeUpgrade};
+use sp_runtime::{FixedPointNumber, Permill};
 
 use primitives::CurrencyId;
 use traits::{LiquidityPools, SyntheticProtocolLiquidityPools};
@@ -28,6 +28,10 @@ fn should_disable_pool() {
 			SyntheticPoolCurrencyOption {
 				bid_spread: None,
 				ask_spread: None,
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				volatility_factor: None,
 				additional_collateral_ratio: None,
 				synthetic_enabled: true,
 			},
@@ -40,6 +44,24 @@ fn should_disable_pool() {
 	})
 }
 
+#[test]
+fn cannot_mint_in_disabled_pool() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(BaseLiquidityPools::disable_pool(Origin::signed(ALICE), 0));
+		assert_ok!(ModuleLiquidityPools::set_synthetic_enabled(
+			Origin::signed(ALICE),
+			0,
+			CurrencyId::AUSD,
+			true,
+		));
+		assert_eq!(
+			<ModuleLiquidityPools as SyntheticProtocolLiquidityPools<AccountId>>::can_mint(0, CurrencyId::AUSD),
+			false
+		);
+	})
+}
+
 #[test]
 fn should_remove_pool() {
 	new_test_ext().execute_with(|| {
@@ -77,6 +99,10 @@ fn should_set_spread() {
 		let pool_option = SyntheticPoolCurrencyOption {
 			bid_spread: Some(Price::from_inner(80)),
 			ask_spread: Some(Price::from_inner(60)),
+			spread_mode: SpreadMode::Absolute,
+			relative_bid_spread: None,
+			relative_ask_spread: None,
+			volatility_factor: None,
 			additional_collateral_ratio: None,
 			synthetic_enabled: false,
 		};
@@ -87,11 +113,19 @@ fn should_set_spread() {
 		);
 
 		assert_eq!(
-			<ModuleLiquidityPools as SyntheticProtocolLiquidityPools<AccountId>>::bid_spread(0, CurrencyId::AUSD),
+			<ModuleLiquidityPools as SyntheticProtocolLiquidityPools<AccountId>>::bid_spread(
+				0,
+				CurrencyId::AUSD,
+				Price::one()
+			),
 			Some(Price::from_inner(80))
 		);
 		assert_eq!(
-			<ModuleLiquidityPools as SyntheticProtocolLiquidityPools<AccountId>>::ask_spread(0, CurrencyId::AUSD),
+			<ModuleLiquidityPools as SyntheticProtocolLiquidityPools<AccountId>>::ask_spread(
+				0,
+				CurrencyId::AUSD,
+				Price::one()
+			),
 			Some(Price::from_inner(60))
 		);
 	})
@@ -146,6 +180,10 @@ fn should_set_max_spread() {
 			SyntheticPoolCurrencyOption {
 				bid_spread: Some(Price::from_inner(28)),
 				ask_spread: Some(Price::from_inner(29)),
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				volatility_factor: None,
 				additional_collateral_ratio: None,
 				synthetic_enabled: false,
 			},
@@ -153,6 +191,216 @@ fn should_set_max_spread() {
 	});
 }
 
+#[test]
+fn should_set_relative_spread() {
+	new_test_ext().execute_with(|| {
+		let price = Price::saturating_from_integer(2);
+		let bid_spread = || {
+			<ModuleLiquidityPools as SyntheticProtocolLiquidityPools<AccountId>>::bid_spread(0, CurrencyId::FEUR, price)
+		};
+		let ask_spread = || {
+			<ModuleLiquidityPools as SyntheticProtocolLiquidityPools<AccountId>>::ask_spread(0, CurrencyId::FEUR, price)
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+
+		// 0.1% bid and 0.2% ask of oracle price
+		assert_ok!(ModuleLiquidityPools::set_relative_spread(
+			Origin::signed(ALICE),
+			0,
+			CurrencyId::FEUR,
+			10,
+			20
+		));
+		assert_eq!(bid_spread(), Some(Price::saturating_from_rational(2, 1000)));
+		assert_eq!(ask_spread(), Some(Price::saturating_from_rational(4, 1000)));
+
+		// absolute max spread caps relative spreads
+		assert_ok!(ModuleLiquidityPools::set_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			CurrencyId::FEUR,
+			Price::saturating_from_rational(3, 1000)
+		));
+		assert_eq!(ask_spread(), Some(Price::saturating_from_rational(3, 1000)));
+
+		assert_ok!(ModuleLiquidityPools::set_relative_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			CurrencyId::FEUR,
+			Some(15)
+		));
+		assert_noop!(
+			ModuleLiquidityPools::set_relative_spread(Origin::signed(ALICE), 0, CurrencyId::FEUR, 10, 20),
+			Error::<Runtime>::SpreadTooHigh
+		);
+		assert_eq!(
+			ModuleLiquidityPools::max_spread_at_price(CurrencyId::FEUR, price),
+			Some(Price::saturating_from_rational(3, 1000))
+		);
+		assert_ok!(ModuleLiquidityPools::set_relative_spread(
+			Origin::signed(ALICE),
+			0,
+			CurrencyId::FEUR,
+			10,
+			15
+		));
+		assert_eq!(ask_spread(), Some(Price::saturating_from_rational(3, 1000)));
+
+		assert_ok!(ModuleLiquidityPools::set_relative_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			CurrencyId::FEUR,
+			None
+		));
+		assert_eq!(ModuleLiquidityPools::relative_max_spread(CurrencyId::FEUR), None);
+		assert_eq!(
+			ModuleLiquidityPools::max_spread_at_price(CurrencyId::FEUR, price),
+			Some(Price::saturating_from_rational(3, 1000))
+		);
+	})
+}
+
+#[test]
+fn should_configure_pool() {
+	new_test_ext().execute_with(|| {
+		let config = |currency_id, bid| SyntheticPoolCurrencyConfig {
+			currency_id,
+			spread: Spreads::Absolute {
+				bid,
+				ask: Price::from_inner(60),
+			},
+			volatility_factor: Some(Price::saturating_from_integer(2)),
+			additional_collateral_ratio: Some(Permill::from_percent(20)),
+			synthetic_enabled: true,
+		};
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_max_spread(
+			Origin::signed(UpdateOrigin::get()),
+			CurrencyId::FJPY,
+			Price::from_inner(100)
+		));
+
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(
+				Origin::signed(BOB),
+				0,
+				vec![config(CurrencyId::FEUR, Price::from_inner(80))]
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(
+				Origin::signed(ALICE),
+				0,
+				vec![
+					config(CurrencyId::FEUR, Price::from_inner(80)),
+					config(CurrencyId::FEUR, Price::from_inner(90))
+				]
+			),
+			Error::<Runtime>::DuplicatedCurrency
+		);
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(
+				Origin::signed(ALICE),
+				0,
+				vec![
+					config(CurrencyId::FEUR, Price::from_inner(80)),
+					config(CurrencyId::FJPY, Price::from_inner(90)),
+					config(CurrencyId::FJPY, Price::from_inner(90))
+				]
+			),
+			Error::<Runtime>::TooManyConfigs
+		);
+		// nothing applied if any currency is invalid
+		assert_noop!(
+			ModuleLiquidityPools::configure_pool(
+				Origin::signed(ALICE),
+				0,
+				vec![
+					config(CurrencyId::FEUR, Price::from_inner(80)),
+					config(CurrencyId::FJPY, Price::from_inner(101))
+				]
+			),
+			Error::<Runtime>::SpreadTooHigh
+		);
+
+		assert_ok!(ModuleLiquidityPools::configure_pool(
+			Origin::signed(ALICE),
+			0,
+			vec![
+				config(CurrencyId::FEUR, Price::from_inner(80)),
+				config(CurrencyId::FJPY, Price::from_inner(100))
+			]
+		));
+		assert_eq!(
+			ModuleLiquidityPools::pool_currency_options(0, CurrencyId::FEUR),
+			SyntheticPoolCurrencyOption {
+				bid_spread: Some(Price::from_inner(80)),
+				ask_spread: Some(Price::from_inner(60)),
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				volatility_factor: Some(Price::saturating_from_integer(2)),
+				additional_collateral_ratio: Some(Permill::from_percent(20)),
+				synthetic_enabled: true,
+			}
+		);
+		assert!(ModuleLiquidityPools::pool_currency_options(0, CurrencyId::FJPY).synthetic_enabled);
+
+		// relative spreads
+		assert_ok!(ModuleLiquidityPools::configure_pool(
+			Origin::signed(ALICE),
+			0,
+			vec![SyntheticPoolCurrencyConfig {
+				spread: Spreads::Relative { bid: 10, ask: 20 },
+				volatility_factor: None,
+				..config(CurrencyId::FEUR, Price::from_inner(80))
+			}]
+		));
+		let option = ModuleLiquidityPools::pool_currency_options(0, CurrencyId::FEUR);
+		assert_eq!(option.spread_mode, SpreadMode::Relative);
+		assert_eq!(option.relative_bid_spread, Some(10));
+		assert_eq!(option.relative_ask_spread, Some(20));
+		assert_eq!(option.volatility_factor, None);
+	})
+}
+
+#[test]
+fn should_set_volatility_factor() {
+	new_test_ext().execute_with(|| {
+		let factor = Price::saturating_from_rational(1, 2);
+		assert_ok!(BaseLiquidityPools::create_pool(Origin::signed(ALICE)));
+		assert_ok!(ModuleLiquidityPools::set_spread(
+			Origin::signed(ALICE),
+			0,
+			CurrencyId::FEUR,
+			Price::saturating_from_rational(1, 1000),
+			Price::saturating_from_rational(2, 1000)
+		));
+
+		assert_noop!(
+			ModuleLiquidityPools::set_volatility_factor(Origin::signed(BOB), 0, CurrencyId::FEUR, Some(factor)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(ModuleLiquidityPools::set_volatility_factor(
+			Origin::signed(ALICE),
+			0,
+			CurrencyId::FEUR,
+			Some(factor)
+		));
+		assert_eq!(
+			ModuleLiquidityPools::pool_currency_options(0, CurrencyId::FEUR).volatility_factor,
+			Some(factor)
+		);
+
+		// volatility not tracked, no add-on
+		assert_eq!(
+			ModuleLiquidityPools::spreads_at_price(0, CurrencyId::FEUR, Price::saturating_from_integer(2)),
+			(
+				Some(Price::saturating_from_rational(1, 1000)),
+				Some(Price::saturating_from_rational(2, 1000))
+			)
+		);
+	})
+}
+
 #[test]
 fn should_set_additional_collateral_ratio() {
 	new_test_ext().execute_with(|| {
@@ -176,6 +424,10 @@ fn should_set_additional_collateral_ratio() {
 		let pool_option = SyntheticPoolCurrencyOption {
 			bid_spread: None,
 			ask_spread: None,
+			spread_mode: SpreadMode::Absolute,
+			relative_bid_spread: None,
+			relative_ask_spread: None,
+			volatility_factor: None,
 			additional_collateral_ratio: Some(Permill::from_percent(120)),
 			synthetic_enabled: false,
 		};
@@ -308,6 +560,10 @@ fn should_set_synthetic_enabled() {
 		let pool_option = SyntheticPoolCurrencyOption {
 			bid_spread: None,
 			ask_spread: None,
+			spread_mode: SpreadMode::Absolute,
+			relative_bid_spread: None,
+			relative_ask_spread: None,
+			volatility_factor: None,
 			additional_collateral_ratio: None,
 			synthetic_enabled: true,
 		};
@@ -323,3 +579,36 @@ fn should_set_synthetic_enabled() {
 		);
 	});
 }
+
+#[test]
+fn migrate_to_v2_should_work() {
+	new_test_ext().execute_with(|| {
+		storage::unhashed::put(
+			&PoolCurrencyOptions::hashed_key_for(0, CurrencyId::FEUR),
+			&(
+				Some(Price::from_inner(1)),
+				Some(Price::from_inner(2)),
+				Some(Permill::from_percent(10)),
+				true,
+			),
+		);
+
+		assert_eq!(ModuleLiquidityPools::storage_version(), Releases::V1_0_0);
+		ModuleLiquidityPools::on_runtime_upgrade();
+		assert_eq!(ModuleLiquidityPools::storage_version(), Releases::V2_0_0);
+
+		assert_eq!(
+			PoolCurrencyOptions::get(0, CurrencyId::FEUR),
+			SyntheticPoolCurrencyOption {
+				bid_spread: Some(Price::from_inner(1)),
+				ask_spread: Some(Price::from_inner(2)),
+				spread_mode: SpreadMode::Absolute,
+				relative_bid_spread: None,
+				relative_ask_spread: None,
+				volatility_factor: None,
+				additional_collateral_ratio: Some(Permill::from_percent(10)),
+				synthetic_enabled: true,
+			}
+		);
+	})
+}
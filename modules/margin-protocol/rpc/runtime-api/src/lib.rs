@@ -7,16 +7,19 @@
 #![allow(clippy::unnecessary_mut_passed)]
 
 use codec::{Codec, Decode, Encode};
-use laminar_primitives::LiquidityPoolId;
+use laminar_primitives::{
+	Balance, BlockNumber, Leverage, LiquidityLock, LiquidityPoolId, Moment, PendingWithdrawal, PoolStatus,
+	PositionLeverage, Price, SpreadTier, SwapRate, SwapRateAccumulation, TradingPair, WithdrawalId,
+};
 use sp_arithmetic::FixedI128;
-use sp_core::RuntimeDebug;
+use sp_core::{RuntimeDebug, RuntimeString};
 use sp_std::prelude::*;
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug)]
 pub struct MarginTraderState {
 	pub equity: FixedI128,
 	pub margin_held: FixedI128,
@@ -26,18 +29,128 @@ pub struct MarginTraderState {
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-#[derive(Encode, Decode, Eq, PartialEq, Default, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug)]
+pub struct MarginTraderStats {
+	pub realized_pl: FixedI128,
+	pub swap: FixedI128,
+	pub volume: Balance,
+	pub positions_opened: u32,
+	pub positions_closed: u32,
+	pub positions_stopped_out: u32,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug)]
 pub struct MarginPoolState {
 	pub enp: FixedI128,
 	pub ell: FixedI128,
 	pub required_deposit: FixedI128,
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct MarginPositionState {
+	pub position_id: u64,
+	pub pool_id: LiquidityPoolId,
+	pub pair: TradingPair,
+	pub is_long: bool,
+	pub leveraged_held: FixedI128,
+	pub leveraged_debits: FixedI128,
+	pub open_price: FixedI128,
+	pub bid_price: Option<FixedI128>,
+	pub ask_price: Option<FixedI128>,
+	pub unrealized_pl: Option<FixedI128>,
+	pub accumulated_swap_rate: Option<FixedI128>,
+	pub margin_held: FixedI128,
+	/// `None` if failed to calculate, e.g. no price.
+	pub liquidation_prices: Option<MarginLiquidationPrices>,
+}
+
+/// Prices at which a position's trader would be margin called and stopped out, `None` if unreachable.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct MarginLiquidationPrices {
+	pub margin_call_price: Option<FixedI128>,
+	pub stop_out_price: Option<FixedI128>,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct MarginTradeSimulation {
+	pub execution_price: FixedI128,
+	pub margin_held: FixedI128,
+	pub margin_level: FixedI128,
+	pub enp: FixedI128,
+	pub ell: FixedI128,
+	pub error: Option<RuntimeString>,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct MarginPairExposure {
+	pub pair: TradingPair,
+	pub positions_count: u64,
+	pub net_position: FixedI128,
+	pub longest_leg: FixedI128,
+	pub unrealized_pl: FixedI128,
+	pub accumulated_swap_rate: FixedI128,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct MarginPoolMarket {
+	pub pair: TradingPair,
+	pub bid_spread: Option<Price>,
+	pub ask_spread: Option<Price>,
+	pub bid_price: Option<FixedI128>,
+	pub ask_price: Option<FixedI128>,
+	pub enabled_leverages: Vec<Leverage>,
+	/// Min and max leverage multiplier allowed in addition to `enabled_leverages`, inclusive.
+	pub leverage_bounds: Option<(u8, u8)>,
+	pub min_leveraged_amount: Balance,
+	pub max_leveraged_amounts: Vec<(Leverage, Balance)>,
+	pub swap_rate: SwapRate,
+	pub accumulated_swap_rate: SwapRate,
+	pub next_accumulate_time: Option<Moment>,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+pub struct MarginPoolInfo<AccountId> {
+	pub pool_id: LiquidityPoolId,
+	pub owner: AccountId,
+	pub status: PoolStatus,
+	pub is_margin_called: bool,
+	pub markets: Vec<MarginPoolMarket>,
+}
+
 sp_api::decl_runtime_apis! {
 	pub trait MarginProtocolApi<AccountId> where
 		AccountId: Codec,
 	{
 		fn trader_state(who: AccountId, pool_id: LiquidityPoolId) -> MarginTraderState;
+		fn trader_stats(who: AccountId, pool_id: LiquidityPoolId) -> MarginTraderStats;
 		fn pool_state(pool_id: LiquidityPoolId) -> Option<MarginPoolState>;
+		fn trader_positions(who: AccountId) -> Vec<MarginPositionState>;
+		fn simulate_open_position(
+			who: AccountId,
+			pool_id: LiquidityPoolId,
+			pair: TradingPair,
+			leverage: PositionLeverage,
+			leveraged_amount: Balance,
+			price: Price,
+		) -> MarginTradeSimulation;
+		fn simulate_close_position(who: AccountId, position_id: u64, price: Price) -> MarginTradeSimulation;
+		fn pool_exposure(pool_id: LiquidityPoolId) -> Vec<MarginPairExposure>;
+		fn pool_pending_withdrawals(
+			pool_id: LiquidityPoolId,
+		) -> Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)>;
+		fn pool_liquidity_lock(pool_id: LiquidityPoolId) -> Option<LiquidityLock<BlockNumber>>;
+		fn pool_spread_tiers(pool_id: LiquidityPoolId, pair: TradingPair) -> Vec<SpreadTier>;
+		fn pool_swap_rate(pool_id: LiquidityPoolId, pair: TradingPair) -> SwapRate;
+		fn pool_markets(pool_id: LiquidityPoolId) -> Vec<MarginPoolMarket>;
+		fn all_pools() -> Vec<MarginPoolInfo<AccountId>>;
+		fn swap_rate_history(pool_id: LiquidityPoolId, pair: TradingPair) -> Vec<SwapRateAccumulation<Moment>>;
+		fn pools_of_trading_pair(pair: TradingPair) -> Vec<LiquidityPoolId>;
 	}
 }
@@ -1,10 +1,24 @@
 use codec::Codec;
-use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use futures::{
+	future::ready,
+	stream::{self, StreamExt, TryStreamExt},
+};
+use jsonrpc_core::{
+	futures::{future, Future, Sink},
+	BoxFuture, Error as RpcError, ErrorCode, Result,
+};
 use jsonrpc_derive::rpc;
-use laminar_primitives::LiquidityPoolId;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use laminar_primitives::{
+	Balance, BlockNumber, LiquidityLock, LiquidityPoolId, Moment, PendingWithdrawal, PositionLeverage, Price,
+	SpreadTier, SwapRate, SwapRateAccumulation, TradingPair, WithdrawalId,
+};
 pub use margin_protocol_rpc_runtime_api::{
-	MarginPoolState, MarginProtocolApi as MarginProtocolRuntimeApi, MarginTraderState,
+	MarginPairExposure, MarginPoolInfo, MarginPoolMarket, MarginPoolState, MarginPositionState,
+	MarginProtocolApi as MarginProtocolRuntimeApi, MarginTradeSimulation, MarginTraderState, MarginTraderStats,
 };
+use sc_client_api::BlockchainEvents;
+use serde::Serialize;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
@@ -18,10 +32,125 @@ pub trait MarginProtocolApi<BlockHash, AccountId> {
 		who: AccountId,
 		pool_id: LiquidityPoolId,
 		at: Option<BlockHash>,
-	) -> Result<MarginTraderState>;
+	) -> BoxFuture<MarginTraderState>;
+
+	#[rpc(name = "margin_traderStats")]
+	fn trader_stats(
+		&self,
+		who: AccountId,
+		pool_id: LiquidityPoolId,
+		at: Option<BlockHash>,
+	) -> BoxFuture<MarginTraderStats>;
 
 	#[rpc(name = "margin_poolState")]
-	fn pool_state(&self, pool_id: LiquidityPoolId, at: Option<BlockHash>) -> Result<Option<MarginPoolState>>;
+	fn pool_state(&self, pool_id: LiquidityPoolId, at: Option<BlockHash>) -> BoxFuture<Option<MarginPoolState>>;
+
+	#[rpc(name = "margin_traderPositions")]
+	fn trader_positions(&self, who: AccountId, at: Option<BlockHash>) -> BoxFuture<Vec<MarginPositionState>>;
+
+	#[rpc(name = "margin_simulateOpenPosition")]
+	fn simulate_open_position(
+		&self,
+		who: AccountId,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		leverage: PositionLeverage,
+		leveraged_amount: Balance,
+		price: Price,
+		at: Option<BlockHash>,
+	) -> BoxFuture<MarginTradeSimulation>;
+
+	#[rpc(name = "margin_simulateClosePosition")]
+	fn simulate_close_position(
+		&self,
+		who: AccountId,
+		position_id: u64,
+		price: Price,
+		at: Option<BlockHash>,
+	) -> BoxFuture<MarginTradeSimulation>;
+
+	#[rpc(name = "margin_poolExposure")]
+	fn pool_exposure(&self, pool_id: LiquidityPoolId, at: Option<BlockHash>) -> BoxFuture<Vec<MarginPairExposure>>;
+
+	#[rpc(name = "margin_poolPendingWithdrawals")]
+	fn pool_pending_withdrawals(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<BlockHash>,
+	) -> BoxFuture<Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)>>;
+
+	#[rpc(name = "margin_poolLiquidityLock")]
+	fn pool_liquidity_lock(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<BlockHash>,
+	) -> BoxFuture<Option<LiquidityLock<BlockNumber>>>;
+
+	#[rpc(name = "margin_poolSpreadTiers")]
+	fn pool_spread_tiers(
+		&self,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		at: Option<BlockHash>,
+	) -> BoxFuture<Vec<SpreadTier>>;
+
+	#[rpc(name = "margin_poolSwapRate")]
+	fn pool_swap_rate(&self, pool_id: LiquidityPoolId, pair: TradingPair, at: Option<BlockHash>)
+		-> BoxFuture<SwapRate>;
+
+	#[rpc(name = "margin_poolMarkets")]
+	fn pool_markets(&self, pool_id: LiquidityPoolId, at: Option<BlockHash>) -> BoxFuture<Vec<MarginPoolMarket>>;
+
+	#[rpc(name = "margin_allPools")]
+	fn all_pools(&self, at: Option<BlockHash>) -> BoxFuture<Vec<MarginPoolInfo<AccountId>>>;
+
+	#[rpc(name = "margin_swapRateHistory")]
+	fn swap_rate_history(
+		&self,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		at: Option<BlockHash>,
+	) -> BoxFuture<Vec<SwapRateAccumulation<Moment>>>;
+
+	#[rpc(name = "margin_poolsOfTradingPair")]
+	fn pools_of_trading_pair(&self, pair: TradingPair, at: Option<BlockHash>) -> BoxFuture<Vec<LiquidityPoolId>>;
+}
+
+#[rpc]
+pub trait MarginProtocolSubscriptionApi<AccountId> {
+	/// RPC metadata
+	type Metadata;
+
+	/// Subscribe to trader state, notified on each new best or finalized block if changed.
+	#[pubsub(subscription = "margin_traderState", subscribe, name = "margin_subscribeTraderState")]
+	fn subscribe_trader_state(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<MarginTraderState>,
+		who: AccountId,
+		pool_id: LiquidityPoolId,
+	);
+
+	/// Unsubscribe from trader state.
+	#[pubsub(
+		subscription = "margin_traderState",
+		unsubscribe,
+		name = "margin_unsubscribeTraderState"
+	)]
+	fn unsubscribe_trader_state(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
+
+	/// Subscribe to pool state, notified on each new best or finalized block if changed.
+	#[pubsub(subscription = "margin_poolState", subscribe, name = "margin_subscribePoolState")]
+	fn subscribe_pool_state(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<Option<MarginPoolState>>,
+		pool_id: LiquidityPoolId,
+	);
+
+	/// Unsubscribe from pool state.
+	#[pubsub(subscription = "margin_poolState", unsubscribe, name = "margin_unsubscribePoolState")]
+	fn unsubscribe_pool_state(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
 }
 
 /// A struct that implements the [`MarginProtocolApi`].
@@ -64,31 +193,370 @@ where
 		who: AccountId,
 		pool_id: LiquidityPoolId,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<MarginTraderState> {
+	) -> BoxFuture<MarginTraderState> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash));
-		api.trader_state(&at, who, pool_id).map_err(|e| RpcError {
-			code: ErrorCode::ServerError(Error::RuntimeError.into()),
-			message: "Unable to get trader state.".into(),
-			data: Some(format!("{:?}", e).into()),
-		})
+		Box::new(future::result(api.trader_state(&at, who, pool_id).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get trader state.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
+	}
+
+	fn trader_stats(
+		&self,
+		who: AccountId,
+		pool_id: LiquidityPoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<MarginTraderStats> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.trader_stats(&at, who, pool_id).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get trader stats.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
 	}
 
 	fn pool_state(
 		&self,
 		pool_id: LiquidityPoolId,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<Option<MarginPoolState>> {
+	) -> BoxFuture<Option<MarginPoolState>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash));
-		api.pool_state(&at, pool_id).map_err(|e| RpcError {
+		Box::new(future::result(api.pool_state(&at, pool_id).map_err(|e| RpcError {
 			code: ErrorCode::ServerError(Error::RuntimeError.into()),
 			message: "Unable to get pool state.".into(),
 			data: Some(format!("{:?}", e).into()),
-		})
+		})))
+	}
+
+	fn trader_positions(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Vec<MarginPositionState>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.trader_positions(&at, who).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get trader positions.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})))
+	}
+
+	fn simulate_open_position(
+		&self,
+		who: AccountId,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		leverage: PositionLeverage,
+		leveraged_amount: Balance,
+		price: Price,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<MarginTradeSimulation> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(
+			api.simulate_open_position(&at, who, pool_id, pair, leverage, leveraged_amount, price)
+				.map_err(|e| RpcError {
+					code: ErrorCode::ServerError(Error::RuntimeError.into()),
+					message: "Unable to simulate open position.".into(),
+					data: Some(format!("{:?}", e).into()),
+				}),
+		))
+	}
+
+	fn simulate_close_position(
+		&self,
+		who: AccountId,
+		position_id: u64,
+		price: Price,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<MarginTradeSimulation> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(
+			api.simulate_close_position(&at, who, position_id, price)
+				.map_err(|e| RpcError {
+					code: ErrorCode::ServerError(Error::RuntimeError.into()),
+					message: "Unable to simulate close position.".into(),
+					data: Some(format!("{:?}", e).into()),
+				}),
+		))
+	}
+
+	fn pool_exposure(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Vec<MarginPairExposure>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pool_exposure(&at, pool_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get pool exposure.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})))
+	}
+
+	fn pool_pending_withdrawals(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pool_pending_withdrawals(&at, pool_id).map_err(
+			|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pool pending withdrawals.".into(),
+				data: Some(format!("{:?}", e).into()),
+			},
+		)))
+	}
+
+	fn pool_liquidity_lock(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Option<LiquidityLock<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pool_liquidity_lock(&at, pool_id).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pool liquidity lock.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
+	}
+
+	fn pool_spread_tiers(
+		&self,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Vec<SpreadTier>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pool_spread_tiers(&at, pool_id, pair).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pool spread tiers.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
+	}
+
+	fn pool_swap_rate(
+		&self,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<SwapRate> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pool_swap_rate(&at, pool_id, pair).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pool swap rate.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
+	}
+
+	fn pool_markets(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Vec<MarginPoolMarket>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pool_markets(&at, pool_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get pool markets.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})))
+	}
+
+	fn all_pools(&self, at: Option<<Block as BlockT>::Hash>) -> BoxFuture<Vec<MarginPoolInfo<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.all_pools(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get all pools.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})))
+	}
+
+	fn swap_rate_history(
+		&self,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Vec<SwapRateAccumulation<Moment>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.swap_rate_history(&at, pool_id, pair).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get swap rate history.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
+	}
+
+	fn pools_of_trading_pair(
+		&self,
+		pair: TradingPair,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Vec<LiquidityPoolId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pools_of_trading_pair(&at, pair).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pools of trading pair.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
+	}
+}
+
+/// A struct that implements the [`MarginProtocolSubscriptionApi`].
+pub struct MarginProtocolSubscription<C, B> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> MarginProtocolSubscription<C, B> {
+	/// Create new `MarginProtocolSubscription` with the given reference to the client and subscription manager.
+	pub fn new(client: Arc<C>, manager: SubscriptionManager) -> Self {
+		Self {
+			client,
+			manager,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block> MarginProtocolSubscription<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + HeaderBackend<Block> + BlockchainEvents<Block>,
+{
+	/// Notify `subscriber` of `state` at the best block, then at each new best or finalized block
+	/// if it changed.
+	fn subscribe<T, F>(&self, subscriber: Subscriber<T>, state: F)
+	where
+		T: Serialize + Clone + PartialEq + Send + 'static,
+		F: Fn(&C, &BlockId<Block>) -> Option<T> + Send + 'static,
+	{
+		let client = self.client.clone();
+		let new_best = self
+			.client
+			.import_notification_stream()
+			.filter(|n| ready(n.is_new_best))
+			.map(|n| n.hash);
+		let finalized = self.client.finality_notification_stream().map(|n| n.hash);
+
+		let mut last = None;
+		let states = stream::once(ready(self.client.info().best_hash))
+			.chain(stream::select(new_best, finalized))
+			.filter_map(move |hash| {
+				let changed = state(&client, &BlockId::hash(hash)).filter(|s| last.as_ref() != Some(s));
+				if let Some(s) = &changed {
+					last = Some(s.clone());
+				}
+				ready(changed)
+			})
+			.map(|s| Ok::<_, ()>(Ok(s)))
+			.boxed()
+			.compat();
+
+		self.manager.add(subscriber, |sink| {
+			sink.sink_map_err(|_| ())
+				.send_all(states)
+				// the stream is never over, so the sink is dropped only if unsubscribed
+				.map(|_| ())
+		});
+	}
+}
+
+impl<C, Block, AccountId> MarginProtocolSubscriptionApi<AccountId> for MarginProtocolSubscription<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+	C::Api: MarginProtocolRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Clone + Send + 'static,
+{
+	type Metadata = sc_rpc_api::Metadata;
+
+	fn subscribe_trader_state(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<MarginTraderState>,
+		who: AccountId,
+		pool_id: LiquidityPoolId,
+	) {
+		self.subscribe(subscriber, move |client, at| {
+			client.runtime_api().trader_state(at, who.clone(), pool_id).ok()
+		});
+	}
+
+	fn unsubscribe_trader_state(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+
+	fn subscribe_pool_state(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<Option<MarginPoolState>>,
+		pool_id: LiquidityPoolId,
+	) {
+		self.subscribe(subscriber, move |client, at| {
+			client.runtime_api().pool_state(at, pool_id).ok()
+		});
+	}
+
+	fn unsubscribe_pool_state(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		Ok(self.manager.cancel(id))
 	}
 }
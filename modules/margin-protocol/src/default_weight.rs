@@ -1,4 +1,4 @@
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! Weights partly estimated by hand. Regenerate with the benchmark CLI before release.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -19,57 +19,96 @@ impl crate::WeightInfo for () {
 	}
 	fn open_position() -> Weight {
 		(1_172_175_000 as Weight)
-			.saturating_add(DbWeight::get().reads(26 as Weight))
-			.saturating_add(DbWeight::get().writes(7 as Weight))
+			.saturating_add(DbWeight::get().reads(29 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
 	}
 	fn open_position_with_ten_in_pool() -> Weight {
 		(4_786_901_000 as Weight)
-			.saturating_add(DbWeight::get().reads(46 as Weight))
-			.saturating_add(DbWeight::get().writes(7 as Weight))
+			.saturating_add(DbWeight::get().reads(49 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
 	}
 	fn close_position() -> Weight {
 		(535_587_000 as Weight)
-			.saturating_add(DbWeight::get().reads(20 as Weight))
-			.saturating_add(DbWeight::get().writes(7 as Weight))
+			.saturating_add(DbWeight::get().reads(23 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
 	}
 	fn close_position_with_ten_in_pool() -> Weight {
 		(2_004_307_000 as Weight)
-			.saturating_add(DbWeight::get().reads(38 as Weight))
-			.saturating_add(DbWeight::get().writes(7 as Weight))
+			.saturating_add(DbWeight::get().reads(41 as Weight))
+			.saturating_add(DbWeight::get().writes(8 as Weight))
 	}
 	fn trader_margin_call() -> Weight {
 		(439_221_000 as Weight)
-			.saturating_add(DbWeight::get().reads(21 as Weight))
+			.saturating_add(DbWeight::get().reads(23 as Weight))
 			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
 	fn trader_become_safe() -> Weight {
 		(481_074_000 as Weight)
-			.saturating_add(DbWeight::get().reads(21 as Weight))
+			.saturating_add(DbWeight::get().reads(23 as Weight))
 			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
 	fn trader_stop_out() -> Weight {
 		(1_476_823_000 as Weight)
-			.saturating_add(DbWeight::get().reads(25 as Weight))
-			.saturating_add(DbWeight::get().writes(10 as Weight))
+			.saturating_add(DbWeight::get().reads(29 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
 	}
 	fn liquidity_pool_margin_call() -> Weight {
 		(532_767_000 as Weight)
-			.saturating_add(DbWeight::get().reads(19 as Weight))
+			.saturating_add(DbWeight::get().reads(21 as Weight))
 			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
 	fn liquidity_pool_become_safe() -> Weight {
 		(525_795_000 as Weight)
-			.saturating_add(DbWeight::get().reads(19 as Weight))
+			.saturating_add(DbWeight::get().reads(21 as Weight))
 			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
 	fn liquidity_pool_force_close() -> Weight {
 		(1_485_114_000 as Weight)
-			.saturating_add(DbWeight::get().reads(28 as Weight))
-			.saturating_add(DbWeight::get().writes(10 as Weight))
+			.saturating_add(DbWeight::get().reads(32 as Weight))
+			.saturating_add(DbWeight::get().writes(12 as Weight))
 	}
 	fn set_trading_pair_risk_threshold() -> Weight {
 		(73_093_000 as Weight)
 			.saturating_add(DbWeight::get().reads(5 as Weight))
 			.saturating_add(DbWeight::get().writes(3 as Weight))
 	}
+	fn transfer_margin() -> Weight {
+		(412_386_000 as Weight)
+			.saturating_add(DbWeight::get().reads(10 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_cross_margin(p: u32) -> Weight {
+		(38_712_000 as Weight)
+			.saturating_add((356_204_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().reads((14 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn deposit_collateral() -> Weight {
+		(182_417_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn withdraw_collateral() -> Weight {
+		(436_935_000 as Weight)
+			.saturating_add(DbWeight::get().reads(14 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn set_collateral_haircut() -> Weight {
+		(52_206_000 as Weight)
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn liquidity_pool_wind_down_close(p: u32) -> Weight {
+		(112_468_000 as Weight)
+			.saturating_add((1_200_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().reads((21 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(DbWeight::get().writes((11 as Weight).saturating_mul(p as Weight)))
+	}
+	fn buy_pool_collateral() -> Weight {
+		(283_517_000 as Weight)
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().writes(6 as Weight))
+	}
 }
@@ -3,6 +3,7 @@
 use codec::{Decode, Encode};
 use frame_support::{
 	debug, decl_error, decl_event, decl_module, decl_storage, ensure,
+	storage::{with_transaction, TransactionOutcome},
 	traits::{EnsureOrigin, Get},
 	weights::{DispatchClass, Weight},
 	IterableStorageDoubleMap, IterableStorageMap,
@@ -11,11 +12,11 @@ use frame_system::{
 	ensure_none, ensure_signed,
 	offchain::{SendTransactionTypes, SubmitTransaction},
 };
-use orml_traits::{BasicCurrency, PriceProvider};
+use orml_traits::{BasicCurrency, MultiCurrency, PriceProvider};
 use orml_utilities::with_transaction_result;
 use primitives::{
 	arithmetic::{fixed_i128_from_fixed_u128, fixed_i128_from_u128, fixed_i128_mul_signum, u128_from_fixed_i128},
-	Balance, CurrencyId, Leverage, LiquidityPoolId, Price, TradingPair,
+	Balance, CurrencyId, LiquidityPoolId, PositionLeverage, Price, TradingPair,
 };
 use sp_arithmetic::{
 	traits::{Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating},
@@ -43,6 +44,7 @@ use traits::{
 use serde::{Deserialize, Serialize};
 
 mod default_weight;
+mod migrations;
 mod mock;
 mod tests;
 
@@ -59,7 +61,14 @@ pub trait WeightInfo {
 	fn liquidity_pool_margin_call() -> Weight;
 	fn liquidity_pool_become_safe() -> Weight;
 	fn liquidity_pool_force_close() -> Weight;
+	fn liquidity_pool_wind_down_close(p: u32) -> Weight;
 	fn set_trading_pair_risk_threshold() -> Weight;
+	fn transfer_margin() -> Weight;
+	fn set_cross_margin(p: u32) -> Weight;
+	fn deposit_collateral() -> Weight;
+	fn withdraw_collateral() -> Weight;
+	fn set_collateral_haircut() -> Weight;
+	fn buy_pool_collateral() -> Weight;
 }
 
 const MODULE_ID: ModuleId = ModuleId(*b"lami/mgn");
@@ -71,6 +80,9 @@ pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 	/// The currency used for liquidity.
 	type LiquidityCurrency: BasicCurrency<Self::AccountId, Balance = Balance>;
 
+	/// The `MultiCurrency` implementation for collaterals.
+	type MultiCurrency: MultiCurrency<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
 	/// The `MarginProtocolLiquidityPools` implementation.
 	type LiquidityPools: MarginProtocolLiquidityPools<Self::AccountId>;
 
@@ -86,6 +98,9 @@ pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 	/// Maximum number of positions could be opened in a pool.
 	type GetPoolMaxOpenPositions: Get<usize>;
 
+	/// Maximum number of positions closed in one wind-down close call.
+	type GetWindDownMaxClosePositions: Get<u32>;
+
 	/// Required origin for updating protocol options.
 	type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
@@ -105,32 +120,32 @@ pub type PositionId = u64;
 #[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq)]
 pub struct Position<T: Config> {
 	/// Owner.
-	owner: T::AccountId,
+	pub owner: T::AccountId,
 
 	/// Liquidity pool ID where the position is opened in.
-	pool: LiquidityPoolId,
+	pub pool: LiquidityPoolId,
 
 	/// Trader pair.
-	pair: TradingPair,
+	pub pair: TradingPair,
 
 	/// Leverage.
-	leverage: Leverage,
+	pub leverage: PositionLeverage,
 
 	/// Leveraged held amount.
 	///
 	/// Positive value if long position, negative if short.
-	leveraged_held: FixedI128,
+	pub leveraged_held: FixedI128,
 
 	/// Leveraged debits amount.
 	///
 	/// Negative value if long position, positive if short.
-	leveraged_debits: FixedI128,
+	pub leveraged_debits: FixedI128,
 
 	/// Accumulated swap rate on open position.
-	open_accumulated_swap_rate: FixedI128,
+	pub open_accumulated_swap_rate: FixedI128,
 
 	/// Margin held.
-	margin_held: FixedI128,
+	pub margin_held: FixedI128,
 }
 
 /// Positions snapshot.
@@ -154,6 +169,68 @@ pub struct LeveragedAmounts {
 
 	/// Leveraged debits amount.
 	debits: FixedI128,
+
+	/// Sum of `abs(leveraged_debits) * open_accumulated_swap_rate` of positions.
+	swap_debits: FixedI128,
+}
+
+/// Storage version of the module.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum Releases {
+	V1_0_0,
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
+}
+
+/// Exposure of a liquidity pool in a trading pair.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq)]
+pub struct PoolPairExposure {
+	/// Trading pair.
+	pub pair: TradingPair,
+
+	/// Open positions count.
+	pub positions_count: PositionId,
+
+	/// Net position(USD value).
+	pub net_position: FixedI128,
+
+	/// Longest leg(USD value).
+	pub longest_leg: FixedI128,
+
+	/// Unrealized profit and loss of traders(USD value), the pool's unrealized profit and loss is the opposite.
+	pub unrealized_pl: FixedI128,
+
+	/// Accumulated swap of traders(USD value).
+	pub accumulated_swap_rate: FixedI128,
+}
+
+/// Trading statistics of a trader in a liquidity pool.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct TraderStats {
+	/// Cumulative realized profit and loss of closed positions, excluding swap(USD value).
+	pub realized_pl: FixedI128,
+
+	/// Cumulative swap received if positive, or paid if negative(USD value).
+	pub swap: FixedI128,
+
+	/// Cumulative leveraged amount of opened and closed positions(USD value).
+	pub volume: Balance,
+
+	/// Number of positions opened.
+	pub positions_opened: u32,
+
+	/// Number of positions closed, including stopped out ones.
+	pub positions_closed: u32,
+
+	/// Number of positions closed by stop out, of the trader by margin level or of the pool by
+	/// ENP/ELL. Positions closed after the pool's wind-down deadline are not counted, as they are
+	/// closed at oracle price regardless of risk.
+	pub positions_stopped_out: u32,
 }
 
 /// Risk threshold.
@@ -185,6 +262,25 @@ impl TradingPairRiskThreshold {
 	}
 }
 
+/// Result of a simulated trade.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct TradeSimulation {
+	/// Execution price of the trade.
+	pub execution_price: FixedI128,
+
+	/// Margin held by the opened position, or released by the closed one.
+	pub margin_held: FixedI128,
+
+	/// Trader's margin level after the trade.
+	pub margin_level: FixedI128,
+
+	/// Pool's ENP after the trade.
+	pub enp: FixedI128,
+
+	/// Pool's ELL after the trade.
+	pub ell: FixedI128,
+}
+
 decl_storage! {
 	trait Store for Module<T: Config> as MarginProtocol {
 		/// Next available position ID.
@@ -227,6 +323,30 @@ decl_storage! {
 		///
 		/// DEFAULT-NOTE: `trader`, `enp`, and `ell` are all `None` by default.
 		RiskThresholds get(fn risk_thresholds): map hasher(twox_64_concat) TradingPair => TradingPairRiskThreshold;
+
+		/// Cross margin traders.
+		///
+		/// Margin of a cross margin trader is shared across all pools: risk is checked over the whole account, and
+		/// realized loss in one pool could be covered by balances in other pools.
+		CrossMarginTraders get(fn cross_margin_traders): map hasher(twox_64_concat) T::AccountId => Option<()>;
+
+		/// Haircuts of currencies accepted as collateral.
+		///
+		/// A currency could be deposited as collateral only if its haircut is set. Collateral value is
+		/// `amount * price * (1 - haircut)`.
+		CollateralHaircuts get(fn collateral_haircuts): map hasher(twox_64_concat) CurrencyId => Option<Permill>;
+
+		/// Collaterals of a trader in a liquidity pool, by currency.
+		Collaterals get(fn collaterals): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) (LiquidityPoolId, CurrencyId) => Balance;
+
+		/// Collaterals seized from traders, held for a liquidity pool by currency.
+		PoolCollaterals get(fn pool_collaterals): double_map hasher(twox_64_concat) LiquidityPoolId, hasher(twox_64_concat) CurrencyId => Balance;
+
+		/// Trading statistics of a trader in a liquidity pool.
+		TraderStatistics get(fn trader_stats): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) LiquidityPoolId => TraderStats;
+
+		/// Storage version of the module, new networks start with the latest version.
+		StorageVersion get(fn storage_version) build(|_: &GenesisConfig| Releases::V2_0_0): Releases;
 	}
 
 	add_extra_genesis {
@@ -250,10 +370,10 @@ decl_event! {
 		Amount = Balance
 	{
 		/// Position opened: \[who, position_id, pool_id, pair, leverage, leveraged_amount, open_price\]
-		PositionOpened(AccountId, PositionId, LiquidityPoolId, TradingPair, Leverage, Amount, Price),
+		PositionOpened(AccountId, PositionId, LiquidityPoolId, TradingPair, PositionLeverage, Amount, Price),
 
-		/// Position closed: \[who, position_id, pool_id, close_price\]
-		PositionClosed(AccountId, PositionId, LiquidityPoolId, Price),
+		/// Position closed: \[who, position_id, pool_id, close_price, realized_pl, swap\]
+		PositionClosed(AccountId, PositionId, LiquidityPoolId, Price, FixedI128, FixedI128),
 
 		/// Deposited: \[who, pool_id, amount\]
 		Deposited(AccountId, LiquidityPoolId, Amount),
@@ -279,8 +399,35 @@ decl_event! {
 		/// Liquidity pool force closed: \[pool_id\]
 		LiquidityPoolForceClosed(LiquidityPoolId),
 
+		/// Remaining positions of a wound down liquidity pool closed: \[pool_id, closed_count\]
+		LiquidityPoolWoundDown(LiquidityPoolId, u32),
+
+		/// Failed to close a position of a wound down liquidity pool: \[pool_id, position_id, error\]
+		WindDownClosePositionFailed(LiquidityPoolId, PositionId, DispatchError),
+
 		/// Trading pair risk threshold set: \[pair, trader_risk_threshold, liquidity_pool_enp_threshold, liquidity_pool_ell_threshold\]
 		TradingPairRiskThresholdSet(TradingPair, Option<RiskThreshold>, Option<RiskThreshold>, Option<RiskThreshold>),
+
+		/// Collateral deposited: \[who, pool_id, currency_id, amount\]
+		CollateralDeposited(AccountId, LiquidityPoolId, CurrencyId, Amount),
+
+		/// Collateral withdrew: \[who, pool_id, currency_id, amount\]
+		CollateralWithdrew(AccountId, LiquidityPoolId, CurrencyId, Amount),
+
+		/// Collateral seized on trader stop out: \[who, pool_id, currency_id, amount, value\]
+		CollateralSeized(AccountId, LiquidityPoolId, CurrencyId, Amount, Amount),
+
+		/// Collateral seized by pool bought: \[who, pool_id, currency_id, amount, value\]
+		PoolCollateralBought(AccountId, LiquidityPoolId, CurrencyId, Amount, Amount),
+
+		/// Collateral haircut set: \[currency_id, haircut\]
+		CollateralHaircutSet(CurrencyId, Option<Permill>),
+
+		/// Margin transferred between pools: \[who, from_pool_id, to_pool_id, amount\]
+		MarginTransferred(AccountId, LiquidityPoolId, LiquidityPoolId, Amount),
+
+		/// Cross margin enabled or disabled: \[who, enabled\]
+		CrossMarginSet(AccountId, bool),
 	}
 }
 
@@ -357,6 +504,54 @@ decl_error! {
 
 		/// Risk threshold not set.
 		NoRiskThreshold,
+
+		/// Cannot transfer margin to the same pool.
+		TransferToSamePool,
+
+		/// Currency is not accepted as collateral.
+		CollateralNotAllowed,
+
+		/// Insufficient collateral.
+		InsufficientCollateral,
+
+		/// Insufficient balance in pool.
+		InsufficientBalance,
+
+		/// Margin in a pool is not enough on its own.
+		InsufficientIsolatedMargin,
+
+		/// Trader has more pools than the given pools count.
+		TooManyPools,
+
+		/// Liquidity pool not found.
+		PoolNotFound,
+
+		/// Insufficient collateral held by pool.
+		InsufficientPoolCollateral,
+
+		/// Liquidity pool is winding down.
+		PoolWindingDown,
+
+		/// Wind-down deadline of liquidity pool not reached yet.
+		NotReachedWindDownDeadline,
+
+		/// Not enough locked liquidity in pool for the leverage.
+		NotEnoughLockedLiquidity,
+
+		/// No position of the wound down liquidity pool could be closed.
+		NoPositionClosed,
+
+		/// Liquidity pool is not active.
+		PoolNotActive,
+
+		/// Leveraged amount is below minimum of the trading pair in pool.
+		BelowTradingPairMinLeveragedAmount,
+
+		/// Leveraged amount is above maximum of the leverage in pool.
+		AboveMaxLeveragedAmount,
+
+		/// Leverage must not be zero.
+		InvalidLeverage,
 	}
 }
 
@@ -367,6 +562,11 @@ impl<T: Config> From<OpenPositionError> for Error<T> {
 			OpenPositionError::TradingPairNotEnabled => Error::<T>::TradingPairNotEnabled,
 			OpenPositionError::TradingPairNotEnabledInPool => Error::<T>::TradingPairNotEnabledInPool,
 			OpenPositionError::BelowMinLeveragedAmount => Error::<T>::BelowMinLeveragedAmount,
+			OpenPositionError::PoolWindingDown => Error::<T>::PoolWindingDown,
+			OpenPositionError::NotEnoughLockedLiquidity => Error::<T>::NotEnoughLockedLiquidity,
+			OpenPositionError::BelowTradingPairMinLeveragedAmount => Error::<T>::BelowTradingPairMinLeveragedAmount,
+			OpenPositionError::AboveMaxLeveragedAmount => Error::<T>::AboveMaxLeveragedAmount,
+			OpenPositionError::PoolNotActive => Error::<T>::PoolNotActive,
 		}
 	}
 }
@@ -380,15 +580,26 @@ decl_module! {
 		const GetTreasuryAccountId: T::AccountId = T::GetTreasuryAccountId::get();
 		const GetTraderMaxOpenPositions: u32 = T::GetTraderMaxOpenPositions::get() as u32;
 		const GetPoolMaxOpenPositions: u32 = T::GetPoolMaxOpenPositions::get() as u32;
+		const GetWindDownMaxClosePositions: u32 = T::GetWindDownMaxClosePositions::get();
 		const UnsignedPriority: TransactionPriority = T::UnsignedPriority::get();
 
+		fn on_runtime_upgrade() -> Weight {
+			if Self::storage_version() == Releases::V1_0_0 {
+				migrations::migrate_to_v2::<T>();
+				StorageVersion::put(Releases::V2_0_0);
+				T::MaximumBlockWeight::get()
+			} else {
+				0
+			}
+		}
+
 		/// Open a position in `pool_id`.
 		#[weight = T::WeightInfo::open_position()]
 		pub fn open_position(
 			origin,
 			#[compact] pool_id: LiquidityPoolId,
 			pair: TradingPair,
-			leverage: Leverage,
+			leverage: PositionLeverage,
 			#[compact] leveraged_amount: Balance,
 			price: Price,
 		) {
@@ -530,6 +741,24 @@ decl_module! {
 			})?;
 		}
 
+		/// Close remaining positions of a wound down liquidity pool at oracle price.
+		///
+		/// May only be called from none origin. Would fail if the pool wind-down deadline not reached,
+		/// or no position could be closed. At most `GetWindDownMaxClosePositions` positions are closed
+		/// in one call.
+		#[weight = (
+			T::WeightInfo::liquidity_pool_wind_down_close(T::GetWindDownMaxClosePositions::get()),
+			DispatchClass::Operational,
+		)]
+		pub fn liquidity_pool_wind_down_close(origin, #[compact] pool: LiquidityPoolId) {
+			with_transaction_result(|| {
+				ensure_none(origin)?;
+				let closed = Self::do_liquidity_pool_wind_down_close(pool)?;
+				Self::deposit_event(RawEvent::LiquidityPoolWoundDown(pool, closed));
+				Ok(())
+			})?;
+		}
+
 		/// Set risk thresholds of a trading pair.
 		///
 		/// May only be called from `UpdateOrigin`.
@@ -562,6 +791,107 @@ decl_module! {
 			})?;
 		}
 
+		/// Transfer margin of caller from `from_pool_id` to `to_pool_id`.
+		///
+		/// Would fail if not enough free margin in `from_pool_id`, or the caller would be unsafe in it.
+		#[weight = T::WeightInfo::transfer_margin()]
+		pub fn transfer_margin(
+			origin,
+			#[compact] from_pool_id: LiquidityPoolId,
+			#[compact] to_pool_id: LiquidityPoolId,
+			#[compact] amount: Balance
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_transfer_margin(&who, from_pool_id, to_pool_id, amount)?;
+				Self::deposit_event(RawEvent::MarginTransferred(who, from_pool_id, to_pool_id, amount));
+				Ok(())
+			})?;
+		}
+
+		/// Enable or disable cross margin of caller.
+		///
+		/// `pools_count` is the number of pools the caller has balance, collaterals or positions in, only
+		/// checked on disabling.
+		///
+		/// Would fail on disabling if the caller has negative balance or free margin, or is not safe, in any
+		/// pool on its own.
+		#[weight = T::WeightInfo::set_cross_margin(if *enabled { 0 } else { *pools_count })]
+		pub fn set_cross_margin(origin, enabled: bool, #[compact] pools_count: u32) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_set_cross_margin(&who, enabled, pools_count)?;
+				Self::deposit_event(RawEvent::CrossMarginSet(who, enabled));
+				Ok(())
+			})?;
+		}
+
+		/// Deposit collateral of `currency_id` to caller's account.
+		#[weight = T::WeightInfo::deposit_collateral()]
+		pub fn deposit_collateral(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			currency_id: CurrencyId,
+			#[compact] amount: Balance
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_deposit_collateral(&who, pool_id, currency_id, amount)?;
+				Self::deposit_event(RawEvent::CollateralDeposited(who, pool_id, currency_id, amount));
+				Ok(())
+			})?;
+		}
+
+		/// Withdraw collateral of `currency_id` from caller's account.
+		///
+		/// Would fail if the collateral value is more than free margin.
+		#[weight = T::WeightInfo::withdraw_collateral()]
+		pub fn withdraw_collateral(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			currency_id: CurrencyId,
+			#[compact] amount: Balance
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				Self::do_withdraw_collateral(&who, pool_id, currency_id, amount)?;
+				Self::deposit_event(RawEvent::CollateralWithdrew(who, pool_id, currency_id, amount));
+				Ok(())
+			})?;
+		}
+
+		/// Set haircut of `currency_id` as collateral. `None` to stop accepting it.
+		///
+		/// May only be called from `UpdateOrigin`.
+		#[weight = T::WeightInfo::set_collateral_haircut()]
+		pub fn set_collateral_haircut(origin, currency_id: CurrencyId, haircut: Option<Permill>) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				ensure!(currency_id != CurrencyId::AUSD, Error::<T>::CollateralNotAllowed);
+
+				CollateralHaircuts::mutate(currency_id, |h| *h = haircut);
+				Self::deposit_event(RawEvent::CollateralHaircutSet(currency_id, haircut));
+
+				Ok(())
+			})?;
+		}
+
+		/// Buy collateral of `currency_id` seized by pool, paying its market value to the pool's liquidity.
+		#[weight = T::WeightInfo::buy_pool_collateral()]
+		pub fn buy_pool_collateral(
+			origin,
+			#[compact] pool_id: LiquidityPoolId,
+			currency_id: CurrencyId,
+			#[compact] amount: Balance
+		) {
+			with_transaction_result(|| {
+				let who = ensure_signed(origin)?;
+				let value = Self::do_buy_pool_collateral(&who, pool_id, currency_id, amount)?;
+				Self::deposit_event(RawEvent::PoolCollateralBought(who, pool_id, currency_id, amount, value));
+				Ok(())
+			})?;
+		}
+
 		fn offchain_worker(block_number: T::BlockNumber) {
 			if let Err(error) = Self::offchain_worker(block_number) {
 				match error {
@@ -608,10 +938,11 @@ impl<T: Config> Module<T> {
 		who: &T::AccountId,
 		pool_id: LiquidityPoolId,
 		pair: TradingPair,
-		leverage: Leverage,
+		leverage: PositionLeverage,
 		leveraged_amount: Balance,
 		price: Price,
 	) -> DispatchResult {
+		ensure!(leverage.value() > 0, Error::<T>::InvalidLeverage);
 		Self::ensure_can_open_more_position(who, pool_id, pair)?;
 		ensure!(
 			Self::margin_called_traders(who, pool_id).is_none(),
@@ -626,9 +957,9 @@ impl<T: Config> Module<T> {
 		let leveraged_held = fixed_i128_from_u128(leveraged_amount);
 		let debits_price = {
 			if leverage.is_long() {
-				Self::ask_price(pool_id, pair, Some(price))?
+				Self::ask_price(pool_id, pair, leveraged_amount, Some(price))?
 			} else {
-				Self::bid_price(pool_id, pair, Some(price))?
+				Self::bid_price(pool_id, pair, leveraged_amount, Some(price))?
 			}
 		};
 		let leveraged_debits = leveraged_held
@@ -661,13 +992,18 @@ impl<T: Config> Module<T> {
 			margin_held,
 		};
 
-		let free_margin = Self::free_margin(who, pool_id)?;
+		let free_margin = Self::account_free_margin(who, pool_id)?;
 		ensure!(free_margin >= margin_held, Error::<T>::InsufficientFreeMargin);
 		Self::ensure_trader_safe(who, pool_id, Action::OpenPosition(position.clone()))?;
 		Self::ensure_pool_safe(pool_id, Action::OpenPosition(position.clone()))?;
 
 		let id = Self::insert_position(who, pool_id, pair, position)?;
 
+		<TraderStatistics<T>>::mutate(who, pool_id, |stats| {
+			stats.volume = stats.volume.saturating_add(u128_from_fixed_i128(leveraged_held_in_usd));
+			stats.positions_opened = stats.positions_opened.saturating_add(1);
+		});
+
 		Self::deposit_event(RawEvent::PositionOpened(
 			who.clone(),
 			id,
@@ -693,7 +1029,7 @@ impl<T: Config> Module<T> {
 			.checked_add(&accumulated_swap_rate)
 			.ok_or(Error::<T>::NumOutOfBound)?;
 
-		if unrealized.is_positive() {
+		let realized = if unrealized.is_positive() {
 			// Realize trader's profit.
 
 			let pool_liquidity = fixed_i128_from_u128(<T::LiquidityPools as LiquidityPools<T::AccountId>>::liquidity(
@@ -719,10 +1055,11 @@ impl<T: Config> Module<T> {
 			}
 
 			Self::update_balance(who, position.pool, realizable);
+			realizable
 		} else {
 			// Realize trader's loss.
 
-			let equity = Self::equity_of_trader(who, position.pool)?;
+			let equity = Self::account_equity(who, position.pool)?;
 			let unrealized_abs = unrealized.saturating_abs();
 			// Max realizable is the trader's equity excluding this lossy position.
 			let realizable = cmp::min(
@@ -732,10 +1069,16 @@ impl<T: Config> Module<T> {
 
 			// If trader has not enough balance to pay the loss, pool won't get full payment for now. Repayment
 			// will happen on close profitable positions later.
-			let pool_deposit = cmp::min(
+			let mut pool_deposit = cmp::min(
 				cmp::max(Self::balances(who, position.pool), FixedI128::zero()),
 				realizable,
 			);
+			// Cross margin trader could cover the rest by balances in other pools.
+			if pool_deposit < realizable {
+				let drew =
+					Self::draw_margin_from_other_pools(who, position.pool, realizable.saturating_sub(pool_deposit));
+				pool_deposit = pool_deposit.saturating_add(drew);
+			}
 			if !pool_deposit.is_zero() {
 				<T::LiquidityPools as LiquidityPools<T::AccountId>>::deposit_liquidity(
 					&Self::account_id(),
@@ -744,17 +1087,34 @@ impl<T: Config> Module<T> {
 				)?;
 			}
 
-			Self::update_balance(who, position.pool, fixed_i128_mul_signum(realizable, -1));
-		}
+			let realized = fixed_i128_mul_signum(realizable, -1);
+			Self::update_balance(who, position.pool, realized);
+			realized
+		};
 
 		// Remove position storage operation.
 		Self::remove_position(who, position_id, &position)?;
 
+		// The swap is fully realized, any shortfall of the realized amount is counted in realized P&L.
+		let realized_pl = realized.saturating_sub(accumulated_swap_rate);
+		let close_volume = Self::usd_value(
+			position.pair.quote,
+			position.leveraged_held.saturating_abs().saturating_mul(market_price),
+		)?;
+		<TraderStatistics<T>>::mutate(who, position.pool, |stats| {
+			stats.realized_pl = stats.realized_pl.saturating_add(realized_pl);
+			stats.swap = stats.swap.saturating_add(accumulated_swap_rate);
+			stats.volume = stats.volume.saturating_add(u128_from_fixed_i128(close_volume));
+			stats.positions_closed = stats.positions_closed.saturating_add(1);
+		});
+
 		Self::deposit_event(RawEvent::PositionClosed(
 			who.clone(),
 			position_id,
 			position.pool,
 			Price::from_inner(u128_from_fixed_i128(market_price)),
+			realized_pl,
+			accumulated_swap_rate,
 		));
 
 		Ok(())
@@ -768,9 +1128,22 @@ impl<T: Config> Module<T> {
 	}
 
 	fn do_withdraw(who: &T::AccountId, pool_id: LiquidityPoolId, amount: Balance) -> DispatchResult {
-		let free_margin = Self::free_margin(who, pool_id)?;
+		// Collaterals could back positions, but not be withdrawn as liquidity currency.
+		let collateral_value = Self::margin_pools(who, pool_id)
+			.iter()
+			.try_fold(FixedI128::zero(), |acc, p| -> FixedI128Result {
+				Ok(acc.saturating_add(Self::collateral_value_of_trader(who, *p)?))
+			})?;
+		let free_margin = Self::account_free_margin(who, pool_id)?.saturating_sub(collateral_value);
 		let amount_fixedi128 = fixed_i128_from_u128(amount);
 		ensure!(free_margin >= amount_fixedi128, Error::<T>::InsufficientFreeMargin);
+		// Free margin could be shared with other pools, but only the pool's own margin is withdrawn from it.
+		let isolated_free_margin =
+			Self::free_margin(who, pool_id)?.saturating_sub(Self::collateral_value_of_trader(who, pool_id)?);
+		ensure!(
+			isolated_free_margin >= amount_fixedi128,
+			Error::<T>::InsufficientIsolatedMargin
+		);
 
 		T::LiquidityCurrency::transfer(&Self::account_id(), who, amount)?;
 		Self::update_balance(who, pool_id, fixed_i128_mul_signum(amount_fixedi128, -1));
@@ -778,6 +1151,153 @@ impl<T: Config> Module<T> {
 		Ok(())
 	}
 
+	fn do_transfer_margin(
+		who: &T::AccountId,
+		from_pool_id: LiquidityPoolId,
+		to_pool_id: LiquidityPoolId,
+		amount: Balance,
+	) -> DispatchResult {
+		ensure!(from_pool_id != to_pool_id, Error::<T>::TransferToSamePool);
+
+		let free_margin = Self::account_free_margin(who, from_pool_id)?;
+		let amount_fixedi128 = fixed_i128_from_u128(amount);
+		ensure!(free_margin >= amount_fixedi128, Error::<T>::InsufficientFreeMargin);
+		ensure!(
+			Self::free_margin(who, from_pool_id)? >= amount_fixedi128,
+			Error::<T>::InsufficientIsolatedMargin
+		);
+
+		Self::update_balance(who, from_pool_id, fixed_i128_mul_signum(amount_fixedi128, -1));
+		Self::update_balance(who, to_pool_id, amount_fixedi128);
+
+		Self::ensure_trader_safe(who, from_pool_id, Action::None)
+	}
+
+	fn do_deposit_collateral(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> DispatchResult {
+		ensure!(
+			<T::LiquidityPools as LiquidityPools<T::AccountId>>::pool_exists(pool_id),
+			Error::<T>::PoolNotFound
+		);
+		ensure!(
+			Self::collateral_haircuts(currency_id).is_some(),
+			Error::<T>::CollateralNotAllowed
+		);
+		// collateral must have a price to be valued
+		Self::price(currency_id, CurrencyId::AUSD)?;
+
+		T::MultiCurrency::transfer(currency_id, who, &Self::account_id(), amount)?;
+		<Collaterals<T>>::mutate(who, (pool_id, currency_id), |c| *c = c.saturating_add(amount));
+
+		Ok(())
+	}
+
+	fn do_withdraw_collateral(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> DispatchResult {
+		let collateral = Self::collaterals(who, (pool_id, currency_id));
+		ensure!(collateral >= amount, Error::<T>::InsufficientCollateral);
+
+		let value = Self::collateral_value(currency_id, amount)?;
+		let free_margin = Self::account_free_margin(who, pool_id)?;
+		ensure!(free_margin >= value, Error::<T>::InsufficientFreeMargin);
+		// Free margin could be shared with other pools, but the collateral only backs positions in its own pool.
+		ensure!(
+			Self::free_margin(who, pool_id)? >= value,
+			Error::<T>::InsufficientIsolatedMargin
+		);
+
+		T::MultiCurrency::transfer(currency_id, &Self::account_id(), who, amount)?;
+		Self::set_collateral(who, pool_id, currency_id, collateral - amount);
+
+		Ok(())
+	}
+
+	/// Seize collaterals of a trader in `pools`, the pool pays their value to the trader's balance and the
+	/// collaterals are held for the pool, until bought back by `buy_pool_collateral`.
+	///
+	/// Collateral with no value, or in a pool without enough liquidity, is not seized.
+	fn seize_collaterals(who: &T::AccountId, pools: &[LiquidityPoolId]) -> DispatchResult {
+		let collaterals: Vec<((LiquidityPoolId, CurrencyId), Balance)> = <Collaterals<T>>::iter_prefix(who)
+			.filter(|((p, _), _)| pools.contains(p))
+			.collect();
+
+		for ((pool_id, currency_id), amount) in collaterals {
+			let value = Self::collateral_value(currency_id, amount)?;
+			let value_u128 = u128_from_fixed_i128(value);
+			if value_u128 == 0 || <T::LiquidityPools as LiquidityPools<T::AccountId>>::liquidity(pool_id) < value_u128 {
+				continue;
+			}
+
+			<T::LiquidityPools as LiquidityPools<T::AccountId>>::withdraw_liquidity(
+				&Self::account_id(),
+				pool_id,
+				value_u128,
+			)?;
+			<PoolCollaterals>::mutate(pool_id, currency_id, |c| *c = c.saturating_add(amount));
+			Self::set_collateral(who, pool_id, currency_id, 0);
+			Self::update_balance(who, pool_id, value);
+
+			Self::deposit_event(RawEvent::CollateralSeized(
+				who.clone(),
+				pool_id,
+				currency_id,
+				amount,
+				value_u128,
+			));
+		}
+
+		Ok(())
+	}
+
+	fn do_buy_pool_collateral(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> result::Result<Balance, DispatchError> {
+		let collateral = Self::pool_collaterals(pool_id, currency_id);
+		ensure!(collateral >= amount, Error::<T>::InsufficientPoolCollateral);
+
+		let value = u128_from_fixed_i128(Self::usd_value(currency_id, fixed_i128_from_u128(amount))?);
+		<T::LiquidityPools as LiquidityPools<T::AccountId>>::deposit_liquidity(who, pool_id, value)?;
+		T::MultiCurrency::transfer(currency_id, &Self::account_id(), who, amount)?;
+		<PoolCollaterals>::insert(pool_id, currency_id, collateral - amount);
+
+		Ok(value)
+	}
+
+	fn do_set_cross_margin(who: &T::AccountId, enabled: bool, pools_count: u32) -> DispatchResult {
+		if enabled {
+			<CrossMarginTraders<T>>::insert(who, ());
+		} else if Self::is_cross_margin_trader(who) {
+			let pools = Self::pools_of_trader(who);
+			ensure!(pools.len() as u32 <= pools_count, Error::<T>::TooManyPools);
+
+			<CrossMarginTraders<T>>::remove(who);
+			// Margin is isolated again, each pool must be covered and safe on its own.
+			for pool_id in pools {
+				Self::ensure_trader_safe(who, pool_id, Action::None)?;
+				ensure!(
+					!Self::balances(who, pool_id).is_negative(),
+					Error::<T>::InsufficientBalance
+				);
+				ensure!(
+					!Self::free_margin(who, pool_id)?.is_negative(),
+					Error::<T>::InsufficientIsolatedMargin
+				);
+			}
+		}
+		Ok(())
+	}
+
 	fn do_trader_margin_call(who: &T::AccountId, pool_id: LiquidityPoolId) -> DispatchResult {
 		if !Self::is_trader_margin_called(who, pool_id) {
 			if Self::ensure_trader_safe(who, pool_id, Action::None).is_err() {
@@ -805,26 +1325,35 @@ impl<T: Config> Module<T> {
 		match risk {
 			Risk::StopOut => {
 				// To stop out a trader:
-				//   1. Close the position with the biggest loss.
-				//   2. Repeat step 1 until no stop out risk, or all positions of this trader has been closed.
-
-				let mut positions: Vec<(PositionId, FixedI128)> = <PositionsByTrader<T>>::iter_prefix(who)
-					.filter_map(|((_, position_id), _)| {
-						let position = Self::positions(position_id)?;
-						if position.pool != pool_id {
-							return None;
-						}
-
-						let unrealized_pl = Self::unrealized_pl_of_position(&position).ok()?;
-						let accumulated_swap_rate = Self::accumulated_swap_rate_of_position(&position).ok()?;
-						let unrealized = unrealized_pl.checked_add(&accumulated_swap_rate)?;
-						Some((position_id, unrealized))
-					})
-					.collect();
-				positions.sort_by(|x, y| x.1.cmp(&y.1));
-
-				for (id, _) in positions {
-					let _ = Self::do_close_position(who, id, None);
+				//   1. Seize all collaterals, the pool pays their value in liquidity currency.
+				//   2. Close the position with the biggest loss.
+				//   3. Repeat step 2 until no stop out risk, or all positions of this trader has been closed.
+
+				let pools = Self::margin_pools(who, pool_id);
+				Self::seize_collaterals(who, &pools)?;
+
+				let mut positions: Vec<(PositionId, LiquidityPoolId, FixedI128)> =
+					<PositionsByTrader<T>>::iter_prefix(who)
+						.filter_map(|((_, position_id), _)| {
+							let position = Self::positions(position_id)?;
+							if !pools.contains(&position.pool) {
+								return None;
+							}
+
+							let unrealized_pl = Self::unrealized_pl_of_position(&position).ok()?;
+							let accumulated_swap_rate = Self::accumulated_swap_rate_of_position(&position).ok()?;
+							let unrealized = unrealized_pl.checked_add(&accumulated_swap_rate)?;
+							Some((position_id, position.pool, unrealized))
+						})
+						.collect();
+				positions.sort_by(|x, y| x.2.cmp(&y.2));
+
+				for (id, position_pool, _) in positions {
+					if Self::do_close_position(who, id, None).is_ok() {
+						<TraderStatistics<T>>::mutate(who, position_pool, |stats| {
+							stats.positions_stopped_out = stats.positions_stopped_out.saturating_add(1);
+						});
+					}
 					let new_risk = Self::check_trader(who, pool_id, Action::None)?;
 					match new_risk {
 						Risk::StopOut => {}
@@ -832,10 +1361,12 @@ impl<T: Config> Module<T> {
 					}
 				}
 
-				if Self::ensure_trader_safe(who, pool_id, Action::None).is_ok()
-					&& Self::is_trader_margin_called(who, pool_id)
-				{
-					<MarginCalledTraders<T>>::remove(who, pool_id);
+				if Self::ensure_trader_safe(who, pool_id, Action::None).is_ok() {
+					for p in pools {
+						if Self::is_trader_margin_called(who, p) {
+							<MarginCalledTraders<T>>::remove(who, p);
+						}
+					}
 				}
 				Ok(())
 			}
@@ -880,6 +1411,41 @@ impl<T: Config> Module<T> {
 			_ => Err(Error::<T>::NotReachedRiskThreshold.into()),
 		}
 	}
+
+	/// Close at most `GetWindDownMaxClosePositions` positions of a wound down pool. Failed closes
+	/// are recorded in `WindDownClosePositionFailed` events.
+	///
+	/// Returns the number of closed positions, or the first error if none closed.
+	fn do_liquidity_pool_wind_down_close(pool: LiquidityPoolId) -> result::Result<u32, DispatchError> {
+		ensure!(
+			T::LiquidityPools::is_wind_down_expired(pool),
+			Error::<T>::NotReachedWindDownDeadline
+		);
+
+		let position_ids: Vec<PositionId> = PositionsByPool::iter_prefix(pool)
+			.map(|((_, position_id), _)| position_id)
+			.take(T::GetWindDownMaxClosePositions::get() as usize)
+			.collect();
+
+		// spreads are zero after the deadline, so positions are closed at oracle price
+		let mut closed: u32 = 0;
+		let mut first_error: Option<DispatchError> = None;
+		for position_id in position_ids {
+			let position = Self::positions(position_id).ok_or(Error::<T>::PositionNotFound)?;
+			match with_transaction_result(|| Self::do_close_position(&position.owner, position_id, None)) {
+				Ok(_) => closed += 1,
+				Err(e) => {
+					Self::deposit_event(RawEvent::WindDownClosePositionFailed(pool, position_id, e));
+					first_error.get_or_insert(e);
+				}
+			}
+		}
+
+		if closed == 0 {
+			return Err(first_error.unwrap_or_else(|| Error::<T>::NoPositionClosed.into()));
+		}
+		Ok(closed)
+	}
 }
 
 // Storage helpers
@@ -897,32 +1463,26 @@ impl<T: Config> Module<T> {
 		let id = Self::next_position_id();
 		ensure!(id != PositionId::max_value(), Error::<T>::NoAvailablePositionId);
 
+		let swap_debits = Self::swap_debits_of_position(&position)?;
 		PositionsSnapshots::try_mutate(pool_id, pair, |snapshot| -> DispatchResult {
-			if position.leverage.is_long() {
-				snapshot.positions_count += 1;
-				snapshot.long.held = snapshot
-					.long
-					.held
-					.checked_add(&position.leveraged_held)
-					.ok_or(Error::<T>::NumOutOfBound)?;
-				snapshot.long.debits = snapshot
-					.long
-					.debits
-					.checked_add(&position.leveraged_debits)
-					.ok_or(Error::<T>::NumOutOfBound)?;
+			snapshot.positions_count += 1;
+			let amounts = if position.leverage.is_long() {
+				&mut snapshot.long
 			} else {
-				snapshot.positions_count += 1;
-				snapshot.short.held = snapshot
-					.long
-					.held
-					.checked_add(&position.leveraged_held)
-					.ok_or(Error::<T>::NumOutOfBound)?;
-				snapshot.short.debits = snapshot
-					.long
-					.debits
-					.checked_add(&position.leveraged_debits)
-					.ok_or(Error::<T>::NumOutOfBound)?;
-			}
+				&mut snapshot.short
+			};
+			amounts.held = amounts
+				.held
+				.checked_add(&position.leveraged_held)
+				.ok_or(Error::<T>::NumOutOfBound)?;
+			amounts.debits = amounts
+				.debits
+				.checked_add(&position.leveraged_debits)
+				.ok_or(Error::<T>::NumOutOfBound)?;
+			amounts.swap_debits = amounts
+				.swap_debits
+				.checked_add(&swap_debits)
+				.ok_or(Error::<T>::NumOutOfBound)?;
 			Ok(())
 		})?;
 
@@ -940,36 +1500,31 @@ impl<T: Config> Module<T> {
 		position_id: PositionId,
 		position: &Position<T>,
 	) -> result::Result<(), DispatchError> {
+		let swap_debits = Self::swap_debits_of_position(position)?;
+
 		<Positions<T>>::remove(position_id);
 		<PositionsByTrader<T>>::remove(who, (position.pool, position_id));
 		PositionsByPool::remove(position.pool, (position.pair, position_id));
 
 		PositionsSnapshots::mutate(position.pool, position.pair, |snapshot| {
-			if position.leverage.is_long() {
-				snapshot.positions_count -= 1;
-				snapshot.long.held = snapshot
-					.long
-					.held
-					.checked_sub(&position.leveraged_held)
-					.expect("pool amount can't overflow; qed");
-				snapshot.long.debits = snapshot
-					.long
-					.debits
-					.checked_sub(&position.leveraged_debits)
-					.expect("pool amount can't overflow; qed");
+			snapshot.positions_count -= 1;
+			let amounts = if position.leverage.is_long() {
+				&mut snapshot.long
 			} else {
-				snapshot.positions_count -= 1;
-				snapshot.short.held = snapshot
-					.short
-					.held
-					.checked_sub(&position.leveraged_held)
-					.expect("pool amount can't overflow; qed");
-				snapshot.short.debits = snapshot
-					.short
-					.debits
-					.checked_sub(&position.leveraged_debits)
-					.expect("pool amount can't overflow; qed");
-			}
+				&mut snapshot.short
+			};
+			amounts.held = amounts
+				.held
+				.checked_sub(&position.leveraged_held)
+				.expect("pool amount can't overflow; qed");
+			amounts.debits = amounts
+				.debits
+				.checked_sub(&position.leveraged_debits)
+				.expect("pool amount can't overflow; qed");
+			amounts.swap_debits = amounts
+				.swap_debits
+				.checked_sub(&swap_debits)
+				.expect("pool amount can't overflow; qed");
 		});
 
 		// reset trader's equity to $0
@@ -979,6 +1534,8 @@ impl<T: Config> Module<T> {
 			<Balances<T>>::remove(who, position.pool);
 		}
 
+		T::LiquidityPools::settle_wind_down(position.pool);
+
 		Ok(())
 	}
 
@@ -990,6 +1547,67 @@ impl<T: Config> Module<T> {
 		<Balances<T>>::insert(who, pool_id, new_balance);
 	}
 
+	fn set_collateral(who: &T::AccountId, pool_id: LiquidityPoolId, currency_id: CurrencyId, amount: Balance) {
+		if amount == 0 {
+			<Collaterals<T>>::remove(who, (pool_id, currency_id));
+		} else {
+			<Collaterals<T>>::insert(who, (pool_id, currency_id), amount);
+		}
+	}
+
+	fn is_cross_margin_trader(who: &T::AccountId) -> bool {
+		<CrossMarginTraders<T>>::contains_key(who)
+	}
+
+	/// All pools `who` has balance, collaterals or positions in.
+	fn pools_of_trader(who: &T::AccountId) -> Vec<LiquidityPoolId> {
+		let mut pools: Vec<LiquidityPoolId> = <Balances<T>>::iter_prefix(who)
+			.map(|(pool_id, _)| pool_id)
+			.chain(<Collaterals<T>>::iter_prefix(who).map(|((pool_id, _), _)| pool_id))
+			.chain(<PositionsByTrader<T>>::iter_prefix(who).map(|((pool_id, _), _)| pool_id))
+			.collect();
+		#[allow(clippy::stable_sort_primitive)] // need stable sort to be deterministic
+		pools.sort();
+		pools.dedup(); // dedup works as unique for sorted vec, so we sort first
+		pools
+	}
+
+	/// Pools sharing margin with `pool_id` for `who`.
+	///
+	/// All pools of a cross margin trader, or only `pool_id` if not cross margin.
+	fn margin_pools(who: &T::AccountId, pool_id: LiquidityPoolId) -> Vec<LiquidityPoolId> {
+		if !Self::is_cross_margin_trader(who) {
+			return vec![pool_id];
+		}
+
+		let mut pools = Self::pools_of_trader(who);
+		if !pools.contains(&pool_id) {
+			pools.push(pool_id);
+		}
+		pools
+	}
+
+	/// Move up to `amount` of positive balances of `who` in other margin pools to `pool_id`.
+	///
+	/// Returns the amount moved. Always zero if `who` is not a cross margin trader.
+	fn draw_margin_from_other_pools(who: &T::AccountId, pool_id: LiquidityPoolId, amount: FixedI128) -> FixedI128 {
+		let mut remaining = amount;
+		for other in Self::margin_pools(who, pool_id) {
+			if other == pool_id || !remaining.is_positive() {
+				continue;
+			}
+
+			let balance = Self::balances(who, other);
+			if balance.is_positive() {
+				let drew = cmp::min(balance, remaining);
+				Self::update_balance(who, other, fixed_i128_mul_signum(drew, -1));
+				Self::update_balance(who, pool_id, drew);
+				remaining = remaining.saturating_sub(drew);
+			}
+		}
+		amount.saturating_sub(remaining)
+	}
+
 	fn ensure_can_open_more_position(who: &T::AccountId, pool: LiquidityPoolId, pair: TradingPair) -> DispatchResult {
 		ensure!(
 			(Self::pool_positions_snapshots(pool, pair).positions_count as usize) < T::GetPoolMaxOpenPositions::get(),
@@ -1013,14 +1631,22 @@ type DoubleFixedI128Result = result::Result<(FixedI128, FixedI128), DispatchErro
 // Price helpers
 impl<T: Config> Module<T> {
 	/// The price from oracle.
-	fn price(base: CurrencyId, quote: CurrencyId) -> PriceResult {
+	pub fn price(base: CurrencyId, quote: CurrencyId) -> PriceResult {
 		T::PriceProvider::get_price(base, quote).ok_or_else(|| Error::<T>::NoPrice.into())
 	}
 
 	/// ask_price = price + ask_spread
-	fn ask_price(pool: LiquidityPoolId, pair: TradingPair, max: Option<Price>) -> FixedI128Result {
+	///
+	/// The ask spread depends on the `leveraged_amount` of the trade.
+	pub fn ask_price(
+		pool: LiquidityPoolId,
+		pair: TradingPair,
+		leveraged_amount: Balance,
+		max: Option<Price>,
+	) -> FixedI128Result {
 		let price = Self::price(pair.base, pair.quote)?;
-		let spread = T::LiquidityPools::ask_spread(pool, pair).ok_or(Error::<T>::NoAskSpread)?;
+		let spread =
+			T::LiquidityPools::ask_spread(pool, pair, price, leveraged_amount).ok_or(Error::<T>::NoAskSpread)?;
 		let ask_price: Price = price.saturating_add(spread);
 
 		if let Some(m) = max {
@@ -1033,9 +1659,17 @@ impl<T: Config> Module<T> {
 	}
 
 	/// bid_price = price - bid_spread
-	fn bid_price(pool: LiquidityPoolId, pair: TradingPair, min: Option<Price>) -> FixedI128Result {
+	///
+	/// The bid spread depends on the `leveraged_amount` of the trade.
+	pub fn bid_price(
+		pool: LiquidityPoolId,
+		pair: TradingPair,
+		leveraged_amount: Balance,
+		min: Option<Price>,
+	) -> FixedI128Result {
 		let price = Self::price(pair.base, pair.quote)?;
-		let spread = T::LiquidityPools::bid_spread(pool, pair).ok_or(Error::<T>::NoBidSpread)?;
+		let spread =
+			T::LiquidityPools::bid_spread(pool, pair, price, leveraged_amount).ok_or(Error::<T>::NoBidSpread)?;
 		let bid_price = price.saturating_sub(spread);
 
 		if let Some(m) = min {
@@ -1064,28 +1698,40 @@ impl<T: Config> Module<T> {
 	/// Unrealized profit and loss of a position(USD value), based on current market price.
 	///
 	/// unrealized_pl_of_position = (curr_price - open_price) * leveraged_held * to_usd_price
-	fn unrealized_pl_of_position(position: &Position<T>) -> FixedI128Result {
+	pub fn unrealized_pl_of_position(position: &Position<T>) -> FixedI128Result {
 		let (unrealized, _) = Self::unrealized_pl_and_market_price_of_position(position, None)?;
 		Ok(unrealized)
 	}
 
+	/// Leveraged amount of a position.
+	pub fn leveraged_amount_of_position(position: &Position<T>) -> Balance {
+		u128_from_fixed_i128(position.leveraged_held.saturating_abs())
+	}
+
+	/// Open price of a position.
+	///
+	/// open_price = abs(leveraged_debits / leveraged_held)
+	pub fn open_price_of_position(position: &Position<T>) -> FixedI128 {
+		position
+			.leveraged_debits
+			.checked_div(&position.leveraged_held)
+			.expect("ensured safe on open position")
+			.saturating_abs()
+	}
+
 	/// Returns `Ok((unrealized_pl, market_price))` of a given position. If `price`, market price
 	/// must fit this bound, else returns `None`.
 	fn unrealized_pl_and_market_price_of_position(
 		position: &Position<T>,
 		price: Option<Price>,
 	) -> result::Result<(FixedI128, FixedI128), DispatchError> {
-		// open_price = abs(leveraged_debits / leveraged_held)
-		let open_price = position
-			.leveraged_debits
-			.checked_div(&position.leveraged_held)
-			.expect("ensured safe on open position")
-			.saturating_abs();
+		let open_price = Self::open_price_of_position(position);
+		let leveraged_amount = Self::leveraged_amount_of_position(position);
 		let curr_price = {
 			if position.leverage.is_long() {
-				Self::bid_price(position.pool, position.pair, price)?
+				Self::bid_price(position.pool, position.pair, leveraged_amount, price)?
 			} else {
-				Self::ask_price(position.pool, position.pair, price)?
+				Self::ask_price(position.pool, position.pair, leveraged_amount, price)?
 			}
 		};
 		let price_delta = curr_price
@@ -1102,41 +1748,55 @@ impl<T: Config> Module<T> {
 
 	/// unrealized_pl_of_pool = pool_per_pair_long_unrealized + pool_per_pair_short_unrealized
 	fn unrealized_pl_of_pool(pool_id: LiquidityPoolId) -> FixedI128Result {
-		PositionsSnapshots::iter_prefix(pool_id).try_fold(FixedI128::zero(), |unrealized, (pair, pool)| {
-			let long_unrealized = {
-				let curr_price = Self::bid_price(pool_id, pair, None)?;
-				let base_in_quote = pool
-					.long
-					.held
-					.checked_mul(&curr_price)
-					.ok_or(Error::<T>::NumOutOfBound)?;
-				let profit_in_quote = base_in_quote
-					.checked_add(&pool.long.debits)
-					.ok_or(Error::<T>::NumOutOfBound)?;
-				Self::usd_value(pair.quote, profit_in_quote)
-			}?;
-
-			let short_unrealized = {
-				let curr_price = Self::ask_price(pool_id, pair, None)?;
-				let base_in_quote = pool
-					.short
-					.held
-					.checked_mul(&curr_price)
-					.ok_or(Error::<T>::NumOutOfBound)?;
-				let profit_in_quote = base_in_quote
-					.checked_add(&pool.short.debits)
-					.ok_or(Error::<T>::NumOutOfBound)?;
-				Self::usd_value(pair.quote, profit_in_quote)
-			}?;
-
-			let sum = long_unrealized
-				.checked_add(&short_unrealized)
-				.ok_or(Error::<T>::NumOutOfBound)?;
-			let new_unrealized = unrealized.checked_add(&sum).ok_or(Error::<T>::NumOutOfBound)?;
-			Ok(new_unrealized)
+		PositionsSnapshots::iter_prefix(pool_id).try_fold(FixedI128::zero(), |unrealized, (pair, snapshot)| {
+			let sum = Self::unrealized_pl_of_snapshot(pool_id, pair, &snapshot)?;
+			unrealized
+				.checked_add(&sum)
+				.ok_or_else(|| Error::<T>::NumOutOfBound.into())
 		})
 	}
 
+	/// Unrealized profit and loss of all positions in a positions snapshot(USD value).
+	///
+	/// Priced at the spreads of zero leveraged amount trades, i.e. without spread tier add-ons, as
+	/// the size of each position is lost in the snapshot aggregation. Lower spreads give traders
+	/// higher unrealized profit, so pool equity, ENP and ELL are never overestimated.
+	fn unrealized_pl_of_snapshot(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		snapshot: &PositionsSnapshot,
+	) -> FixedI128Result {
+		let long_unrealized = {
+			let curr_price = Self::bid_price(pool_id, pair, 0, None)?;
+			let base_in_quote = snapshot
+				.long
+				.held
+				.checked_mul(&curr_price)
+				.ok_or(Error::<T>::NumOutOfBound)?;
+			let profit_in_quote = base_in_quote
+				.checked_add(&snapshot.long.debits)
+				.ok_or(Error::<T>::NumOutOfBound)?;
+			Self::usd_value(pair.quote, profit_in_quote)
+		}?;
+
+		let short_unrealized = {
+			let curr_price = Self::ask_price(pool_id, pair, 0, None)?;
+			let base_in_quote = snapshot
+				.short
+				.held
+				.checked_mul(&curr_price)
+				.ok_or(Error::<T>::NumOutOfBound)?;
+			let profit_in_quote = base_in_quote
+				.checked_add(&snapshot.short.debits)
+				.ok_or(Error::<T>::NumOutOfBound)?;
+			Self::usd_value(pair.quote, profit_in_quote)
+		}?;
+
+		long_unrealized
+			.checked_add(&short_unrealized)
+			.ok_or_else(|| Error::<T>::NumOutOfBound.into())
+	}
+
 	/// Unrealized profit and loss of a given trader in a pool(USD value). It is the sum of
 	/// unrealized profit and loss of all positions opened by a trader.
 	pub fn unrealized_pl_of_trader(who: &T::AccountId, pool_id: LiquidityPoolId) -> FixedI128Result {
@@ -1150,6 +1810,13 @@ impl<T: Config> Module<T> {
 			})
 	}
 
+	/// All open positions of a given trader.
+	pub fn positions_of_trader(who: &T::AccountId) -> Vec<(PositionId, Position<T>)> {
+		<PositionsByTrader<T>>::iter_prefix(who)
+			.filter_map(|((_, position_id), _)| Some((position_id, Self::positions(position_id)?)))
+			.collect()
+	}
+
 	/// Sum of all margin held of a given trader in a pool.
 	pub fn margin_held(who: &T::AccountId, pool_id: LiquidityPoolId) -> FixedI128 {
 		<PositionsByTrader<T>>::iter_prefix(who)
@@ -1165,7 +1832,7 @@ impl<T: Config> Module<T> {
 	///
 	/// accumulated_swap_rate_of_position =
 	///   (current_accumulated - open_accumulated) * leveraged_held
-	fn accumulated_swap_rate_of_position(position: &Position<T>) -> FixedI128Result {
+	pub fn accumulated_swap_rate_of_position(position: &Position<T>) -> FixedI128Result {
 		let rate = T::LiquidityPools::accumulated_swap_rate(position.pool, position.pair, position.leverage.is_long())
 			.checked_sub(&position.open_accumulated_swap_rate)
 			.ok_or(Error::<T>::NumOutOfBound)?;
@@ -1191,11 +1858,75 @@ impl<T: Config> Module<T> {
 			})
 	}
 
-	/// equity_of_trader = balance + unrealized_pl + accumulated_swap_rate
+	/// `abs(leveraged_debits) * open_accumulated_swap_rate` of a position, tracked in positions snapshots.
+	fn swap_debits_of_position(position: &Position<T>) -> FixedI128Result {
+		position
+			.leveraged_debits
+			.saturating_abs()
+			.checked_mul(&position.open_accumulated_swap_rate)
+			.ok_or_else(|| Error::<T>::NumOutOfBound.into())
+	}
+
+	/// Accumulated swap of all positions in a positions snapshot(USD value).
+	fn accumulated_swap_rate_of_snapshot(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		snapshot: &PositionsSnapshot,
+	) -> FixedI128Result {
+		let of_side = |amounts: &LeveragedAmounts, is_long: bool| -> FixedI128Result {
+			let rate = T::LiquidityPools::accumulated_swap_rate(pool_id, pair, is_long);
+			amounts
+				.debits
+				.saturating_abs()
+				.checked_mul(&rate)
+				.and_then(|v| v.checked_sub(&amounts.swap_debits))
+				.ok_or_else(|| Error::<T>::NumOutOfBound.into())
+		};
+		let accumulated_swap_rate = of_side(&snapshot.long, true)?
+			.checked_add(&of_side(&snapshot.short, false)?)
+			.ok_or(Error::<T>::NumOutOfBound)?;
+
+		Self::usd_value(pair.quote, accumulated_swap_rate)
+	}
+
+	/// Value of a collateral(USD value), after haircut.
+	///
+	/// collateral_value = amount * price * (1 - haircut)
+	fn collateral_value(currency_id: CurrencyId, amount: Balance) -> FixedI128Result {
+		let haircut = match Self::collateral_haircuts(currency_id) {
+			Some(h) => h,
+			None => return Ok(FixedI128::zero()),
+		};
+		// collateral with missing or stale price is valued at zero
+		let price = match T::PriceProvider::get_price(currency_id, CurrencyId::AUSD) {
+			Some(p) => fixed_i128_from_fixed_u128(p),
+			None => return Ok(FixedI128::zero()),
+		};
+		let value = fixed_i128_from_u128(amount)
+			.checked_mul(&price)
+			.ok_or(Error::<T>::NumOutOfBound)?;
+		value
+			.checked_mul(&Permill::one().saturating_sub(haircut).into())
+			.ok_or_else(|| Error::<T>::NumOutOfBound.into())
+	}
+
+	/// Sum of all collaterals value of a given trader in a pool.
+	pub fn collateral_value_of_trader(who: &T::AccountId, pool_id: LiquidityPoolId) -> FixedI128Result {
+		<Collaterals<T>>::iter_prefix(who)
+			.filter(|((p, _), _)| *p == pool_id)
+			.try_fold(FixedI128::zero(), |acc, ((_, currency_id), amount)| {
+				let value = Self::collateral_value(currency_id, amount)?;
+				acc.checked_add(&value).ok_or_else(|| Error::<T>::NumOutOfBound.into())
+			})
+	}
+
+	/// equity_of_trader = balance + collateral_value + unrealized_pl + accumulated_swap_rate
 	pub fn equity_of_trader(who: &T::AccountId, pool_id: LiquidityPoolId) -> FixedI128Result {
 		let unrealized = Self::unrealized_pl_of_trader(who, pool_id)?;
+		let collateral_value = Self::collateral_value_of_trader(who, pool_id)?;
 		let with_unrealized = Self::balances(who, pool_id)
-			.checked_add(&unrealized)
+			.checked_add(&collateral_value)
+			.and_then(|v| v.checked_add(&unrealized))
 			.ok_or(Error::<T>::NumOutOfBound)?;
 		let accumulated_swap_rate = Self::accumulated_swap_rate_of_trader(who, pool_id)?;
 		with_unrealized
@@ -1210,17 +1941,50 @@ impl<T: Config> Module<T> {
 		Ok(equity.saturating_sub(margin_held))
 	}
 
+	/// Equity of a given trader over all pools sharing margin with `pool_id`.
+	pub fn account_equity(who: &T::AccountId, pool_id: LiquidityPoolId) -> FixedI128Result {
+		Self::equity_of_trader_in_pools(who, &Self::margin_pools(who, pool_id))
+	}
+
+	/// Free margin of a given trader over all pools sharing margin with `pool_id`.
+	pub fn account_free_margin(who: &T::AccountId, pool_id: LiquidityPoolId) -> FixedI128Result {
+		let pools = Self::margin_pools(who, pool_id);
+		let equity = Self::equity_of_trader_in_pools(who, &pools)?;
+		let margin_held = pools.iter().fold(FixedI128::zero(), |acc, p| {
+			acc.saturating_add(Self::margin_held(who, *p))
+		});
+		Ok(equity.saturating_sub(margin_held))
+	}
+
+	/// Sum of equity of a given trader in `pools`.
+	fn equity_of_trader_in_pools(who: &T::AccountId, pools: &[LiquidityPoolId]) -> FixedI128Result {
+		pools.iter().try_fold(FixedI128::zero(), |acc, p| {
+			let equity = Self::equity_of_trader(who, *p)?;
+			acc.checked_add(&equity).ok_or_else(|| Error::<T>::NumOutOfBound.into())
+		})
+	}
+
 	/// Margin level of a given trader in a pool.
 	pub fn margin_level(who: &T::AccountId, pool_id: LiquidityPoolId) -> FixedI128Result {
-		let equity = Self::equity_of_trader(who, pool_id)?;
-		let leveraged_debits_in_usd = <PositionsByTrader<T>>::iter_prefix(who)
+		Self::margin_level_in_pools(who, &[pool_id])
+	}
+
+	/// Sum of leveraged debits(USD value) of a given trader in `pools`.
+	fn leveraged_debits_in_usd_of_trader(who: &T::AccountId, pools: &[LiquidityPoolId]) -> FixedI128Result {
+		<PositionsByTrader<T>>::iter_prefix(who)
 			.filter_map(|((_, position_id), _)| Self::positions(position_id))
-			.filter(|p| p.pool == pool_id)
-			.try_fold::<_, _, FixedI128Result>(FixedI128::zero(), |acc, p| {
+			.filter(|p| pools.contains(&p.pool))
+			.try_fold(FixedI128::zero(), |acc, p| {
 				let debits_in_usd = Self::usd_value(p.pair.quote, p.leveraged_debits.saturating_abs())?;
 				acc.checked_add(&debits_in_usd)
 					.ok_or_else(|| Error::<T>::NumOutOfBound.into())
-			})?;
+			})
+	}
+
+	/// Margin level of a given trader over `pools`.
+	fn margin_level_in_pools(who: &T::AccountId, pools: &[LiquidityPoolId]) -> FixedI128Result {
+		let equity = Self::equity_of_trader_in_pools(who, pools)?;
+		let leveraged_debits_in_usd = Self::leveraged_debits_in_usd_of_trader(who, pools)?;
 
 		Ok(equity
 			.checked_div(&leveraged_debits_in_usd)
@@ -1228,6 +1992,104 @@ impl<T: Config> Module<T> {
 			.unwrap_or_else(FixedI128::max_value))
 	}
 
+	/// Estimated close prices of a position, at which its owner would be margin called and stopped out.
+	///
+	/// Only the price of this position changes in estimation, other positions and the current equity are
+	/// taken as they are. Close price is bid price for long positions, and ask price for short ones.
+	///
+	/// estimated_price = curr_price + (threshold * leveraged_debits_in_usd - equity) / (leveraged_held * to_usd_price)
+	///
+	/// Return `Ok((margin_call_price, stop_out_price))`, a price is `None` if unreachable.
+	pub fn margin_call_and_stop_out_prices_of_position(
+		position: &Position<T>,
+	) -> result::Result<(Option<FixedI128>, Option<FixedI128>), DispatchError> {
+		let pools = Self::margin_pools(&position.owner, position.pool);
+		let equity = Self::equity_of_trader_in_pools(&position.owner, &pools)?;
+		let leveraged_debits_in_usd = Self::leveraged_debits_in_usd_of_trader(&position.owner, &pools)?;
+		let leveraged_held_in_usd = Self::usd_value(position.pair.quote, position.leveraged_held)?;
+		let (_, curr_price) = Self::unrealized_pl_and_market_price_of_position(position, None)?;
+		let threshold = Self::risk_threshold_of_trader_in_pools(&position.owner, &pools);
+
+		let estimate = |threshold: Permill| -> Option<FixedI128> {
+			let price = leveraged_debits_in_usd
+				.checked_mul(&threshold.into())?
+				.checked_sub(&equity)?
+				.checked_div(&leveraged_held_in_usd)?
+				.checked_add(&curr_price)?;
+			if price.is_positive() {
+				Some(price)
+			} else {
+				None
+			}
+		};
+
+		Ok((estimate(threshold.margin_call), estimate(threshold.stop_out)))
+	}
+
+	/// Simulate `open_position` of a trader. All storage changes are reverted.
+	///
+	/// Return `Err` the real call would hit if it fails.
+	pub fn simulate_open_position(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		leverage: PositionLeverage,
+		leveraged_amount: Balance,
+		price: Price,
+	) -> result::Result<TradeSimulation, DispatchError> {
+		with_transaction(|| {
+			let simulate = || -> result::Result<TradeSimulation, DispatchError> {
+				let id = Self::next_position_id();
+				Self::do_open_position(who, pool_id, pair, leverage, leveraged_amount, price)?;
+				let position = Self::positions(id).ok_or(Error::<T>::PositionNotFound)?;
+				Self::trade_simulation(
+					who,
+					pool_id,
+					Self::open_price_of_position(&position),
+					position.margin_held,
+				)
+			};
+			TransactionOutcome::Rollback(simulate())
+		})
+	}
+
+	/// Simulate `close_position` of a trader. All storage changes are reverted.
+	///
+	/// Return `Err` the real call would hit if it fails.
+	pub fn simulate_close_position(
+		who: &T::AccountId,
+		position_id: PositionId,
+		price: Price,
+	) -> result::Result<TradeSimulation, DispatchError> {
+		with_transaction(|| {
+			let simulate = || -> result::Result<TradeSimulation, DispatchError> {
+				let position = Self::positions(position_id);
+				Self::do_close_position(who, position_id, Some(price))?;
+				let position = position.expect("position closed; qed");
+				let (_, market_price) = Self::unrealized_pl_and_market_price_of_position(&position, Some(price))?;
+				Self::trade_simulation(who, position.pool, market_price, position.margin_held)
+			};
+			TransactionOutcome::Rollback(simulate())
+		})
+	}
+
+	fn trade_simulation(
+		who: &T::AccountId,
+		pool_id: LiquidityPoolId,
+		execution_price: FixedI128,
+		margin_held: FixedI128,
+	) -> result::Result<TradeSimulation, DispatchError> {
+		let margin_level = Self::margin_level_in_pools(who, &Self::margin_pools(who, pool_id))?;
+		let (enp, ell) = Self::enp_and_ell_with_action(pool_id, Action::None)?;
+		Ok(TradeSimulation {
+			execution_price,
+			margin_held,
+			margin_level,
+			enp,
+			ell,
+		})
+	}
+
 	/// Ensure a trader is safe.
 	///
 	/// Return `Ok` if ensured safe, or `Err` if not.
@@ -1241,16 +2103,19 @@ impl<T: Config> Module<T> {
 
 	/// Check trader risk after performing an action.
 	///
+	/// Risk of a cross margin trader is checked over all pools.
+	///
 	/// Return `Ok(Risk)`, or `Err` if check fails.
 	fn check_trader(who: &T::AccountId, pool_id: LiquidityPoolId, action: Action<T>) -> Result<Risk, DispatchError> {
-		let margin_level = Self::margin_level(who, pool_id)?;
+		let pools = Self::margin_pools(who, pool_id);
+		let margin_level = Self::margin_level_in_pools(who, &pools)?;
 
 		let new_pair_risk_threshold = match action {
 			Action::OpenPosition(p) => Self::trader_risk_threshold(p.pair).unwrap_or_default(),
 			_ => RiskThreshold::default(),
 		};
 
-		let trader_threshold = Self::risk_threshold_of_trader(who, pool_id);
+		let trader_threshold = Self::risk_threshold_of_trader_in_pools(who, &pools);
 		let risk = if margin_level <= cmp::max(trader_threshold.stop_out, new_pair_risk_threshold.stop_out).into() {
 			Risk::StopOut
 		} else if margin_level <= cmp::max(trader_threshold.margin_call, new_pair_risk_threshold.margin_call).into() {
@@ -1299,50 +2164,69 @@ impl<T: Config> Module<T> {
 		PositionsSnapshots::iter_prefix(pool)
 			.map(|(pair, pool)| (pair, pool))
 			.chain(new_position.map_or(vec![], |p| {
+				let amounts = LeveragedAmounts {
+					held: p.leveraged_held,
+					debits: p.leveraged_debits,
+					swap_debits: FixedI128::zero(),
+				};
 				let info = if p.leverage.is_long() {
 					PositionsSnapshot {
 						positions_count: 1,
-						long: LeveragedAmounts {
-							held: p.leveraged_held,
-							debits: p.leveraged_debits,
-						},
-						short: LeveragedAmounts {
-							held: FixedI128::zero(),
-							debits: FixedI128::zero(),
-						},
+						long: amounts,
+						short: Default::default(),
 					}
 				} else {
 					PositionsSnapshot {
 						positions_count: 1,
-						long: LeveragedAmounts {
-							held: FixedI128::zero(),
-							debits: FixedI128::zero(),
-						},
-						short: LeveragedAmounts {
-							held: p.leveraged_held,
-							debits: p.leveraged_debits,
-						},
+						long: Default::default(),
+						short: amounts,
 					}
 				};
 				vec![(p.pair, info)]
 			}))
 			.try_fold((FixedI128::zero(), FixedI128::zero()), |(net, max), (pair, pool)| {
-				let new_net = pool
-					.long
-					.held
-					.checked_add(&pool.short.held)
-					.ok_or(Error::<T>::NumOutOfBound)?;
-				let net_in_usd = Self::usd_value(pair.base, new_net.saturating_abs())?;
-
-				let new_max = cmp::max(pool.long.held, pool.short.held.saturating_abs());
-				let max_in_usd = Self::usd_value(pair.base, new_max.saturating_abs())?;
-
+				let (net_in_usd, max_in_usd) = Self::net_position_and_longest_leg_of_snapshot(pair, &pool)?;
 				let new_net = net.checked_add(&net_in_usd).ok_or(Error::<T>::NumOutOfBound)?;
 				let new_max = max.checked_add(&max_in_usd).ok_or(Error::<T>::NumOutOfBound)?;
 				Ok((new_net, new_max))
 			})
 	}
 
+	/// Returns `(net_position, longest_leg)` of a positions snapshot(USD value).
+	fn net_position_and_longest_leg_of_snapshot(
+		pair: TradingPair,
+		snapshot: &PositionsSnapshot,
+	) -> DoubleFixedI128Result {
+		let net = snapshot
+			.long
+			.held
+			.checked_add(&snapshot.short.held)
+			.ok_or(Error::<T>::NumOutOfBound)?;
+		let net_in_usd = Self::usd_value(pair.base, net.saturating_abs())?;
+
+		let max = cmp::max(snapshot.long.held, snapshot.short.held.saturating_abs());
+		let max_in_usd = Self::usd_value(pair.base, max.saturating_abs())?;
+
+		Ok((net_in_usd, max_in_usd))
+	}
+
+	/// Exposure of a liquidity pool per trading pair, from positions snapshots.
+	pub fn pool_exposure(pool_id: LiquidityPoolId) -> result::Result<Vec<PoolPairExposure>, DispatchError> {
+		PositionsSnapshots::iter_prefix(pool_id)
+			.map(|(pair, snapshot)| {
+				let (net_position, longest_leg) = Self::net_position_and_longest_leg_of_snapshot(pair, &snapshot)?;
+				Ok(PoolPairExposure {
+					pair,
+					positions_count: snapshot.positions_count,
+					net_position,
+					longest_leg,
+					unrealized_pl: Self::unrealized_pl_of_snapshot(pool_id, pair, &snapshot)?,
+					accumulated_swap_rate: Self::accumulated_swap_rate_of_snapshot(pool_id, pair, &snapshot)?,
+				})
+			})
+			.collect()
+	}
+
 	/// ENP and ELL after performing action.
 	///
 	/// ENP - Equity to Net Position ratio of a liquidity pool.
@@ -1419,13 +2303,15 @@ impl<T: Config> Module<T> {
 	fn liquidity_pool_close_position(pool: LiquidityPoolId, position_id: PositionId) -> DispatchResult {
 		let position = Self::positions(position_id).ok_or(Error::<T>::PositionNotFound)?;
 
+		let price = Self::price(position.pair.base, position.pair.quote)?;
+		let leveraged_amount = Self::leveraged_amount_of_position(&position);
 		let spread = {
 			if position.leverage.is_long() {
-				T::LiquidityPools::bid_spread(pool, position.pair)
+				T::LiquidityPools::bid_spread(pool, position.pair, price, leveraged_amount)
 					.ok_or(Error::<T>::NoBidSpread)
 					.map(fixed_i128_from_fixed_u128)?
 			} else {
-				T::LiquidityPools::ask_spread(pool, position.pair)
+				T::LiquidityPools::ask_spread(pool, position.pair, price, leveraged_amount)
 					.ok_or(Error::<T>::NoAskSpread)
 					.map(fixed_i128_from_fixed_u128)?
 			}
@@ -1443,6 +2329,9 @@ impl<T: Config> Module<T> {
 			.ok_or(Error::<T>::NumOutOfBound)?;
 
 		Self::do_close_position(&position.owner, position_id, None)?;
+		<TraderStatistics<T>>::mutate(&position.owner, pool, |stats| {
+			stats.positions_stopped_out = stats.positions_stopped_out.saturating_add(1);
+		});
 
 		let realized = cmp::min(
 			<T::LiquidityPools as LiquidityPools<T::AccountId>>::liquidity(position.pool),
@@ -1457,6 +2346,17 @@ impl<T: Config> Module<T> {
 		Ok(())
 	}
 
+	/// Return the strictest risk threshold of trader over `pools`.
+	fn risk_threshold_of_trader_in_pools(who: &T::AccountId, pools: &[LiquidityPoolId]) -> RiskThreshold {
+		pools
+			.iter()
+			.map(|p| Self::risk_threshold_of_trader(who, *p))
+			.fold(RiskThreshold::default(), |max, t| RiskThreshold {
+				margin_call: cmp::max(max.margin_call, t.margin_call),
+				stop_out: cmp::max(max.stop_out, t.stop_out),
+			})
+	}
+
 	/// Return risk threshold of trader based on opened positions after performing an action.
 	///
 	/// Return `RiskThreshold` or `Default` value.
@@ -1631,6 +2531,9 @@ impl<T: Config> Module<T> {
 		#[allow(clippy::stable_sort_primitive)] // need stable sort to be deterministic
 		pools.sort();
 		pools.dedup(); // dedup works as unique for sorted vec, so we sort first
+
+		// skip inactive pools
+		pools.retain(|pool_id| T::LiquidityPools::status(*pool_id).map_or(false, |status| status.is_live()));
 		pools
 	}
 
@@ -1696,6 +2599,20 @@ impl<T: Config> Module<T> {
 		}
 
 		for pool_id in Self::get_pools() {
+			if T::LiquidityPools::is_wind_down_expired(pool_id) && !Self::can_remove(pool_id) {
+				let call = Call::<T>::liquidity_pool_wind_down_close(pool_id);
+				SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+					.map_err(|_| OffchainErr::SubmitTransaction)?;
+				debug::native::trace!(
+					target: TAG,
+					"Liquidity pool wind down close [pool_id = {:?}, block_number = {:?}]",
+					pool_id,
+					block_number
+				);
+				guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
+				continue;
+			}
+
 			match Self::check_pool(pool_id, Action::None).map_err(|_| OffchainErr::CheckFail)? {
 				Risk::StopOut => {
 					let call = Call::<T>::liquidity_pool_force_close(pool_id);
@@ -1849,6 +2766,22 @@ impl<T: Config> frame_support::unsigned::ValidateUnsigned for Module<T> {
 
 				InvalidTransaction::Stale.into()
 			}
+			Call::liquidity_pool_wind_down_close(pool_id) => {
+				// dry run, accept only if some position could be closed
+				let can_close = with_transaction(|| {
+					TransactionOutcome::Rollback(Self::do_liquidity_pool_wind_down_close(*pool_id).is_ok())
+				});
+				if can_close {
+					return ValidTransaction::with_tag_prefix("margin_protocol/liquidity_pool_wind_down_close")
+						.priority(T::UnsignedPriority::get())
+						.and_provides(pool_id)
+						.longevity(64_u64)
+						.propagate(true)
+						.build();
+				}
+
+				InvalidTransaction::Stale.into()
+			}
 			_ => InvalidTransaction::Call.into(),
 		}
 	}
@@ -0,0 +1,51 @@
+//! Storage migrations.
+
+use super::*;
+use sp_std::collections::btree_map::BTreeMap;
+
+/// `LeveragedAmounts` in `V1_0_0`.
+#[derive(Decode)]
+#[allow(dead_code)]
+pub struct OldLeveragedAmounts {
+	pub held: FixedI128,
+	pub debits: FixedI128,
+}
+
+/// `PositionsSnapshot` in `V1_0_0`.
+#[derive(Decode)]
+#[allow(dead_code)]
+pub struct OldPositionsSnapshot {
+	pub positions_count: PositionId,
+	pub long: OldLeveragedAmounts,
+	pub short: OldLeveragedAmounts,
+}
+
+/// Migrate storage from `V1_0_0` to `V2_0_0`.
+///
+/// `LeveragedAmounts` gained `swap_debits`, so positions snapshots are recomputed from open positions.
+pub fn migrate_to_v2<T: Config>() {
+	let mut snapshots: BTreeMap<(LiquidityPoolId, TradingPair), PositionsSnapshot> = BTreeMap::new();
+	for (pool_id, (pair, position_id), _) in PositionsByPool::iter() {
+		let position = match <Module<T>>::positions(position_id) {
+			Some(p) => p,
+			None => continue,
+		};
+		let swap_debits = <Module<T>>::swap_debits_of_position(&position).unwrap_or_default();
+		let snapshot = snapshots.entry((pool_id, pair)).or_default();
+		snapshot.positions_count += 1;
+		let amounts = if position.leverage.is_long() {
+			&mut snapshot.long
+		} else {
+			&mut snapshot.short
+		};
+		amounts.held = amounts.held.saturating_add(position.leveraged_held);
+		amounts.debits = amounts.debits.saturating_add(position.leveraged_debits);
+		amounts.swap_debits = amounts.swap_debits.saturating_add(swap_debits);
+	}
+
+	// snapshots without open positions are removed
+	PositionsSnapshots::translate(|pool_id, pair, _: OldPositionsSnapshot| snapshots.get(&(pool_id, pair)).cloned());
+	for ((pool_id, pair), snapshot) in snapshots {
+		PositionsSnapshots::insert(pool_id, pair, snapshot);
+	}
+}
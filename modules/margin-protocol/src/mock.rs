@@ -5,7 +5,7 @@
 use frame_support::{impl_outer_dispatch, impl_outer_event, impl_outer_origin, ord_parameter_types, parameter_types};
 use frame_system::EnsureSignedBy;
 use orml_traits::{parameter_type_with_key, DataProvider, DefaultPriceProvider};
-use primitives::{Balance, CurrencyId, LiquidityPoolId, TradingPair};
+use primitives::{Balance, CurrencyId, LiquidityPoolId, PoolRole, PoolStatus, TradingPair};
 use sp_core::H256;
 use sp_runtime::{
 	testing::{Header, TestXt},
@@ -123,6 +123,7 @@ impl pallet_balances::Config for Runtime {
 
 pub type NativeCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, pallet_balances::Module<Runtime>, Amount, u64>;
 pub type LiquidityCurrency = orml_currencies::Currency<Runtime, GetLiquidityCurrencyId>;
+pub type Currencies = orml_currencies::Module<Runtime>;
 impl orml_currencies::Config for Runtime {
 	type Event = TestEvent;
 	type MultiCurrency = orml_tokens::Module<Runtime>;
@@ -160,6 +161,7 @@ thread_local! {
 	static SPREAD: RefCell<Price> = RefCell::new(Price::zero());
 	static ACC_SWAP_RATES: RefCell<BTreeMap<TradingPair, FixedI128>> = RefCell::new(BTreeMap::new());
 	static LIQUIDITIES: RefCell<BTreeMap<LiquidityPoolId, Balance>> = RefCell::new(BTreeMap::new());
+	static WIND_DOWN_EXPIRED: RefCell<bool> = RefCell::new(false);
 }
 
 pub const MOCK_LIQUIDITY_LOCK_ACCOUNT: u64 = 1000;
@@ -189,16 +191,42 @@ impl MockLiquidityPools {
 	pub fn set_mock_liquidity(pool: LiquidityPoolId, liquidity: Balance) {
 		LIQUIDITIES.with(|v| v.borrow_mut().insert(pool, liquidity));
 	}
+
+	pub fn wind_down_expired() -> bool {
+		WIND_DOWN_EXPIRED.with(|v| *v.borrow_mut())
+	}
+
+	pub fn set_mock_wind_down_expired(expired: bool) {
+		WIND_DOWN_EXPIRED.with(|v| *v.borrow_mut() = expired);
+	}
 }
 impl LiquidityPools<AccountId> for MockLiquidityPools {
 	fn all() -> Vec<LiquidityPoolId> {
 		unimplemented!()
 	}
 
+	fn pools_with_status(_status: PoolStatus) -> Vec<LiquidityPoolId> {
+		unimplemented!()
+	}
+
+	fn status(_pool_id: LiquidityPoolId) -> Option<PoolStatus> {
+		Some(PoolStatus::Active)
+	}
+
+	fn settle_wind_down(_pool_id: LiquidityPoolId) {}
+
 	fn is_owner(_pool_id: LiquidityPoolId, _who: &AccountId) -> bool {
 		unimplemented!()
 	}
 
+	fn has_role(_pool_id: LiquidityPoolId, _who: &AccountId, _role: PoolRole) -> bool {
+		unimplemented!()
+	}
+
+	fn owner(_pool_id: LiquidityPoolId) -> Option<AccountId> {
+		Some(MOCK_POOL_OWNER)
+	}
+
 	fn pool_exists(pool_id: LiquidityPoolId) -> bool {
 		pool_id == MOCK_POOL
 	}
@@ -207,6 +235,10 @@ impl LiquidityPools<AccountId> for MockLiquidityPools {
 		Self::liquidity(pool_id)
 	}
 
+	fn locked_liquidity(_pool_id: LiquidityPoolId) -> Balance {
+		unimplemented!()
+	}
+
 	fn deposit_liquidity(source: &u64, pool_id: LiquidityPoolId, amount: Balance) -> DispatchResult {
 		LiquidityCurrency::transfer(source, &MOCK_LIQUIDITY_LOCK_ACCOUNT, amount)?;
 		Self::set_mock_liquidity(pool_id, amount + Self::liquidity(pool_id));
@@ -221,14 +253,24 @@ impl LiquidityPools<AccountId> for MockLiquidityPools {
 }
 
 impl MarginProtocolLiquidityPools<AccountId> for MockLiquidityPools {
-	fn bid_spread(_pool_id: LiquidityPoolId, pair: TradingPair) -> Option<Price> {
+	fn bid_spread(
+		_pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		_price: Price,
+		_leveraged_amount: Balance,
+	) -> Option<Price> {
 		let base_price = MockPrices::prices(pair.base)?;
 		let quote_price = MockPrices::prices(pair.quote)?;
 		let price = base_price.checked_div(&quote_price).unwrap();
 		Some(Self::spread().saturating_mul(price))
 	}
 
-	fn ask_spread(_pool_id: LiquidityPoolId, pair: TradingPair) -> Option<Price> {
+	fn ask_spread(
+		_pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		_price: Price,
+		_leveraged_amount: Balance,
+	) -> Option<Price> {
 		let base_price = MockPrices::prices(pair.base)?;
 		let quote_price = MockPrices::prices(pair.quote)?;
 		let price = base_price.checked_div(&quote_price).unwrap();
@@ -246,11 +288,15 @@ impl MarginProtocolLiquidityPools<AccountId> for MockLiquidityPools {
 	fn ensure_can_open_position(
 		_pool_id: LiquidityPoolId,
 		_pair: TradingPair,
-		_leverage: Leverage,
+		_leverage: PositionLeverage,
 		_leveraged_amount: Balance,
 	) -> result::Result<(), OpenPositionError> {
 		Ok(())
 	}
+
+	fn is_wind_down_expired(_pool_id: LiquidityPoolId) -> bool {
+		Self::wind_down_expired()
+	}
 }
 
 pub type Extrinsic = TestXt<Call, ()>;
@@ -265,6 +311,7 @@ where
 parameter_types! {
 	pub const GetTraderMaxOpenPositions: usize = 200;
 	pub const GetPoolMaxOpenPositions: usize = 1000;
+	pub const GetWindDownMaxClosePositions: u32 = 2;
 	pub const GetTreasuryAccountId: AccountId = TREASURY_ACCOUNT;
 	pub const UnsignedPriority: u64 = 1 << 20;
 }
@@ -272,11 +319,13 @@ parameter_types! {
 impl Config for Runtime {
 	type Event = TestEvent;
 	type LiquidityCurrency = LiquidityCurrency;
+	type MultiCurrency = Currencies;
 	type LiquidityPools = MockLiquidityPools;
 	type PriceProvider = DefaultPriceProvider<CurrencyId, MockPrices>;
 	type GetTreasuryAccountId = GetTreasuryAccountId;
 	type GetTraderMaxOpenPositions = GetTraderMaxOpenPositions;
 	type GetPoolMaxOpenPositions = GetPoolMaxOpenPositions;
+	type GetWindDownMaxClosePositions = GetWindDownMaxClosePositions;
 	type UpdateOrigin = EnsureSignedBy<UpdateOrigin, AccountId>;
 	type UnsignedPriority = UnsignedPriority;
 	type WeightInfo = ();
@@ -286,6 +335,7 @@ pub type MarginProtocol = Module<Runtime>;
 pub const ALICE: AccountId = 0;
 pub const BOB: AccountId = 1;
 pub const TREASURY_ACCOUNT: AccountId = 3;
+pub const MOCK_POOL_OWNER: AccountId = 4;
 pub const MOCK_POOL: LiquidityPoolId = 100;
 pub const MOCK_POOL_1: LiquidityPoolId = 101;
 
@@ -362,6 +412,11 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn alice_currency_balance(mut self, currency_id: CurrencyId, balance: Balance) -> Self {
+		self.endowed_accounts.push((ALICE, currency_id, balance));
+		self
+	}
+
 	pub fn module_balance(mut self, balance: FixedI128) -> Self {
 		self.endowed_accounts.push((
 			MarginProtocol::account_id(),
@@ -5,12 +5,13 @@
 use super::*;
 use mock::*;
 
-use frame_support::{assert_noop, assert_ok};
-use primitives::Leverage;
+use frame_support::{assert_noop, assert_ok, storage, traits::OnRuntimeUpgrade};
+use primitives::{Leverage, Side};
 use sp_core::offchain::{
 	testing::{TestOffchainExt, TestTransactionPoolExt},
 	OffchainExt, TransactionPoolExt,
 };
+use sp_runtime::traits::BadOrigin;
 
 // `n` is a natural currency amount by cent, with 2 fractional digits precision
 fn fixedi128_saturating_from_integer_currency_cent(n: i128) -> FixedI128 {
@@ -71,10 +72,12 @@ fn positions_snapshot(
 		long: LeveragedAmounts {
 			held: long_base_amount,
 			debits: long_quote_amount,
+			swap_debits: FixedI128::zero(),
 		},
 		short: LeveragedAmounts {
 			held: short_base_amount,
 			debits: short_quote_amount,
+			swap_debits: FixedI128::zero(),
 		},
 	}
 }
@@ -84,7 +87,7 @@ fn eur_jpy_long() -> Position<Runtime> {
 		owner: ALICE,
 		pool: MOCK_POOL,
 		pair: EUR_JPY_PAIR,
-		leverage: Leverage::LongTwenty,
+		leverage: Leverage::LongTwenty.into(),
 		leveraged_held: FixedI128::saturating_from_integer(100_000),
 		leveraged_debits: FixedI128::saturating_from_integer(-14_104_090),
 		open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -97,7 +100,7 @@ fn eur_jpy_short() -> Position<Runtime> {
 		owner: ALICE,
 		pool: MOCK_POOL,
 		pair: EUR_JPY_PAIR,
-		leverage: Leverage::ShortTwenty,
+		leverage: Leverage::ShortTwenty.into(),
 		leveraged_held: FixedI128::saturating_from_integer(-100_000),
 		leveraged_debits: FixedI128::saturating_from_integer(14_175_810),
 		open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -171,6 +174,14 @@ fn margin_held_sums_all_margin_held() {
 	});
 }
 
+fn set_collateral_haircut(currency_id: CurrencyId, haircut_percent: u32) {
+	assert_ok!(MarginProtocol::set_collateral_haircut(
+		Origin::signed(UpdateOrigin::get()),
+		currency_id,
+		Some(Permill::from_percent(haircut_percent))
+	));
+}
+
 fn eur_usd_long_1() -> Position<Runtime> {
 	let open_rate =
 		FixedI128::saturating_from_integer(1).saturating_add(FixedI128::saturating_from_rational(36_87, 100_000_00));
@@ -178,7 +189,7 @@ fn eur_usd_long_1() -> Position<Runtime> {
 		owner: ALICE,
 		pool: MOCK_POOL,
 		pair: EUR_USD_PAIR,
-		leverage: Leverage::LongFive,
+		leverage: Leverage::LongFive.into(),
 		leveraged_held: FixedI128::saturating_from_integer(100_000),
 		leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-120_420_30),
 		open_accumulated_swap_rate: open_rate,
@@ -193,7 +204,7 @@ fn eur_usd_long_2() -> Position<Runtime> {
 		owner: ALICE,
 		pool: MOCK_POOL,
 		pair: EUR_USD_PAIR,
-		leverage: Leverage::LongTwenty,
+		leverage: Leverage::LongTwenty.into(),
 		leveraged_held: FixedI128::saturating_from_integer(100_000),
 		leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-119_419_30),
 		open_accumulated_swap_rate: open_rate,
@@ -208,7 +219,7 @@ fn eur_usd_short_1() -> Position<Runtime> {
 		owner: ALICE,
 		pool: MOCK_POOL,
 		pair: EUR_USD_PAIR,
-		leverage: Leverage::ShortTen,
+		leverage: Leverage::ShortTen.into(),
 		leveraged_held: FixedI128::saturating_from_integer(-100_000),
 		leveraged_debits: fixedi128_saturating_from_integer_currency_cent(119_780_10),
 		open_accumulated_swap_rate: open_rate,
@@ -223,7 +234,7 @@ fn eur_usd_short_2() -> Position<Runtime> {
 		owner: ALICE,
 		pool: MOCK_POOL,
 		pair: EUR_USD_PAIR,
-		leverage: Leverage::ShortFifty,
+		leverage: Leverage::ShortFifty.into(),
 		leveraged_held: FixedI128::saturating_from_integer(-200_000),
 		leveraged_debits: fixedi128_saturating_from_integer_currency_cent(237_362_40),
 		open_accumulated_swap_rate: open_rate,
@@ -238,7 +249,7 @@ fn jpy_usd_long_1() -> Position<Runtime> {
 		owner: ALICE,
 		pool: MOCK_POOL,
 		pair: JPY_USD_PAIR,
-		leverage: Leverage::LongFive,
+		leverage: Leverage::LongFive.into(),
 		leveraged_held: FixedI128::saturating_from_integer(100_000),
 		leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-120_420_30),
 		open_accumulated_swap_rate: open_rate,
@@ -372,7 +383,7 @@ fn ensure_trader_safe_works() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -571,7 +582,7 @@ fn ensure_liquidity_works() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -615,7 +626,7 @@ fn ensure_pool_safe_works() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -708,7 +719,7 @@ fn trader_margin_call_should_work() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -757,7 +768,7 @@ fn trader_become_safe_should_work() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -818,7 +829,7 @@ fn trader_stop_out_should_work() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -849,6 +860,10 @@ fn trader_stop_out_should_work() {
 
 			let event = TestEvent::margin_protocol(RawEvent::TraderStoppedOut(ALICE));
 			assert!(System::events().iter().any(|record| record.event == event));
+
+			let stats = MarginProtocol::trader_stats(ALICE, MOCK_POOL);
+			assert_eq!(stats.positions_closed, 1);
+			assert_eq!(stats.positions_stopped_out, 1);
 		});
 }
 
@@ -867,7 +882,7 @@ fn trader_stop_out_close_bigger_loss_position() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -878,7 +893,7 @@ fn trader_stop_out_close_bigger_loss_position() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -910,6 +925,11 @@ fn trader_stop_out_close_bigger_loss_position() {
 			// position with bigger loss is closed
 			assert!(<PositionsByTrader<Runtime>>::contains_key(ALICE, (MOCK_POOL, 0)));
 			assert!(!<PositionsByTrader<Runtime>>::contains_key(ALICE, (MOCK_POOL, 1)));
+
+			// only the closed position is counted
+			let stats = MarginProtocol::trader_stats(ALICE, MOCK_POOL);
+			assert_eq!(stats.positions_closed, 1);
+			assert_eq!(stats.positions_stopped_out, 1);
 		});
 }
 
@@ -928,7 +948,7 @@ fn liquidity_pool_margin_call_and_become_safe_work() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -1000,7 +1020,7 @@ fn liquidity_pool_force_close_works() {
 				Origin::signed(ALICE),
 				MOCK_POOL,
 				EUR_USD_PAIR,
-				Leverage::LongTwenty,
+				Leverage::LongTwenty.into(),
 				balance_saturating_from_integer_currency_cent(10_000_00),
 				Price::saturating_from_integer(2)
 			));
@@ -1035,6 +1055,253 @@ fn liquidity_pool_force_close_works() {
 				LiquidityCurrency::total_balance(&TREASURY_ACCOUNT),
 				300_000000000000000000
 			);
+
+			let stats = MarginProtocol::trader_stats(ALICE, MOCK_POOL);
+			assert_eq!(stats.positions_opened, 1);
+			assert_eq!(stats.positions_closed, 1);
+			assert_eq!(stats.positions_stopped_out, 1);
+		});
+}
+
+#[test]
+fn open_position_with_arbitrary_leverage_works() {
+	ExtBuilder::default()
+		.spread(Price::from_fraction(0.01))
+		.price(CurrencyId::FEUR, (1, 1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(10_000_00))
+		.build()
+		.execute_with(|| {
+			set_enp_risk_threshold(EUR_USD_PAIR, risk_threshold(0, 0));
+			set_ell_risk_threshold(EUR_USD_PAIR, risk_threshold(0, 0));
+			<Balances<Runtime>>::insert(
+				ALICE,
+				MOCK_POOL,
+				fixedi128_saturating_from_integer_currency_cent(10_000_00),
+			);
+
+			assert_noop!(
+				MarginProtocol::open_position(
+					Origin::signed(ALICE),
+					MOCK_POOL,
+					EUR_USD_PAIR,
+					PositionLeverage::new(Side::Long, 0),
+					balance_saturating_from_integer_currency_cent(7_000_00),
+					Price::saturating_from_integer(2)
+				),
+				Error::<Runtime>::InvalidLeverage
+			);
+
+			let leverage = PositionLeverage::new(Side::Long, 7);
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_USD_PAIR,
+				leverage,
+				balance_saturating_from_integer_currency_cent(7_000_00),
+				Price::saturating_from_integer(2)
+			));
+
+			// leveraged debits 7000 * 1.01 = 7070, margin held 7070 / 7 = 1010
+			let position = MarginProtocol::positions(0).unwrap();
+			assert_eq!(position.leverage, leverage);
+			assert_eq!(
+				position.margin_held,
+				fixedi128_saturating_from_integer_currency_cent(1_010_00)
+			);
+
+			let stats = MarginProtocol::trader_stats(ALICE, MOCK_POOL);
+			assert_eq!(stats.positions_opened, 1);
+			assert_eq!(stats.volume, balance_saturating_from_integer_currency_cent(7_070_00));
+		});
+}
+
+#[test]
+fn liquidity_pool_wind_down_close_works() {
+	ExtBuilder::default()
+		.spread(Price::from_fraction(0.01))
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(10_000_00))
+		.build()
+		.execute_with(|| {
+			<Balances<Runtime>>::insert(
+				ALICE,
+				MOCK_POOL,
+				fixedi128_saturating_from_integer_currency_cent(10_000_00),
+			);
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_USD_PAIR,
+				Leverage::LongTwenty.into(),
+				balance_saturating_from_integer_currency_cent(10_000_00),
+				Price::saturating_from_integer(2)
+			));
+			assert_eq!(
+				MarginProtocol::positions_by_pool(MOCK_POOL, (EUR_USD_PAIR, 0)),
+				Some(())
+			);
+
+			assert_noop!(
+				MarginProtocol::liquidity_pool_wind_down_close(Origin::none(), MOCK_POOL),
+				Error::<Runtime>::NotReachedWindDownDeadline
+			);
+
+			MockLiquidityPools::set_mock_wind_down_expired(true);
+			assert_ok!(MarginProtocol::liquidity_pool_wind_down_close(
+				Origin::none(),
+				MOCK_POOL
+			));
+
+			let event = TestEvent::margin_protocol(RawEvent::LiquidityPoolWoundDown(MOCK_POOL, 1));
+			assert!(System::events().iter().any(|record| record.event == event));
+
+			assert_eq!(MarginProtocol::positions_by_pool(MOCK_POOL, (EUR_USD_PAIR, 0)), None);
+			assert_eq!(MarginProtocol::positions_by_trader(ALICE, (MOCK_POOL, 0)), None);
+			assert_eq!(MarginProtocol::positions(0), None);
+
+			// closed at oracle price, not a stop out
+			let stats = MarginProtocol::trader_stats(ALICE, MOCK_POOL);
+			assert_eq!(stats.positions_closed, 1);
+			assert_eq!(stats.positions_stopped_out, 0);
+
+			assert_noop!(
+				MarginProtocol::liquidity_pool_wind_down_close(Origin::none(), MOCK_POOL),
+				Error::<Runtime>::NoPositionClosed
+			);
+		});
+}
+
+#[test]
+fn trader_stats_accumulate_over_closes() {
+	ExtBuilder::default()
+		.spread(Price::zero())
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(10_000_00))
+		.build()
+		.execute_with(|| {
+			<Balances<Runtime>>::insert(
+				ALICE,
+				MOCK_POOL,
+				fixedi128_saturating_from_integer_currency_cent(1_000_00),
+			);
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_USD_PAIR,
+				Leverage::LongTwo.into(),
+				balance_saturating_from_integer_currency_cent(200_00),
+				Price::saturating_from_integer(2)
+			));
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_USD_PAIR,
+				Leverage::ShortTwo.into(),
+				balance_saturating_from_integer_currency_cent(100_00),
+				Price::zero()
+			));
+
+			MockPrices::set_mock_price(CurrencyId::FEUR, Some(Price::saturating_from_rational(6, 5)));
+			assert_ok!(MarginProtocol::close_position(Origin::signed(ALICE), 0, Price::zero()));
+			assert_ok!(MarginProtocol::close_position(
+				Origin::signed(ALICE),
+				1,
+				Price::saturating_from_integer(2)
+			));
+
+			// long profit 200 * 0.2 = 40, short loss 100 * 0.2 = 20
+			// volume 200 + 100 at open, 200 * 1.2 + 100 * 1.2 at close
+			assert_eq!(
+				MarginProtocol::trader_stats(ALICE, MOCK_POOL),
+				TraderStats {
+					realized_pl: FixedI128::saturating_from_integer(20),
+					swap: FixedI128::zero(),
+					volume: balance_saturating_from_integer_currency_cent(660_00),
+					positions_opened: 2,
+					positions_closed: 2,
+					positions_stopped_out: 0,
+				}
+			);
+		});
+}
+
+#[test]
+fn liquidity_pool_wind_down_close_is_limited_per_call() {
+	ExtBuilder::default()
+		.spread(Price::from_fraction(0.01))
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(10_000_00))
+		.build()
+		.execute_with(|| {
+			<Balances<Runtime>>::insert(
+				ALICE,
+				MOCK_POOL,
+				fixedi128_saturating_from_integer_currency_cent(10_000_00),
+			);
+			for _ in 0..3 {
+				assert_ok!(MarginProtocol::open_position(
+					Origin::signed(ALICE),
+					MOCK_POOL,
+					EUR_USD_PAIR,
+					Leverage::LongTwenty.into(),
+					balance_saturating_from_integer_currency_cent(1_000_00),
+					Price::saturating_from_integer(2)
+				));
+			}
+
+			MockLiquidityPools::set_mock_wind_down_expired(true);
+			assert_ok!(MarginProtocol::liquidity_pool_wind_down_close(
+				Origin::none(),
+				MOCK_POOL
+			));
+			let event = TestEvent::margin_protocol(RawEvent::LiquidityPoolWoundDown(MOCK_POOL, 2));
+			assert!(System::events().iter().any(|record| record.event == event));
+			assert_eq!(PositionsByPool::iter_prefix(MOCK_POOL).count(), 1);
+
+			assert_ok!(MarginProtocol::liquidity_pool_wind_down_close(
+				Origin::none(),
+				MOCK_POOL
+			));
+			assert_eq!(PositionsByPool::iter_prefix(MOCK_POOL).count(), 0);
+		});
+}
+
+#[test]
+fn liquidity_pool_wind_down_close_fails_if_no_position_closed() {
+	ExtBuilder::default()
+		.spread(Price::from_fraction(0.01))
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(10_000_00))
+		.build()
+		.execute_with(|| {
+			<Balances<Runtime>>::insert(
+				ALICE,
+				MOCK_POOL,
+				fixedi128_saturating_from_integer_currency_cent(10_000_00),
+			);
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_USD_PAIR,
+				Leverage::LongTwenty.into(),
+				balance_saturating_from_integer_currency_cent(10_000_00),
+				Price::saturating_from_integer(2)
+			));
+
+			MockLiquidityPools::set_mock_wind_down_expired(true);
+			MockPrices::set_mock_price(CurrencyId::FEUR, None);
+			assert_noop!(
+				MarginProtocol::liquidity_pool_wind_down_close(Origin::none(), MOCK_POOL),
+				Error::<Runtime>::NoPrice
+			);
+			assert_eq!(
+				MarginProtocol::positions_by_pool(MOCK_POOL, (EUR_USD_PAIR, 0)),
+				Some(())
+			);
 		});
 }
 
@@ -1058,7 +1325,7 @@ fn open_long_position_works() {
 				Origin::signed(ALICE),
 				MOCK_POOL,
 				EUR_JPY_PAIR,
-				Leverage::LongTwenty,
+				Leverage::LongTwenty.into(),
 				balance_saturating_from_integer_currency_cent(100_000_00),
 				Price::saturating_from_integer(142)
 			));
@@ -1084,7 +1351,7 @@ fn open_long_position_works() {
 				0,
 				MOCK_POOL,
 				EUR_JPY_PAIR,
-				Leverage::LongTwenty,
+				Leverage::LongTwenty.into(),
 				balance_saturating_from_integer_currency_cent(100_000_00),
 				// price: 141.0409
 				Price::from_inner(141_040900000000007325),
@@ -1113,7 +1380,7 @@ fn open_short_position_works() {
 				Origin::signed(ALICE),
 				MOCK_POOL,
 				EUR_JPY_PAIR,
-				Leverage::ShortTwenty,
+				Leverage::ShortTwenty.into(),
 				balance_saturating_from_integer_currency_cent(100_000_00),
 				Price::saturating_from_integer(141)
 			));
@@ -1151,7 +1418,7 @@ fn open_position_fails_if_trader_margin_called() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1182,7 +1449,7 @@ fn open_position_fails_if_pool_margin_called() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1209,7 +1476,7 @@ fn open_position_fails_if_no_base_price() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1236,7 +1503,7 @@ fn open_position_fails_if_no_quote_price() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1266,7 +1533,7 @@ fn open_long_position_fails_if_market_price_too_high() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(141)
 				),
@@ -1296,7 +1563,7 @@ fn open_short_position_fails_if_market_price_too_low() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::ShortTwenty,
+					Leverage::ShortTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1320,7 +1587,7 @@ fn open_position_fails_if_leveraged_debits_out_of_bound() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					u128::max_value() / 2 + 1,
 					Price::saturating_from_integer(142)
 				),
@@ -1343,7 +1610,7 @@ fn open_position_fails_if_insufficient_free_margin() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwo,
+					Leverage::LongTwo.into(),
 					1,
 					Price::saturating_from_integer(142)
 				),
@@ -1374,7 +1641,7 @@ fn open_position_fails_if_would_reach_enp_threshold() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1405,7 +1672,7 @@ fn open_position_fails_if_would_reach_ell_threshold() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1436,7 +1703,7 @@ fn open_position_fails_if_run_out_of_position_id() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1466,7 +1733,7 @@ fn free_margin_cannot_be_used_across_pool() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_JPY_PAIR,
-					Leverage::LongTwenty,
+					Leverage::LongTwenty.into(),
 					balance_saturating_from_integer_currency_cent(100_000_00),
 					Price::saturating_from_integer(142)
 				),
@@ -1475,6 +1742,35 @@ fn free_margin_cannot_be_used_across_pool() {
 		});
 }
 
+#[test]
+fn cross_margin_free_margin_can_be_used_across_pool() {
+	ExtBuilder::default()
+		// USD/JPY = 107
+		.price(CurrencyId::FJPY, (1, 107))
+		// EUR/JPY = 140.9 => EUR/USD = 140.9/107
+		.price(CurrencyId::FEUR, (1409, 1070))
+		.accumulated_swap_rate(EUR_JPY_PAIR, FixedI128::saturating_from_integer(1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(100_000_00))
+		.build()
+		.execute_with(|| {
+			<Balances<Runtime>>::insert(
+				ALICE,
+				MOCK_POOL_1,
+				fixedi128_saturating_from_integer_currency_cent(10_000_00),
+			);
+			assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_JPY_PAIR,
+				Leverage::LongTwenty.into(),
+				balance_saturating_from_integer_currency_cent(100_000_00),
+				Price::saturating_from_integer(142)
+			));
+			assert!(MarginProtocol::positions(0).is_some());
+		});
+}
+
 #[test]
 fn close_loss_position_works() {
 	let alice_initial = fixedi128_saturating_from_integer_currency_cent(10_000_00);
@@ -1525,13 +1821,75 @@ fn close_loss_position_works() {
 			assert_eq!(MarginProtocol::positions_by_trader(ALICE, (MOCK_POOL, id)), None);
 			assert_eq!(MarginProtocol::positions_by_pool(MOCK_POOL, (EUR_USD_PAIR, id)), None);
 
+			// realized = -584.69896461, swap = 120420.30 * (1 - 1.0003687) = -44.39896461
+			let realized_pl = FixedI128::from_inner(-540_300_000_000_000_000_000);
+			let swap = FixedI128::from_inner(-44_398_964_610_000_000_000);
 			let event = TestEvent::margin_protocol(RawEvent::PositionClosed(
 				ALICE,
 				id,
 				MOCK_POOL,
 				Price::saturating_from_rational(11988, 10000),
+				realized_pl,
+				swap,
 			));
 			assert!(System::events().iter().any(|record| record.event == event));
+
+			assert_eq!(
+				MarginProtocol::trader_stats(ALICE, MOCK_POOL),
+				TraderStats {
+					realized_pl,
+					swap,
+					volume: balance_saturating_from_integer_currency_cent(119_880_00),
+					positions_opened: 0,
+					positions_closed: 1,
+					positions_stopped_out: 0,
+				}
+			);
+		});
+}
+
+#[test]
+fn cross_margin_close_loss_position_draws_from_other_pools() {
+	let alice_initial = fixedi128_saturating_from_integer_currency_cent(10_000_00);
+	ExtBuilder::default()
+		.module_balance(alice_initial)
+		// EUR/USD = 1.2
+		.price(CurrencyId::FEUR, (12, 10))
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(100_000_00))
+		.build()
+		.execute_with(|| {
+			assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL_1, alice_initial);
+
+			let position = eur_usd_long_1();
+			let id = 0;
+			<Positions<Runtime>>::insert(id, position.clone());
+			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
+			PositionsByPool::insert(MOCK_POOL, (EUR_USD_PAIR, 0), ());
+
+			let snapshot = positions_snapshot(
+				1,
+				position.leveraged_held,
+				position.leveraged_debits,
+				FixedI128::saturating_from_integer(0),
+				FixedI128::saturating_from_integer(0),
+			);
+			PositionsSnapshots::insert(MOCK_POOL, EUR_USD_PAIR, snapshot.clone());
+
+			assert_ok!(MarginProtocol::close_position(
+				Origin::signed(ALICE),
+				id,
+				Price::saturating_from_rational(11, 10)
+			));
+
+			// loss paid by the balance in `MOCK_POOL_1`
+			assert_eq!(MarginProtocol::balances(ALICE, MOCK_POOL), FixedI128::zero());
+			assert_eq!(
+				MarginProtocol::balances(ALICE, MOCK_POOL_1),
+				FixedI128::from_inner(9415301035390000000000)
+			);
+			assert_eq!(MockLiquidityPools::liquidity(MOCK_POOL), 100584698964610000000000);
 		});
 }
 
@@ -1551,7 +1909,7 @@ fn close_loss_position_realizing_part_on_not_enough_equity() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTen,
+				leverage: Leverage::LongTen.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(10_00),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100_00),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -1603,7 +1961,7 @@ fn close_loss_position_owning_and_repayment() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTen,
+				leverage: Leverage::LongTen.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(10_00),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100_00),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -1614,7 +1972,7 @@ fn close_loss_position_owning_and_repayment() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: JPY_USD_PAIR,
-				leverage: Leverage::LongFive,
+				leverage: Leverage::LongFive.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(5_00),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-5_00),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -1705,13 +2063,14 @@ fn close_profit_position_works() {
 			<Positions<Runtime>>::insert(id, position.clone());
 			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
 			PositionsByPool::insert(MOCK_POOL, (EUR_USD_PAIR, 0), ());
-			let snapshot = positions_snapshot(
+			let mut snapshot = positions_snapshot(
 				1,
 				position.leveraged_held,
 				position.leveraged_debits,
 				FixedI128::saturating_from_integer(0),
 				FixedI128::saturating_from_integer(0),
 			);
+			snapshot.long.swap_debits = MarginProtocol::swap_debits_of_position(&position).unwrap();
 			PositionsSnapshots::insert(MOCK_POOL, EUR_USD_PAIR, snapshot.clone());
 			assert_eq!(
 				MarginProtocol::pool_positions_snapshots(MOCK_POOL, EUR_USD_PAIR),
@@ -1980,7 +2339,7 @@ fn trader_can_withdraw_unrealized_profit() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(10_00),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -2015,7 +2374,7 @@ fn withdraw_fails_if_insufficient_free_margin() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -2040,8 +2399,830 @@ fn withdraw_fails_if_insufficient_free_margin() {
 }
 
 #[test]
-fn offchain_worker_should_work() {
-	let mut ext = ExtBuilder::default()
+fn transfer_margin_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		<Balances<Runtime>>::insert(ALICE, MOCK_POOL, FixedI128::from_inner(1000));
+		assert_ok!(MarginProtocol::transfer_margin(
+			Origin::signed(ALICE),
+			MOCK_POOL,
+			MOCK_POOL_1,
+			400
+		));
+
+		assert_eq!(MarginProtocol::balances(&ALICE, MOCK_POOL), FixedI128::from_inner(600));
+		assert_eq!(
+			MarginProtocol::balances(&ALICE, MOCK_POOL_1),
+			FixedI128::from_inner(400)
+		);
+
+		let event = TestEvent::margin_protocol(RawEvent::MarginTransferred(ALICE, MOCK_POOL, MOCK_POOL_1, 400));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		assert_noop!(
+			MarginProtocol::transfer_margin(Origin::signed(ALICE), MOCK_POOL, MOCK_POOL, 100),
+			Error::<Runtime>::TransferToSamePool
+		);
+	});
+}
+
+#[test]
+fn transfer_margin_fails_if_insufficient_free_margin() {
+	ExtBuilder::default()
+		.spread(Price::zero())
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.build()
+		.execute_with(|| {
+			set_trader_risk_threshold(EUR_USD_PAIR, risk_threshold(100, 0));
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL, fixedi128_saturating_from_integer_currency_cent(100));
+			let position: Position<Runtime> = Position {
+				owner: ALICE,
+				pool: MOCK_POOL,
+				pair: EUR_USD_PAIR,
+				leverage: Leverage::LongTwo.into(),
+				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
+				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
+				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
+				margin_held: fixedi128_saturating_from_integer_currency_cent(100),
+			};
+			<Positions<Runtime>>::insert(0, position);
+			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
+
+			assert_noop!(
+				MarginProtocol::transfer_margin(
+					Origin::signed(ALICE),
+					MOCK_POOL,
+					MOCK_POOL_1,
+					balance_saturating_from_integer_currency_cent(1)
+				),
+				Error::<Runtime>::InsufficientFreeMargin
+			);
+		});
+}
+
+#[test]
+fn set_cross_margin_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+		assert_eq!(MarginProtocol::cross_margin_traders(ALICE), Some(()));
+
+		let event = TestEvent::margin_protocol(RawEvent::CrossMarginSet(ALICE, true));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), false, 2));
+		assert_eq!(MarginProtocol::cross_margin_traders(ALICE), None);
+	});
+}
+
+#[test]
+fn disable_cross_margin_fails_if_unsafe_in_any_pool() {
+	ExtBuilder::default()
+		.spread(Price::zero())
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.build()
+		.execute_with(|| {
+			set_trader_risk_threshold(EUR_USD_PAIR, risk_threshold(50, 10));
+			assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL_1, fixedi128_saturating_from_integer_currency_cent(100));
+			let position: Position<Runtime> = Position {
+				owner: ALICE,
+				pool: MOCK_POOL,
+				pair: EUR_USD_PAIR,
+				leverage: Leverage::LongTwo.into(),
+				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
+				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
+				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
+				margin_held: fixedi128_saturating_from_integer_currency_cent(50),
+			};
+			<Positions<Runtime>>::insert(0, position);
+			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
+
+			// safe with margin in `MOCK_POOL_1`
+			assert_ok!(MarginProtocol::ensure_trader_safe(&ALICE, MOCK_POOL, Action::None));
+
+			assert_noop!(
+				MarginProtocol::set_cross_margin(Origin::signed(ALICE), false, 2),
+				Error::<Runtime>::UnsafeTrader
+			);
+		});
+}
+
+#[test]
+fn disable_cross_margin_fails_if_too_many_pools() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+		<Balances<Runtime>>::insert(ALICE, MOCK_POOL, fixedi128_saturating_from_integer_currency_cent(100));
+		<Balances<Runtime>>::insert(ALICE, MOCK_POOL_1, fixedi128_saturating_from_integer_currency_cent(100));
+
+		assert_noop!(
+			MarginProtocol::set_cross_margin(Origin::signed(ALICE), false, 1),
+			Error::<Runtime>::TooManyPools
+		);
+		assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), false, 2));
+	});
+}
+
+#[test]
+fn disable_cross_margin_fails_if_negative_balance_in_any_pool() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+		<Balances<Runtime>>::insert(ALICE, MOCK_POOL, fixedi128_saturating_from_integer_currency_cent(-10));
+		<Balances<Runtime>>::insert(ALICE, MOCK_POOL_1, fixedi128_saturating_from_integer_currency_cent(100));
+
+		assert_noop!(
+			MarginProtocol::set_cross_margin(Origin::signed(ALICE), false, 2),
+			Error::<Runtime>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn disable_cross_margin_fails_if_negative_free_margin_in_any_pool() {
+	ExtBuilder::default()
+		.spread(Price::zero())
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.build()
+		.execute_with(|| {
+			set_trader_risk_threshold(EUR_USD_PAIR, risk_threshold(5, 2));
+			assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL, fixedi128_saturating_from_integer_currency_cent(10));
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL_1, fixedi128_saturating_from_integer_currency_cent(100));
+			let position: Position<Runtime> = Position {
+				owner: ALICE,
+				pool: MOCK_POOL,
+				pair: EUR_USD_PAIR,
+				leverage: Leverage::LongTwo.into(),
+				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
+				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
+				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
+				margin_held: fixedi128_saturating_from_integer_currency_cent(50),
+			};
+			<Positions<Runtime>>::insert(0, position);
+			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
+
+			// safe in `MOCK_POOL` on its own, but margin held not covered
+			assert_eq!(
+				MarginProtocol::free_margin(&ALICE, MOCK_POOL),
+				Ok(fixedi128_saturating_from_integer_currency_cent(-40))
+			);
+			assert_noop!(
+				MarginProtocol::set_cross_margin(Origin::signed(ALICE), false, 2),
+				Error::<Runtime>::InsufficientIsolatedMargin
+			);
+		});
+}
+
+#[test]
+fn cross_margin_trader_cannot_withdraw_more_than_pool_margin() {
+	ExtBuilder::default()
+		.module_balance(fixedi128_saturating_from_integer_currency_cent(200))
+		.build()
+		.execute_with(|| {
+			assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL, fixedi128_saturating_from_integer_currency_cent(100));
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL_1, fixedi128_saturating_from_integer_currency_cent(100));
+
+			assert_noop!(
+				MarginProtocol::withdraw(
+					Origin::signed(ALICE),
+					MOCK_POOL,
+					balance_saturating_from_integer_currency_cent(101)
+				),
+				Error::<Runtime>::InsufficientIsolatedMargin
+			);
+			assert_noop!(
+				MarginProtocol::transfer_margin(
+					Origin::signed(ALICE),
+					MOCK_POOL,
+					MOCK_POOL_1,
+					balance_saturating_from_integer_currency_cent(101)
+				),
+				Error::<Runtime>::InsufficientIsolatedMargin
+			);
+			assert_ok!(MarginProtocol::withdraw(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				balance_saturating_from_integer_currency_cent(100)
+			));
+		});
+}
+
+#[test]
+fn set_collateral_haircut_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			MarginProtocol::set_collateral_haircut(Origin::signed(BOB), CurrencyId::FEUR, None),
+			BadOrigin
+		);
+		assert_noop!(
+			MarginProtocol::set_collateral_haircut(
+				Origin::signed(UpdateOrigin::get()),
+				CurrencyId::AUSD,
+				Some(Permill::from_percent(10))
+			),
+			Error::<Runtime>::CollateralNotAllowed
+		);
+
+		set_collateral_haircut(CurrencyId::FEUR, 10);
+		assert_eq!(
+			MarginProtocol::collateral_haircuts(CurrencyId::FEUR),
+			Some(Permill::from_percent(10))
+		);
+		let event = TestEvent::margin_protocol(RawEvent::CollateralHaircutSet(
+			CurrencyId::FEUR,
+			Some(Permill::from_percent(10)),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		assert_ok!(MarginProtocol::set_collateral_haircut(
+			Origin::signed(UpdateOrigin::get()),
+			CurrencyId::FEUR,
+			None
+		));
+		assert_eq!(MarginProtocol::collateral_haircuts(CurrencyId::FEUR), None);
+	});
+}
+
+#[test]
+fn deposit_and_withdraw_collateral_works() {
+	ExtBuilder::default()
+		.alice_currency_balance(CurrencyId::FEUR, 1000)
+		// EUR/USD = 1.2
+		.price(CurrencyId::FEUR, (12, 10))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				MarginProtocol::deposit_collateral(Origin::signed(ALICE), MOCK_POOL, CurrencyId::FEUR, 1000),
+				Error::<Runtime>::CollateralNotAllowed
+			);
+
+			set_collateral_haircut(CurrencyId::FEUR, 10);
+			assert_noop!(
+				MarginProtocol::deposit_collateral(Origin::signed(ALICE), MOCK_POOL_1, CurrencyId::FEUR, 1000),
+				Error::<Runtime>::PoolNotFound
+			);
+			assert_ok!(MarginProtocol::deposit_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FEUR,
+				1000
+			));
+			assert_eq!(MarginProtocol::collaterals(ALICE, (MOCK_POOL, CurrencyId::FEUR)), 1000);
+			assert_eq!(Currencies::free_balance(CurrencyId::FEUR, &ALICE), 0);
+			let event =
+				TestEvent::margin_protocol(RawEvent::CollateralDeposited(ALICE, MOCK_POOL, CurrencyId::FEUR, 1000));
+			assert!(System::events().iter().any(|record| record.event == event));
+
+			// 1000 * 1.2 * (1 - 10%)
+			assert_eq!(
+				MarginProtocol::equity_of_trader(&ALICE, MOCK_POOL),
+				Ok(FixedI128::from_inner(1080))
+			);
+
+			assert_noop!(
+				MarginProtocol::withdraw_collateral(Origin::signed(ALICE), MOCK_POOL, CurrencyId::FEUR, 1001),
+				Error::<Runtime>::InsufficientCollateral
+			);
+			assert_ok!(MarginProtocol::withdraw_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FEUR,
+				400
+			));
+			assert_eq!(MarginProtocol::collaterals(ALICE, (MOCK_POOL, CurrencyId::FEUR)), 600);
+			assert_eq!(Currencies::free_balance(CurrencyId::FEUR, &ALICE), 400);
+			let event =
+				TestEvent::margin_protocol(RawEvent::CollateralWithdrew(ALICE, MOCK_POOL, CurrencyId::FEUR, 400));
+			assert!(System::events().iter().any(|record| record.event == event));
+		});
+}
+
+#[test]
+fn collateral_cannot_be_withdrawn_if_insufficient_free_margin() {
+	ExtBuilder::default()
+		.spread(Price::zero())
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.price(CurrencyId::FJPY, (1, 1))
+		.alice_currency_balance(CurrencyId::FJPY, balance_saturating_from_integer_currency_cent(200))
+		.build()
+		.execute_with(|| {
+			set_collateral_haircut(CurrencyId::FJPY, 0);
+			assert_ok!(MarginProtocol::deposit_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FJPY,
+				balance_saturating_from_integer_currency_cent(200)
+			));
+			let position: Position<Runtime> = Position {
+				owner: ALICE,
+				pool: MOCK_POOL,
+				pair: EUR_USD_PAIR,
+				leverage: Leverage::LongTwo.into(),
+				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
+				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
+				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
+				margin_held: fixedi128_saturating_from_integer_currency_cent(100),
+			};
+			<Positions<Runtime>>::insert(0, position);
+			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
+
+			assert_eq!(
+				MarginProtocol::free_margin(&ALICE, MOCK_POOL),
+				Ok(fixedi128_saturating_from_integer_currency_cent(100))
+			);
+
+			// collateral can't be withdrawn as liquidity currency
+			assert_noop!(
+				MarginProtocol::withdraw(
+					Origin::signed(ALICE),
+					MOCK_POOL,
+					balance_saturating_from_integer_currency_cent(1)
+				),
+				Error::<Runtime>::InsufficientFreeMargin
+			);
+
+			assert_noop!(
+				MarginProtocol::withdraw_collateral(
+					Origin::signed(ALICE),
+					MOCK_POOL,
+					CurrencyId::FJPY,
+					balance_saturating_from_integer_currency_cent(101)
+				),
+				Error::<Runtime>::InsufficientFreeMargin
+			);
+			assert_ok!(MarginProtocol::withdraw_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FJPY,
+				balance_saturating_from_integer_currency_cent(100)
+			));
+		});
+}
+
+#[test]
+fn cross_margin_trader_cannot_withdraw_collateral_backing_pool_positions() {
+	ExtBuilder::default()
+		.spread(Price::zero())
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.price(CurrencyId::FJPY, (1, 1))
+		.alice_currency_balance(CurrencyId::FJPY, balance_saturating_from_integer_currency_cent(200))
+		.build()
+		.execute_with(|| {
+			set_collateral_haircut(CurrencyId::FJPY, 0);
+			assert_ok!(MarginProtocol::set_cross_margin(Origin::signed(ALICE), true, 0));
+			assert_ok!(MarginProtocol::deposit_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FJPY,
+				balance_saturating_from_integer_currency_cent(200)
+			));
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL_1, fixedi128_saturating_from_integer_currency_cent(100));
+			let position: Position<Runtime> = Position {
+				owner: ALICE,
+				pool: MOCK_POOL,
+				pair: EUR_USD_PAIR,
+				leverage: Leverage::LongTwo.into(),
+				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
+				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
+				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
+				margin_held: fixedi128_saturating_from_integer_currency_cent(100),
+			};
+			<Positions<Runtime>>::insert(0, position);
+			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
+
+			assert_eq!(
+				MarginProtocol::free_margin(&ALICE, MOCK_POOL),
+				Ok(fixedi128_saturating_from_integer_currency_cent(100))
+			);
+
+			// enough free margin across pools, but not in `MOCK_POOL` on its own
+			assert_noop!(
+				MarginProtocol::withdraw_collateral(
+					Origin::signed(ALICE),
+					MOCK_POOL,
+					CurrencyId::FJPY,
+					balance_saturating_from_integer_currency_cent(101)
+				),
+				Error::<Runtime>::InsufficientIsolatedMargin
+			);
+			assert_ok!(MarginProtocol::withdraw_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FJPY,
+				balance_saturating_from_integer_currency_cent(100)
+			));
+		});
+}
+
+#[test]
+fn trader_stop_out_seizes_collaterals() {
+	ExtBuilder::default()
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(200))
+		.spread(Price::zero())
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.price(CurrencyId::FJPY, (1, 1))
+		.alice_currency_balance(CurrencyId::FJPY, balance_saturating_from_integer_currency_cent(100))
+		.build()
+		.execute_with(|| {
+			set_trader_risk_threshold(EUR_USD_PAIR, risk_threshold(5, 3));
+			set_collateral_haircut(CurrencyId::FJPY, 0);
+			assert_ok!(MarginProtocol::deposit_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FJPY,
+				balance_saturating_from_integer_currency_cent(100)
+			));
+
+			let position: Position<Runtime> = Position {
+				owner: ALICE,
+				pool: MOCK_POOL,
+				pair: EUR_USD_PAIR,
+				leverage: Leverage::LongTwo.into(),
+				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
+				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
+				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
+				margin_held: fixedi128_saturating_from_integer_currency_cent(100),
+			};
+			<Positions<Runtime>>::insert(0, position.clone());
+			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
+			PositionsByPool::insert(MOCK_POOL, (EUR_USD_PAIR, 0), ());
+			let snapshot = positions_snapshot(
+				1,
+				position.leveraged_held,
+				position.leveraged_debits,
+				FixedI128::saturating_from_integer(0),
+				FixedI128::saturating_from_integer(0),
+			);
+			PositionsSnapshots::insert(MOCK_POOL, EUR_USD_PAIR, snapshot);
+			assert_eq!(
+				MarginProtocol::margin_level(&ALICE, MOCK_POOL),
+				Ok(FixedI128::saturating_from_integer(1))
+			);
+
+			MockPrices::set_mock_price(CurrencyId::FEUR, Some(Price::saturating_from_rational(3, 100)));
+			assert_ok!(MarginProtocol::trader_stop_out(Origin::none(), ALICE, MOCK_POOL));
+
+			// collateral seized by pool
+			assert_eq!(MarginProtocol::collaterals(ALICE, (MOCK_POOL, CurrencyId::FJPY)), 0);
+			assert_eq!(
+				MarginProtocol::pool_collaterals(MOCK_POOL, CurrencyId::FJPY),
+				balance_saturating_from_integer_currency_cent(100)
+			);
+			assert_eq!(Currencies::free_balance(CurrencyId::FJPY, &MOCK_POOL_OWNER), 0);
+			let event = TestEvent::margin_protocol(RawEvent::CollateralSeized(
+				ALICE,
+				MOCK_POOL,
+				CurrencyId::FJPY,
+				balance_saturating_from_integer_currency_cent(100),
+				balance_saturating_from_integer_currency_cent(100),
+			));
+			assert!(System::events().iter().any(|record| record.event == event));
+
+			// loss realized by seized value
+			assert!(MarginProtocol::positions(0).is_none());
+			assert_eq!(
+				MarginProtocol::balances(ALICE, MOCK_POOL),
+				fixedi128_saturating_from_integer_currency_cent(3)
+			);
+			assert_eq!(
+				MockLiquidityPools::liquidity(MOCK_POOL),
+				balance_saturating_from_integer_currency_cent(197)
+			);
+		});
+}
+
+#[test]
+fn unpriced_collateral_is_valued_at_zero() {
+	ExtBuilder::default()
+		.alice_currency_balance(CurrencyId::FEUR, 1000)
+		.price(CurrencyId::FEUR, (12, 10))
+		.build()
+		.execute_with(|| {
+			set_collateral_haircut(CurrencyId::FEUR, 10);
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL, FixedI128::from_inner(100));
+			assert_ok!(MarginProtocol::deposit_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FEUR,
+				1000
+			));
+			assert_eq!(
+				MarginProtocol::equity_of_trader(&ALICE, MOCK_POOL),
+				Ok(FixedI128::from_inner(1180))
+			);
+
+			MockPrices::set_mock_price(CurrencyId::FEUR, None);
+			assert_eq!(
+				MarginProtocol::equity_of_trader(&ALICE, MOCK_POOL),
+				Ok(FixedI128::from_inner(100))
+			);
+		});
+}
+
+#[test]
+fn buy_pool_collateral_works() {
+	ExtBuilder::default()
+		.pool_liquidity(MOCK_POOL, 1080)
+		.alice_currency_balance(CurrencyId::AUSD, 480)
+		.alice_currency_balance(CurrencyId::FEUR, 1000)
+		// EUR/USD = 1.2
+		.price(CurrencyId::FEUR, (12, 10))
+		.build()
+		.execute_with(|| {
+			set_collateral_haircut(CurrencyId::FEUR, 10);
+			assert_ok!(MarginProtocol::deposit_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FEUR,
+				1000
+			));
+			assert_ok!(MarginProtocol::seize_collaterals(&ALICE, &[MOCK_POOL]));
+			assert_eq!(MarginProtocol::pool_collaterals(MOCK_POOL, CurrencyId::FEUR), 1000);
+
+			assert_noop!(
+				MarginProtocol::buy_pool_collateral(Origin::signed(ALICE), MOCK_POOL, CurrencyId::FEUR, 1001),
+				Error::<Runtime>::InsufficientPoolCollateral
+			);
+
+			// 1000 * 1.2 * (1 - 10%) paid by pool
+			assert_eq!(MockLiquidityPools::liquidity(MOCK_POOL), 0);
+			assert_ok!(MarginProtocol::buy_pool_collateral(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				CurrencyId::FEUR,
+				400
+			));
+			// paid 400 * 1.2 to pool
+			assert_eq!(MockLiquidityPools::liquidity(MOCK_POOL), 480);
+			assert_eq!(MarginProtocol::pool_collaterals(MOCK_POOL, CurrencyId::FEUR), 600);
+			assert_eq!(Currencies::free_balance(CurrencyId::FEUR, &ALICE), 400);
+			let event = TestEvent::margin_protocol(RawEvent::PoolCollateralBought(
+				ALICE,
+				MOCK_POOL,
+				CurrencyId::FEUR,
+				400,
+				480,
+			));
+			assert!(System::events().iter().any(|record| record.event == event));
+		});
+}
+
+#[test]
+fn margin_call_and_stop_out_prices_of_position_works() {
+	ExtBuilder::default()
+		.spread(Price::zero())
+		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
+		.price(CurrencyId::FEUR, (1, 1))
+		.build()
+		.execute_with(|| {
+			set_trader_risk_threshold(EUR_USD_PAIR, risk_threshold(50, 20));
+			<Balances<Runtime>>::insert(ALICE, MOCK_POOL, fixedi128_saturating_from_integer_currency_cent(100));
+			<Balances<Runtime>>::insert(BOB, MOCK_POOL, fixedi128_saturating_from_integer_currency_cent(100));
+			let long: Position<Runtime> = Position {
+				owner: ALICE,
+				pool: MOCK_POOL,
+				pair: EUR_USD_PAIR,
+				leverage: Leverage::LongTwo.into(),
+				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
+				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
+				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
+				margin_held: fixedi128_saturating_from_integer_currency_cent(50),
+			};
+			let short: Position<Runtime> = Position {
+				owner: BOB,
+				leverage: Leverage::ShortTwo.into(),
+				leveraged_held: fixedi128_saturating_from_integer_currency_cent(-100),
+				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(100),
+				..long.clone()
+			};
+			<Positions<Runtime>>::insert(0, long.clone());
+			<PositionsByTrader<Runtime>>::insert(ALICE, (MOCK_POOL, 0), ());
+			<Positions<Runtime>>::insert(1, short.clone());
+			<PositionsByTrader<Runtime>>::insert(BOB, (MOCK_POOL, 1), ());
+
+			assert_eq!(MarginProtocol::positions_of_trader(&ALICE), vec![(0, long.clone())]);
+			assert_eq!(
+				MarginProtocol::open_price_of_position(&long),
+				FixedI128::saturating_from_integer(1)
+			);
+
+			// equity 1 + (price - 1) * 1 == threshold * 1
+			assert_eq!(
+				MarginProtocol::margin_call_and_stop_out_prices_of_position(&long),
+				Ok((
+					Some(FixedI128::saturating_from_rational(1, 2)),
+					Some(FixedI128::saturating_from_rational(1, 5))
+				))
+			);
+			// equity 1 + (price - 1) * -1 == threshold * 1
+			assert_eq!(
+				MarginProtocol::margin_call_and_stop_out_prices_of_position(&short),
+				Ok((
+					Some(FixedI128::saturating_from_rational(3, 2)),
+					Some(FixedI128::saturating_from_rational(9, 5))
+				))
+			);
+
+			// stop out price is reached
+			MockPrices::set_mock_price(CurrencyId::FEUR, Some(Price::saturating_from_rational(1, 5)));
+			assert_eq!(
+				MarginProtocol::margin_level(&ALICE, MOCK_POOL),
+				Ok(FixedI128::saturating_from_rational(1, 5))
+			);
+		});
+}
+
+#[test]
+fn simulate_open_and_close_position_works() {
+	ExtBuilder::default()
+		// USD/JPY = 107
+		.price(CurrencyId::FJPY, (1, 107))
+		// EUR/JPY = 140.9 => EUR/USD = 140.9/107
+		.price(CurrencyId::FEUR, (1409, 1070))
+		.accumulated_swap_rate(EUR_JPY_PAIR, FixedI128::saturating_from_integer(1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(100_000_00))
+		.build()
+		.execute_with(|| {
+			<Balances<Runtime>>::insert(
+				ALICE,
+				MOCK_POOL,
+				fixedi128_saturating_from_integer_currency_cent(10_000_00),
+			);
+			let simulation = MarginProtocol::simulate_open_position(
+				&ALICE,
+				MOCK_POOL,
+				EUR_JPY_PAIR,
+				Leverage::LongTwenty.into(),
+				balance_saturating_from_integer_currency_cent(100_000_00),
+				Price::saturating_from_integer(142),
+			)
+			.unwrap();
+			assert_eq!(
+				simulation.execution_price,
+				FixedI128::from_inner(141_040900000000007325)
+			);
+			assert_eq!(simulation.margin_held, FixedI128::from_inner(6590_696261682242990228));
+
+			// no storage change
+			assert_eq!(MarginProtocol::next_position_id(), 0);
+			assert_eq!(MarginProtocol::positions(0), None);
+			assert!(System::events().is_empty());
+
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_JPY_PAIR,
+				Leverage::LongTwenty.into(),
+				balance_saturating_from_integer_currency_cent(100_000_00),
+				Price::saturating_from_integer(142)
+			));
+			assert_eq!(
+				MarginProtocol::margin_level(&ALICE, MOCK_POOL),
+				Ok(simulation.margin_level)
+			);
+			assert_eq!(
+				MarginProtocol::enp_and_ell(MOCK_POOL),
+				Some((simulation.enp, simulation.ell))
+			);
+
+			assert_eq!(
+				MarginProtocol::simulate_close_position(&BOB, 0, Price::zero()),
+				Err(Error::<Runtime>::PositionNotOpenedByTrader.into())
+			);
+			let simulation = MarginProtocol::simulate_close_position(&ALICE, 0, Price::zero()).unwrap();
+			assert_eq!(
+				Ok(simulation.execution_price),
+				MarginProtocol::bid_price(
+					MOCK_POOL,
+					EUR_JPY_PAIR,
+					MarginProtocol::leveraged_amount_of_position(&MarginProtocol::positions(0).unwrap()),
+					None
+				)
+			);
+			assert_eq!(simulation.margin_held, FixedI128::from_inner(6590_696261682242990228));
+			assert!(MarginProtocol::positions(0).is_some());
+
+			assert_ok!(MarginProtocol::close_position(Origin::signed(ALICE), 0, Price::zero()));
+			assert_eq!(
+				MarginProtocol::margin_level(&ALICE, MOCK_POOL),
+				Ok(simulation.margin_level)
+			);
+			assert_eq!(
+				MarginProtocol::enp_and_ell(MOCK_POOL),
+				Some((simulation.enp, simulation.ell))
+			);
+		});
+}
+
+#[test]
+fn simulate_open_position_returns_error() {
+	ExtBuilder::default()
+		.price(CurrencyId::FJPY, (1, 1))
+		.price(CurrencyId::FEUR, (2, 1))
+		.accumulated_swap_rate(EUR_JPY_PAIR, FixedI128::saturating_from_integer(1))
+		.pool_liquidity(MOCK_POOL, Balance::max_value())
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				MarginProtocol::simulate_open_position(
+					&ALICE,
+					MOCK_POOL,
+					EUR_JPY_PAIR,
+					Leverage::LongTwo.into(),
+					1,
+					Price::saturating_from_integer(142)
+				),
+				Err(Error::<Runtime>::InsufficientFreeMargin.into())
+			);
+		});
+}
+
+#[test]
+fn pool_exposure_works() {
+	ExtBuilder::default()
+		// USD/JPY = 107
+		.price(CurrencyId::FJPY, (1, 107))
+		// EUR/JPY = 140.9 => EUR/USD = 140.9/107
+		.price(CurrencyId::FEUR, (1409, 1070))
+		.accumulated_swap_rate(EUR_JPY_PAIR, FixedI128::saturating_from_integer(1))
+		.pool_liquidity(MOCK_POOL, balance_saturating_from_integer_currency_cent(100_000_00))
+		.build()
+		.execute_with(|| {
+			assert_eq!(MarginProtocol::pool_exposure(MOCK_POOL), Ok(vec![]));
+
+			<Balances<Runtime>>::insert(
+				ALICE,
+				MOCK_POOL,
+				fixedi128_saturating_from_integer_currency_cent(10_000_00),
+			);
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_JPY_PAIR,
+				Leverage::LongTwenty.into(),
+				balance_saturating_from_integer_currency_cent(100_000_00),
+				Price::saturating_from_integer(142)
+			));
+			MockLiquidityPools::set_mock_accumulated_swap_rate(
+				EUR_JPY_PAIR,
+				FixedI128::saturating_from_rational(101, 100),
+			);
+			assert_ok!(MarginProtocol::open_position(
+				Origin::signed(ALICE),
+				MOCK_POOL,
+				EUR_JPY_PAIR,
+				Leverage::ShortTen.into(),
+				balance_saturating_from_integer_currency_cent(20_000_00),
+				Price::saturating_from_integer(139)
+			));
+			MockLiquidityPools::set_mock_accumulated_swap_rate(
+				EUR_JPY_PAIR,
+				FixedI128::saturating_from_rational(103, 100),
+			);
+
+			let long = MarginProtocol::positions(0).unwrap();
+			let short = MarginProtocol::positions(1).unwrap();
+			let (net_position, longest_leg) = MarginProtocol::net_position_and_longest_leg(MOCK_POOL, None).unwrap();
+			let exposure = MarginProtocol::pool_exposure(MOCK_POOL).unwrap();
+			assert_eq!(exposure.len(), 1);
+			assert_eq!(exposure[0].pair, EUR_JPY_PAIR);
+			assert_eq!(exposure[0].positions_count, 2);
+			assert_eq!(exposure[0].net_position, net_position);
+			assert_eq!(exposure[0].longest_leg, longest_leg);
+			assert_eq!(
+				Ok(exposure[0].unrealized_pl),
+				MarginProtocol::unrealized_pl_of_pool(MOCK_POOL)
+			);
+			assert_eq!(
+				exposure[0].accumulated_swap_rate,
+				MarginProtocol::accumulated_swap_rate_of_position(&long)
+					.unwrap()
+					.saturating_add(MarginProtocol::accumulated_swap_rate_of_position(&short).unwrap())
+			);
+
+			assert_ok!(MarginProtocol::close_position(Origin::signed(ALICE), 0, Price::zero()));
+			assert_ok!(MarginProtocol::close_position(
+				Origin::signed(ALICE),
+				1,
+				Price::saturating_from_integer(1000)
+			));
+			let exposure = MarginProtocol::pool_exposure(MOCK_POOL).unwrap();
+			assert_eq!(exposure[0].positions_count, 0);
+			assert_eq!(exposure[0].net_position, FixedI128::zero());
+			assert_eq!(exposure[0].accumulated_swap_rate, FixedI128::zero());
+		});
+}
+
+#[test]
+fn offchain_worker_should_work() {
+	let mut ext = ExtBuilder::default()
 		.spread(Price::zero())
 		.accumulated_swap_rate(EUR_USD_PAIR, FixedI128::saturating_from_integer(1))
 		.price(CurrencyId::FEUR, (1, 1))
@@ -2071,7 +3252,7 @@ fn offchain_worker_should_work() {
 			Origin::signed(ALICE),
 			MOCK_POOL,
 			EUR_USD_PAIR,
-			Leverage::LongTwenty,
+			Leverage::LongTwenty.into(),
 			balance_saturating_from_integer_currency_cent(200_00),
 			Price::saturating_from_integer(100)
 		));
@@ -2240,7 +3421,7 @@ fn liquidity_pool_manager_get_required_deposit_works() {
 				owner: ALICE,
 				pool: MOCK_POOL,
 				pair: EUR_USD_PAIR,
-				leverage: Leverage::LongTwo,
+				leverage: Leverage::LongTwo.into(),
 				leveraged_held: fixedi128_saturating_from_integer_currency_cent(100),
 				leveraged_debits: fixedi128_saturating_from_integer_currency_cent(-100),
 				open_accumulated_swap_rate: FixedI128::saturating_from_integer(1),
@@ -2325,7 +3506,7 @@ fn trader_open_positions_limit() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_USD_PAIR,
-					Leverage::LongTen,
+					Leverage::LongTen.into(),
 					balance_saturating_from_integer_currency_cent(10_00),
 					Price::saturating_from_integer(100)
 				),
@@ -2366,7 +3547,7 @@ fn pool_open_positions_limit() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_USD_PAIR,
-					Leverage::LongTen,
+					Leverage::LongTen.into(),
 					balance_saturating_from_integer_currency_cent(1_00),
 					Price::saturating_from_integer(100),
 				);
@@ -2391,7 +3572,7 @@ fn pool_open_positions_limit() {
 					Origin::signed(ALICE),
 					MOCK_POOL,
 					EUR_USD_PAIR,
-					Leverage::LongTen,
+					Leverage::LongTen.into(),
 					balance_saturating_from_integer_currency_cent(10_00),
 					Price::saturating_from_integer(100)
 				),
@@ -2698,3 +3879,49 @@ fn risk_threshold_of_trader_is_per_pool() {
 			);
 		});
 }
+
+#[test]
+fn migrate_to_v2_recomputes_positions_snapshots() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::put(Releases::V1_0_0);
+
+		let long = eur_usd_long_1();
+		let short = eur_usd_short_1();
+		<Positions<Runtime>>::insert(0, long.clone());
+		<Positions<Runtime>>::insert(1, short.clone());
+		PositionsByPool::insert(MOCK_POOL, (EUR_USD_PAIR, 0), ());
+		PositionsByPool::insert(MOCK_POOL, (EUR_USD_PAIR, 1), ());
+
+		// snapshots in `V1_0_0` layout, without swap debits
+		let zero = FixedI128::zero();
+		storage::unhashed::put(
+			&PositionsSnapshots::hashed_key_for(MOCK_POOL, EUR_USD_PAIR),
+			&(2u64, (long.leveraged_held, long.leveraged_debits), (zero, zero)),
+		);
+		storage::unhashed::put(
+			&PositionsSnapshots::hashed_key_for(MOCK_POOL_1, EUR_USD_PAIR),
+			&(0u64, (zero, zero), (zero, zero)),
+		);
+
+		<MarginProtocol as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+		assert_eq!(MarginProtocol::storage_version(), Releases::V2_0_0);
+		assert_eq!(
+			MarginProtocol::pool_positions_snapshots(MOCK_POOL, EUR_USD_PAIR),
+			PositionsSnapshot {
+				positions_count: 2,
+				long: LeveragedAmounts {
+					held: long.leveraged_held,
+					debits: long.leveraged_debits,
+					swap_debits: MarginProtocol::swap_debits_of_position(&long).unwrap(),
+				},
+				short: LeveragedAmounts {
+					held: short.leveraged_held,
+					debits: short.leveraged_debits,
+					swap_debits: MarginProtocol::swap_debits_of_position(&short).unwrap(),
+				},
+			}
+		);
+		assert!(!PositionsSnapshots::contains_key(MOCK_POOL_1, EUR_USD_PAIR));
+	});
+}
@@ -7,7 +7,9 @@
 #![allow(clippy::unnecessary_mut_passed)]
 
 use codec::{Codec, Decode, Encode};
-use laminar_primitives::{CurrencyId, LiquidityPoolId};
+use laminar_primitives::{
+	Balance, BlockNumber, CurrencyId, LiquidityLock, LiquidityPoolId, PendingWithdrawal, WithdrawalId,
+};
 use sp_arithmetic::FixedU128;
 use sp_core::RuntimeDebug;
 use sp_std::prelude::*;
@@ -27,5 +29,9 @@ sp_api::decl_runtime_apis! {
 		AccountId: Codec,
 	{
 		fn pool_state(pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Option<SyntheticPoolState>;
+		fn pool_pending_withdrawals(
+			pool_id: LiquidityPoolId,
+		) -> Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)>;
+		fn pool_liquidity_lock(pool_id: LiquidityPoolId) -> Option<LiquidityLock<BlockNumber>>;
 	}
 }
@@ -1,7 +1,9 @@
 use codec::Codec;
-use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_core::{futures::future, BoxFuture, Error as RpcError, ErrorCode};
 use jsonrpc_derive::rpc;
-use laminar_primitives::{CurrencyId, LiquidityPoolId};
+use laminar_primitives::{
+	Balance, BlockNumber, CurrencyId, LiquidityLock, LiquidityPoolId, PendingWithdrawal, WithdrawalId,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
@@ -16,7 +18,21 @@ pub trait SyntheticProtocolApi<BlockHash, AccountId> {
 		pool_id: LiquidityPoolId,
 		currency_id: CurrencyId,
 		at: Option<BlockHash>,
-	) -> Result<Option<SyntheticPoolState>>;
+	) -> BoxFuture<Option<SyntheticPoolState>>;
+
+	#[rpc(name = "synthetic_poolPendingWithdrawals")]
+	fn pool_pending_withdrawals(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<BlockHash>,
+	) -> BoxFuture<Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)>>;
+
+	#[rpc(name = "synthetic_poolLiquidityLock")]
+	fn pool_liquidity_lock(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<BlockHash>,
+	) -> BoxFuture<Option<LiquidityLock<BlockNumber>>>;
 }
 
 /// A struct that implements the [`SyntheticProtocolApi`].
@@ -59,15 +75,53 @@ where
 		pool_id: LiquidityPoolId,
 		currency_id: CurrencyId,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<Option<SyntheticPoolState>> {
+	) -> BoxFuture<Option<SyntheticPoolState>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pool_state(&at, pool_id, currency_id).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pool state.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
+	}
+
+	fn pool_pending_withdrawals(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		Box::new(future::result(api.pool_pending_withdrawals(&at, pool_id).map_err(
+			|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pool pending withdrawals.".into(),
+				data: Some(format!("{:?}", e).into()),
+			},
+		)))
+	}
+
+	fn pool_liquidity_lock(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> BoxFuture<Option<LiquidityLock<BlockNumber>>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash));
-		api.pool_state(&at, pool_id, currency_id).map_err(|e| RpcError {
-			code: ErrorCode::ServerError(Error::RuntimeError.into()),
-			message: "Unable to get pool state.".into(),
-			data: Some(format!("{:?}", e).into()),
-		})
+		Box::new(future::result(api.pool_liquidity_lock(&at, pool_id).map_err(|e| {
+			RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get pool liquidity lock.".into(),
+				data: Some(format!("{:?}", e).into()),
+			}
+		})))
 	}
 }
@@ -310,6 +310,7 @@ impl<T: Config> Module<T> {
 			.map_err(|_| Error::<T>::InsufficientLockedCollateral)?;
 
 		<SyntheticTokens<T>>::remove_position(pool_id, currency_id, collateral_position_delta, synthetic);
+		T::LiquidityPools::settle_wind_down(pool_id);
 
 		Ok(redeemed_collateral)
 	}
@@ -347,6 +348,7 @@ impl<T: Config> Module<T> {
 			.map_err(|_| Error::<T>::InsufficientLockedCollateral)?;
 
 		<SyntheticTokens<T>>::remove_position(pool_id, currency_id, collateral_position_delta, synthetic);
+		T::LiquidityPools::settle_wind_down(pool_id);
 
 		Ok(collateral)
 	}
@@ -405,8 +407,8 @@ impl<T: Config> Module<T> {
 		price: Price,
 		max_price: Price,
 	) -> result::Result<Price, DispatchError> {
-		let ask_spread =
-			T::SyntheticProtocolLiquidityPools::ask_spread(pool_id, currency_id).ok_or(Error::<T>::NoAskSpread)?;
+		let ask_spread = T::SyntheticProtocolLiquidityPools::ask_spread(pool_id, currency_id, price)
+			.ok_or(Error::<T>::NoAskSpread)?;
 		let ask_price = price.checked_add(&ask_spread).ok_or(Error::<T>::NumOverflow)?;
 
 		ensure!(ask_price <= max_price, Error::<T>::AskPriceTooHigh);
@@ -423,8 +425,8 @@ impl<T: Config> Module<T> {
 		price: Price,
 		min_price: Option<Price>,
 	) -> result::Result<Price, DispatchError> {
-		let bid_spread =
-			T::SyntheticProtocolLiquidityPools::bid_spread(pool_id, currency_id).ok_or(Error::<T>::NoBidSpread)?;
+		let bid_spread = T::SyntheticProtocolLiquidityPools::bid_spread(pool_id, currency_id, price)
+			.ok_or(Error::<T>::NoBidSpread)?;
 		let bid_price = price.checked_sub(&bid_spread).expect("price > spread_amount; qed");
 
 		if let Some(min) = min_price {
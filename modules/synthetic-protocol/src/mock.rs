@@ -11,7 +11,7 @@ use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 use orml_currencies::Currency;
 use orml_traits::{parameter_type_with_key, DataProvider, DefaultPriceProvider};
 
-use laminar_primitives::LiquidityPoolId;
+use laminar_primitives::{LiquidityPoolId, PoolRole, PoolStatus};
 use module_traits::{LiquidityPools, SyntheticProtocolLiquidityPools};
 
 use super::*;
@@ -196,11 +196,29 @@ impl LiquidityPools<AccountId> for MockLiquidityPools {
 		unimplemented!()
 	}
 
+	fn pools_with_status(_status: PoolStatus) -> Vec<LiquidityPoolId> {
+		unimplemented!()
+	}
+
+	fn status(_pool_id: LiquidityPoolId) -> Option<PoolStatus> {
+		unimplemented!()
+	}
+
+	fn settle_wind_down(_pool_id: LiquidityPoolId) {}
+
 	/// ALICE is the mock owner
 	fn is_owner(_pool_id: LiquidityPoolId, who: &u32) -> bool {
 		who == &ALICE
 	}
 
+	fn has_role(pool_id: LiquidityPoolId, who: &u32, _role: PoolRole) -> bool {
+		Self::is_owner(pool_id, who)
+	}
+
+	fn owner(_pool_id: LiquidityPoolId) -> Option<AccountId> {
+		Some(ALICE)
+	}
+
 	fn pool_exists(pool_id: LiquidityPoolId) -> bool {
 		pool_id == MOCK_POOL
 	}
@@ -209,6 +227,10 @@ impl LiquidityPools<AccountId> for MockLiquidityPools {
 		CollateralCurrency::free_balance(&pool_id)
 	}
 
+	fn locked_liquidity(_pool_id: LiquidityPoolId) -> Balance {
+		unimplemented!()
+	}
+
 	fn deposit_liquidity(from: &AccountId, pool_id: LiquidityPoolId, amount: Balance) -> DispatchResult {
 		CollateralCurrency::transfer(from, &pool_id, amount).map_err(|e| e.into())
 	}
@@ -219,12 +241,12 @@ impl LiquidityPools<AccountId> for MockLiquidityPools {
 }
 
 impl SyntheticProtocolLiquidityPools<AccountId> for MockLiquidityPools {
-	fn bid_spread(_pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Option<Price> {
+	fn bid_spread(_pool_id: LiquidityPoolId, currency_id: CurrencyId, _price: Price) -> Option<Price> {
 		let price = MockPrices::prices(currency_id)?;
 		Some(Self::spread().saturating_mul(price))
 	}
 
-	fn ask_spread(_pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Option<Price> {
+	fn ask_spread(_pool_id: LiquidityPoolId, currency_id: CurrencyId, _price: Price) -> Option<Price> {
 		let price = MockPrices::prices(currency_id)?;
 		Some(Self::spread().saturating_mul(price))
 	}
@@ -1,24 +1,42 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use primitives::{Balance, CurrencyId, Leverage, LiquidityPoolId, Price, TradingPair};
+use primitives::{Balance, CurrencyId, LiquidityPoolId, PoolRole, PoolStatus, PositionLeverage, Price, TradingPair};
 use sp_arithmetic::FixedI128;
 use sp_runtime::{DispatchResult, Permill, RuntimeDebug};
 use sp_std::{prelude::*, result};
 
 /// An abstraction of liquidity pools basic functionalities.
 pub trait LiquidityPools<AccountId> {
-	/// Return all liquidity pools.
+	/// Return all liquidity pools, excluding removed ones.
 	fn all() -> Vec<LiquidityPoolId>;
 
+	/// Return liquidity pools of the given `status`.
+	fn pools_with_status(status: PoolStatus) -> Vec<LiquidityPoolId>;
+
+	/// Return status of `pool_id`, or `None` if pool not found.
+	fn status(pool_id: LiquidityPoolId) -> Option<PoolStatus>;
+
+	/// Mark `pool_id` as disabled if it's winding down and no liability left.
+	fn settle_wind_down(pool_id: LiquidityPoolId);
+
 	/// Return `true` if `who` is owner of `pool_id`.
 	fn is_owner(pool_id: LiquidityPoolId, who: &AccountId) -> bool;
 
+	/// Return `true` if `who` is owner of `pool_id`, or has been granted `role` in it.
+	fn has_role(pool_id: LiquidityPoolId, who: &AccountId, role: PoolRole) -> bool;
+
+	/// Return owner of `pool_id`, or `None` if pool not found.
+	fn owner(pool_id: LiquidityPoolId) -> Option<AccountId>;
+
 	/// Return `true` if `pool_id` exists.
 	fn pool_exists(pool_id: LiquidityPoolId) -> bool;
 
 	/// Return liquidity balance of `pool_id`.
 	fn liquidity(pool_id: LiquidityPoolId) -> Balance;
 
+	/// Return liquidity of `pool_id` locked by the pool owner.
+	fn locked_liquidity(pool_id: LiquidityPoolId) -> Balance;
+
 	/// Deposit liquidity from `source` to `pool_id` of the given amount.
 	fn deposit_liquidity(source: &AccountId, pool_id: LiquidityPoolId, amount: Balance) -> DispatchResult;
 
@@ -38,11 +56,13 @@ pub trait BaseLiquidityPoolManager<LiquidityPoolId, Balance> {
 
 /// An abstraction of liquidity pools for Synthetic Protocol.
 pub trait SyntheticProtocolLiquidityPools<AccountId>: LiquidityPools<AccountId> {
-	/// Return bid spread of `currency_id` in `pool_id`, or `None` if not set by pool owner.
-	fn bid_spread(pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Option<Price>;
+	/// Return bid spread of `currency_id` in `pool_id` at oracle `price`, or `None` if not set by
+	/// pool owner.
+	fn bid_spread(pool_id: LiquidityPoolId, currency_id: CurrencyId, price: Price) -> Option<Price>;
 
-	/// Return ask spread of `currency_id` in `pool_id`, or `None` if not set by pool owner.
-	fn ask_spread(pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Option<Price>;
+	/// Return ask spread of `currency_id` in `pool_id` at oracle `price`, or `None` if not set by
+	/// pool owner.
+	fn ask_spread(pool_id: LiquidityPoolId, currency_id: CurrencyId, price: Price) -> Option<Price>;
 
 	/// Return additional collateral ratio of `currency_id`.
 	fn additional_collateral_ratio(pool_id: LiquidityPoolId, currency_id: CurrencyId) -> Permill;
@@ -57,15 +77,32 @@ pub enum OpenPositionError {
 	TradingPairNotEnabled,
 	TradingPairNotEnabledInPool,
 	BelowMinLeveragedAmount,
+	PoolWindingDown,
+	NotEnoughLockedLiquidity,
+	BelowTradingPairMinLeveragedAmount,
+	AboveMaxLeveragedAmount,
+	PoolNotActive,
 }
 
 /// An abstraction of liquidity pools for Margin Protocol.
 pub trait MarginProtocolLiquidityPools<AccountId>: LiquidityPools<AccountId> {
-	/// Returns bid spread of `pair` in `pool_id`, or `None` if not set by pool owner.
-	fn bid_spread(pool_id: LiquidityPoolId, pair: TradingPair) -> Option<Price>;
+	/// Returns bid spread of `pair` in `pool_id` at oracle `price` for a trade of
+	/// `leveraged_amount`, or `None` if not set by pool owner.
+	fn bid_spread(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		price: Price,
+		leveraged_amount: Balance,
+	) -> Option<Price>;
 
-	/// Returns ask spread of `pair` in `pool_id`, or `None` if not set by pool owner.
-	fn ask_spread(pool_id: LiquidityPoolId, pair: TradingPair) -> Option<Price>;
+	/// Returns ask spread of `pair` in `pool_id` at oracle `price` for a trade of
+	/// `leveraged_amount`, or `None` if not set by pool owner.
+	fn ask_spread(
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		price: Price,
+		leveraged_amount: Balance,
+	) -> Option<Price>;
 
 	/// Returns swap rate of `pair` in `pool_id`.
 	fn swap_rate(pool_id: LiquidityPoolId, pair: TradingPair, is_long: bool) -> FixedI128;
@@ -77,9 +114,13 @@ pub trait MarginProtocolLiquidityPools<AccountId>: LiquidityPools<AccountId> {
 	fn ensure_can_open_position(
 		pool_id: LiquidityPoolId,
 		pair: TradingPair,
-		leverage: Leverage,
+		leverage: PositionLeverage,
 		leveraged_amount: Balance,
 	) -> result::Result<(), OpenPositionError>;
+
+	/// Return `true` if `pool_id` is winding down and its deadline has passed, so remaining positions could be
+	/// force closed.
+	fn is_wind_down_expired(pool_id: LiquidityPoolId) -> bool;
 }
 
 /// Margin protocol liquidity pools manager.
@@ -99,3 +140,16 @@ pub trait OnRemoveLiquidityPool {
 	/// Invoked when the liquiditiy pool has been removed.
 	fn on_remove(pool_id: LiquidityPoolId);
 }
+
+/// An abstraction of on-chain price volatility estimator.
+pub trait VolatilityProvider {
+	/// Return estimated volatility of `currency_id` price, relative to the price, or `None` if not
+	/// tracked.
+	fn volatility(currency_id: CurrencyId) -> Option<Price>;
+}
+
+impl VolatilityProvider for () {
+	fn volatility(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+}
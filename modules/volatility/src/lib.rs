@@ -0,0 +1,157 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure, traits::EnsureOrigin, weights::Weight};
+use laminar_primitives::{arithmetic::fixed_u128_sqrt, CurrencyId, Price};
+use module_traits::VolatilityProvider;
+use orml_traits::{DataProvider, OnNewData};
+use orml_utilities::with_transaction_result;
+use sp_runtime::{
+	traits::{Bounded, CheckedDiv, Saturating, Zero},
+	FixedPointNumber, Permill, RuntimeDebug,
+};
+
+mod default_weight;
+mod mock;
+mod tests;
+
+pub trait WeightInfo {
+	fn set_smoothing_factor() -> Weight;
+}
+
+pub trait Config: frame_system::Config {
+	/// The overarching event type.
+	type Event: From<Event> + Into<<Self as frame_system::Config>::Event>;
+
+	/// Required origin for updating volatility options.
+	type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Combined oracle prices of currencies. Read on every oracle feed, so it should have no side effects.
+	type PriceSource: DataProvider<CurrencyId, Price>;
+
+	/// Weight information for the extrinsics in this module.
+	type WeightInfo: WeightInfo;
+}
+
+/// Volatility estimate of a currency.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct VolatilityEstimate {
+	/// The last combined oracle price.
+	pub last_price: Price,
+
+	/// Exponentially weighted variance of price returns.
+	pub variance: Price,
+}
+
+decl_storage! {
+	trait Store for Module<T: Config> as Volatility {
+		/// Smoothing factor of a currency, the weight of a new price return in the exponentially
+		/// weighted variance. Volatility is only estimated for currencies with a smoothing factor.
+		pub SmoothingFactors get(fn smoothing_factor): map hasher(twox_64_concat) CurrencyId => Option<Permill>;
+
+		/// Volatility estimates of currencies.
+		pub Estimates get(fn estimate): map hasher(twox_64_concat) CurrencyId => Option<VolatilityEstimate>;
+	}
+}
+
+decl_event! {
+	pub enum Event {
+		/// Smoothing factor set: \[currency_id, smoothing_factor\]
+		SmoothingFactorSet(CurrencyId, Option<Permill>),
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Config> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		fn deposit_event() = default;
+
+		/// Set smoothing factor of `currency_id` to start estimating its volatility, or stop it if
+		/// `None`.
+		///
+		/// May only be called from `UpdateOrigin`.
+		#[weight = T::WeightInfo::set_smoothing_factor()]
+		pub fn set_smoothing_factor(origin, currency_id: CurrencyId, smoothing_factor: Option<Permill>) {
+			with_transaction_result(|| {
+				T::UpdateOrigin::ensure_origin(origin)?;
+				match smoothing_factor {
+					Some(factor) => {
+						ensure!(!factor.is_zero(), Error::<T>::InvalidSmoothingFactor);
+						SmoothingFactors::insert(currency_id, factor);
+					}
+					None => {
+						SmoothingFactors::remove(currency_id);
+						Estimates::remove(currency_id);
+					}
+				}
+				Self::deposit_event(Event::SmoothingFactorSet(currency_id, smoothing_factor));
+				Ok(())
+			})?;
+		}
+	}
+}
+
+decl_error! {
+	/// Errors for the volatility module.
+	pub enum Error for Module<T: Config> {
+		/// Smoothing factor must not be zero.
+		InvalidSmoothingFactor,
+	}
+}
+
+impl<T: Config> Module<T> {
+	/// Update volatility estimate of `currency_id` with the combined oracle `price`. Returns are
+	/// only sampled when the combined price changes, so stale prices don't dilute the variance.
+	///
+	/// variance = (1 - smoothing_factor) * variance + smoothing_factor * return^2
+	/// return = abs(price - last_price) / last_price
+	fn update_estimate(currency_id: CurrencyId, smoothing_factor: Permill, price: Price) {
+		let smoothing_factor = Price::from(smoothing_factor);
+		let variance = match Self::estimate(currency_id) {
+			Some(estimate) if estimate.last_price == price => return,
+			Some(estimate) if !estimate.last_price.is_zero() => {
+				let delta = price.max(estimate.last_price) - price.min(estimate.last_price);
+				let price_return = delta
+					.checked_div(&estimate.last_price)
+					.unwrap_or_else(Bounded::max_value);
+				estimate
+					.variance
+					.saturating_mul(Price::one().saturating_sub(smoothing_factor))
+					.saturating_add(
+						price_return
+							.saturating_mul(price_return)
+							.saturating_mul(smoothing_factor),
+					)
+			}
+			_ => Zero::zero(),
+		};
+
+		Estimates::insert(
+			currency_id,
+			VolatilityEstimate {
+				last_price: price,
+				variance,
+			},
+		);
+	}
+}
+
+impl<T: Config> VolatilityProvider for Module<T> {
+	/// volatility = sqrt(variance)
+	fn volatility(currency_id: CurrencyId) -> Option<Price> {
+		Self::estimate(currency_id).map(|e| fixed_u128_sqrt(e.variance))
+	}
+}
+
+/// Oracle feeds of currencies with a smoothing factor trigger a volatility estimate update. The
+/// combined price is sampled instead of the fed value, so a single operator can't skew the estimate.
+impl<T: Config> OnNewData<T::AccountId, CurrencyId, Price> for Module<T> {
+	fn on_new_data(_who: &T::AccountId, key: &CurrencyId, _value: &Price) {
+		if let Some(smoothing_factor) = Self::smoothing_factor(key) {
+			if let Some(price) = T::PriceSource::get(key) {
+				Self::update_estimate(*key, smoothing_factor, price);
+			}
+		}
+	}
+}
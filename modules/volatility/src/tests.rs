@@ -0,0 +1,117 @@
+//! Unit tests for the volatility module.
+
+#![cfg(test)]
+
+use super::*;
+use mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::BadOrigin, FixedU128, Permill};
+
+fn feed(price: FixedU128) {
+	MockPrices::set_price(CurrencyId::FEUR, price);
+	Volatility::on_new_data(&0, &CurrencyId::FEUR, &price);
+}
+
+#[test]
+fn set_smoothing_factor_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let factor = Permill::from_percent(10);
+		assert_noop!(
+			Volatility::set_smoothing_factor(bob(), CurrencyId::FEUR, Some(factor)),
+			BadOrigin
+		);
+		assert_noop!(
+			Volatility::set_smoothing_factor(alice(), CurrencyId::FEUR, Some(Permill::zero())),
+			Error::<Runtime>::InvalidSmoothingFactor
+		);
+
+		assert_ok!(Volatility::set_smoothing_factor(
+			alice(),
+			CurrencyId::FEUR,
+			Some(factor)
+		));
+		assert_eq!(Volatility::smoothing_factor(CurrencyId::FEUR), Some(factor));
+
+		let event = TestEvent::volatility(Event::SmoothingFactorSet(CurrencyId::FEUR, Some(factor)));
+		assert!(System::events().iter().any(|record| record.event == event));
+	});
+}
+
+#[test]
+fn should_estimate_volatility_from_oracle_prices() {
+	ExtBuilder::default().build().execute_with(|| {
+		// not tracked without smoothing factor
+		feed(FixedU128::saturating_from_integer(100));
+		assert_eq!(Volatility::volatility(CurrencyId::FEUR), None);
+
+		assert_ok!(Volatility::set_smoothing_factor(
+			alice(),
+			CurrencyId::FEUR,
+			Some(Permill::from_percent(50))
+		));
+
+		// first price has no return
+		feed(FixedU128::saturating_from_integer(100));
+		assert_eq!(Volatility::volatility(CurrencyId::FEUR), Some(FixedU128::zero()));
+
+		// return 10%, variance = 0.5 * 0.01 = 0.005
+		feed(FixedU128::saturating_from_integer(110));
+		assert_eq!(
+			Volatility::estimate(CurrencyId::FEUR),
+			Some(VolatilityEstimate {
+				last_price: FixedU128::saturating_from_integer(110),
+				variance: FixedU128::saturating_from_rational(5, 1000),
+			})
+		);
+
+		// not sampled if price not changed
+		feed(FixedU128::saturating_from_integer(110));
+		assert_eq!(
+			Volatility::estimate(CurrencyId::FEUR).map(|e| e.variance),
+			Some(FixedU128::saturating_from_rational(5, 1000))
+		);
+
+		// return 0.1%, variance = 0.5 * 0.005 + 0.5 * 0.000001 = 0.0025005
+		feed(FixedU128::saturating_from_rational(11011, 100));
+		assert_eq!(
+			Volatility::estimate(CurrencyId::FEUR).map(|e| e.variance),
+			Some(FixedU128::saturating_from_rational(25005, 10_000_000))
+		);
+
+		// stop tracking
+		assert_ok!(Volatility::set_smoothing_factor(alice(), CurrencyId::FEUR, None));
+		assert_eq!(Volatility::estimate(CurrencyId::FEUR), None);
+	});
+}
+
+#[test]
+fn should_sample_combined_price_on_new_data() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Volatility::set_smoothing_factor(
+			alice(),
+			CurrencyId::FEUR,
+			Some(Permill::from_percent(50))
+		));
+		assert_ok!(Volatility::set_smoothing_factor(
+			alice(),
+			CurrencyId::FJPY,
+			Some(Permill::from_percent(50))
+		));
+		MockPrices::set_price(CurrencyId::FEUR, FixedU128::saturating_from_integer(100));
+
+		// the fed value is ignored
+		Volatility::on_new_data(&0, &CurrencyId::FEUR, &FixedU128::saturating_from_integer(200));
+		assert_eq!(
+			Volatility::estimate(CurrencyId::FEUR),
+			Some(VolatilityEstimate {
+				last_price: FixedU128::saturating_from_integer(100),
+				variance: FixedU128::zero(),
+			})
+		);
+
+		// currencies without combined price are not estimated
+		Volatility::on_new_data(&0, &CurrencyId::FJPY, &FixedU128::saturating_from_integer(100));
+		assert_eq!(Volatility::volatility(CurrencyId::FJPY), None);
+	});
+}
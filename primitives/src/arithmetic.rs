@@ -1,4 +1,7 @@
-use sp_arithmetic::{traits::UniqueSaturatedInto, FixedI128, FixedPointNumber, FixedU128};
+use sp_arithmetic::{
+	traits::{Saturating, UniqueSaturatedInto},
+	FixedI128, FixedPointNumber, FixedU128,
+};
 
 /// Create a `FixedI128` from `FixedU128` by saturating.
 ///
@@ -32,3 +35,40 @@ pub fn u128_from_fixed_i128(f: FixedI128) -> u128 {
 
 	f.into_inner().unique_saturated_into()
 }
+
+/// Basis points in one, i.e. `100%`.
+pub const BPS_PER_UNIT: u32 = 10_000;
+
+/// Returns `bps` basis points of `price` by saturating.
+pub fn fixed_u128_from_bps(price: FixedU128, bps: u32) -> FixedU128 {
+	price.saturating_mul(FixedU128::saturating_from_rational(bps, BPS_PER_UNIT))
+}
+
+/// Integer square root, rounded down.
+fn integer_sqrt(n: u128) -> u128 {
+	if n < 2 {
+		return n;
+	}
+
+	// initial guess no less than the root
+	let mut x = 1u128 << ((128 - n.leading_zeros() + 1) / 2);
+	loop {
+		let y = (x + n / x) / 2;
+		if y >= x {
+			return x;
+		}
+		x = y;
+	}
+}
+
+/// Square root of a `FixedU128`, rounded down.
+///
+/// Note precision is lost if `f` is too large to be scaled up.
+pub fn fixed_u128_sqrt(f: FixedU128) -> FixedU128 {
+	let inner = f.into_inner();
+	// sqrt(inner / DIV) * DIV = sqrt(inner * DIV)
+	match inner.checked_mul(FixedU128::DIV) {
+		Some(scaled) => FixedU128::from_inner(integer_sqrt(scaled)),
+		None => FixedU128::from_inner(integer_sqrt(inner).saturating_mul(integer_sqrt(FixedU128::DIV))),
+	}
+}
@@ -2,7 +2,7 @@
 // Suppress warning generated from bitmask! macro.
 #![allow(clippy::transmute_ptr_to_ptr)]
 
-use codec::{Decode, Encode, Error, Input};
+use codec::{Decode, Encode, Error, Input, Output};
 use sp_runtime::{
 	generic,
 	traits::{BlakeTwo256, IdentifyAccount, Verify},
@@ -79,7 +79,7 @@ pub type LiquidityPoolId = u32;
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum CurrencyId {
-	LAMI = 0,
+	LAMI,
 	AUSD,
 	FEUR,
 	FJPY,
@@ -90,6 +90,8 @@ pub enum CurrencyId {
 	FCHF,
 	FXAU,
 	FOIL,
+	/// LP shares of a margin liquidity pool.
+	MarginLiquidityPoolShare(LiquidityPoolId),
 }
 
 pub type Price = FixedU128;
@@ -154,6 +156,22 @@ impl Decode for Leverage {
 	}
 }
 
+#[cfg(feature = "std")]
+impl Serialize for Leverage {
+	/// Serialize as the same index of `Encode`.
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_u8(u16::trailing_zeros(**self) as u8)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for Leverage {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let index = u8::deserialize(deserializer)?;
+		Leverage::decode(&mut &[index][..]).map_err(|_| serde::de::Error::custom("invalid leverage"))
+	}
+}
+
 impl Leverage {
 	pub fn is_long(&self) -> bool {
 		!self.is_short()
@@ -177,6 +195,116 @@ impl Leverage {
 	}
 }
 
+/// Side of a margin position.
+#[derive(Encode, Decode, Copy, Clone, RuntimeDebug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Side {
+	Long,
+	Short,
+}
+
+/// Index of a long `PositionLeverage` with an arbitrary multiplier, in encoding.
+pub const LEVERAGE_LONG_INDEX: u8 = 16;
+/// Index of a short `PositionLeverage` with an arbitrary multiplier, in encoding.
+pub const LEVERAGE_SHORT_INDEX: u8 = 17;
+
+/// Leverage of a margin position, with an explicit side and any multiplier.
+///
+/// The encoding is backwards compatible with `Leverage`: fixed leverages are encoded as `Leverage`
+/// indexes, others as `LEVERAGE_LONG_INDEX` or `LEVERAGE_SHORT_INDEX` followed by the multiplier.
+/// Data encoded as `Leverage` decodes as `PositionLeverage` without migration. Likewise, the
+/// serialized `Leverage` index deserializes as `PositionLeverage`.
+#[derive(Copy, Clone, RuntimeDebug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize))]
+pub struct PositionLeverage {
+	/// Long or short.
+	pub side: Side,
+
+	/// The leverage multiplier.
+	pub multiplier: u8,
+}
+
+impl PositionLeverage {
+	pub fn new(side: Side, multiplier: u8) -> Self {
+		PositionLeverage { side, multiplier }
+	}
+
+	pub fn is_long(&self) -> bool {
+		self.side == Side::Long
+	}
+
+	pub fn is_short(&self) -> bool {
+		self.side == Side::Short
+	}
+
+	pub fn value(&self) -> u8 {
+		self.multiplier
+	}
+
+	/// The fixed `Leverage` of the same side and value, if any.
+	pub fn as_leverage(&self) -> Option<Leverage> {
+		(0u8..16)
+			.filter_map(|index| Leverage::decode(&mut &[index][..]).ok())
+			.find(|l| l.is_long() == self.is_long() && l.value() == self.multiplier)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for PositionLeverage {
+	/// Deserialize from either the struct form or the index of a fixed `Leverage`.
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Repr {
+			Fixed(Leverage),
+			Position { side: Side, multiplier: u8 },
+		}
+
+		match Repr::deserialize(deserializer)? {
+			Repr::Fixed(leverage) => Ok(leverage.into()),
+			Repr::Position { side, multiplier } => Ok(PositionLeverage::new(side, multiplier)),
+		}
+	}
+}
+
+impl From<Leverage> for PositionLeverage {
+	fn from(leverage: Leverage) -> Self {
+		let side = if leverage.is_long() { Side::Long } else { Side::Short };
+		PositionLeverage::new(side, leverage.value())
+	}
+}
+
+impl Encode for PositionLeverage {
+	fn size_hint(&self) -> usize {
+		2
+	}
+
+	fn encode_to<T: Output>(&self, dest: &mut T) {
+		match self.as_leverage() {
+			Some(leverage) => dest.write(&leverage.encode()),
+			None => {
+				let index = if self.is_long() {
+					LEVERAGE_LONG_INDEX
+				} else {
+					LEVERAGE_SHORT_INDEX
+				};
+				dest.push_byte(index);
+				dest.push_byte(self.multiplier);
+			}
+		}
+	}
+}
+
+impl Decode for PositionLeverage {
+	fn decode<I: Input>(value: &mut I) -> Result<Self, Error> {
+		match value.read_byte()? {
+			LEVERAGE_LONG_INDEX => Ok(PositionLeverage::new(Side::Long, value.read_byte()?)),
+			LEVERAGE_SHORT_INDEX => Ok(PositionLeverage::new(Side::Short, value.read_byte()?)),
+			index => Leverage::decode(&mut &[index][..]).map(Into::into),
+		}
+	}
+}
+
 #[cfg(not(feature = "std"))]
 impl core::fmt::Debug for Leverages {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -237,6 +365,136 @@ pub struct IdentityInfo {
 	pub image_url: Vec<u8>,
 }
 
+/// Liquidity pool withdrawal ID.
+pub type WithdrawalId = u64;
+
+/// Pending liquidity pool withdrawal, claimable after the withdrawal notice period.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PendingWithdrawal<AccountId, BlockNumber> {
+	/// The account that requested the withdrawal.
+	pub who: AccountId,
+
+	/// LP shares to redeem.
+	pub shares: Share,
+
+	/// The block number from which the withdrawal can be claimed.
+	pub claimable_at: BlockNumber,
+}
+
+/// Liquidity locked in a pool by the pool owner, which cannot be withdrawn until expired.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LiquidityLock<BlockNumber> {
+	/// Locked liquidity amount.
+	pub amount: Balance,
+
+	/// The block number the lock expires at.
+	pub until: BlockNumber,
+}
+
+/// Liquidity pool role, granted by the pool owner. The pool owner has all roles.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PoolRole {
+	/// Could update spreads and trading pair configuration of the pool.
+	Operator,
+	/// Could deposit liquidity to the pool, but not withdraw.
+	Treasurer,
+}
+
+/// Liquidity pool status.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PoolStatus {
+	/// Open for new positions and mints.
+	Active,
+	/// Disabled, and no liability left.
+	Disabled,
+	/// Disabled, but there are still liabilities to be settled.
+	WindingDown,
+	/// Removed by the pool owner.
+	Removed,
+}
+
+impl PoolStatus {
+	/// Return `true` if the pool may still have liabilities.
+	pub fn is_live(&self) -> bool {
+		matches!(self, PoolStatus::Active | PoolStatus::WindingDown)
+	}
+}
+
+/// Identity verification level of liquidity pools.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VerificationLevel {
+	/// Not verified, or verification lapsed.
+	Unknown,
+	/// Identity has been checked and seems reasonable.
+	Reasonable,
+	/// Identity is known to be good.
+	KnownGood,
+}
+
+impl Default for VerificationLevel {
+	fn default() -> Self {
+		VerificationLevel::Unknown
+	}
+}
+
+/// Identity verification of liquidity pools.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Verification<BlockNumber> {
+	/// Verification level.
+	pub level: VerificationLevel,
+
+	/// The block number verification lapses at.
+	///
+	/// DEFAULT-NOTE: `None`, never lapses.
+	pub expires_at: Option<BlockNumber>,
+}
+
+/// Spread in basis points of the oracle price.
+pub type SpreadBps = u32;
+
+/// How spreads of a liquidity pool are denominated.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SpreadMode {
+	/// Absolute price difference.
+	Absolute,
+	/// Basis points of the oracle price.
+	Relative,
+}
+
+impl Default for SpreadMode {
+	fn default() -> Self {
+		SpreadMode::Absolute
+	}
+}
+
+/// Bid and ask spread of a liquidity pool, in either spread mode.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Spreads {
+	/// Absolute price difference.
+	Absolute { bid: Price, ask: Price },
+	/// Basis points of the oracle price.
+	Relative { bid: SpreadBps, ask: SpreadBps },
+}
+
+/// Spread add-on for trades of a given size or larger.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SpreadTier {
+	/// Min leveraged amount of a trade in this tier.
+	pub threshold: Balance,
+
+	/// Spread add-on in basis points of the oracle price.
+	pub add_on: SpreadBps,
+}
+
 #[derive(Clone, Encode, Decode, RuntimeDebug, Eq, PartialEq, Default)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct SwapRate {
@@ -244,6 +502,20 @@ pub struct SwapRate {
 	pub short: FixedI128,
 }
 
+/// A swap rate accumulation of a trading pair in a liquidity pool.
+#[derive(Encode, Decode, RuntimeDebug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapRateAccumulation<Moment> {
+	/// The accumulation time.
+	pub time: Moment,
+
+	/// The swap rate applied.
+	pub rate: SwapRate,
+
+	/// The accumulated swap rate after this accumulation.
+	pub accumulated: SwapRate,
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum DataProviderId {
@@ -345,4 +617,58 @@ mod tests {
 		let all_encoded = Leverages::all().encode();
 		assert_eq!(Leverages::decode(&mut &all_encoded[..]).unwrap(), Leverages::all());
 	}
+	#[test]
+	fn position_leverage_encoding_should_be_backwards_compatible() {
+		let mut all = LONGS.clone().to_vec();
+		all.extend_from_slice(&SHORTS);
+		for leverage in all {
+			let position_leverage = PositionLeverage::from(leverage);
+			assert_eq!(position_leverage.as_leverage(), Some(leverage));
+			assert_eq!(position_leverage.encode(), leverage.encode());
+			assert_eq!(
+				PositionLeverage::decode(&mut &leverage.encode()[..]).unwrap(),
+				position_leverage
+			);
+		}
+
+		let long_seven = PositionLeverage::new(Side::Long, 7);
+		assert_eq!(long_seven.as_leverage(), None);
+		assert_eq!(long_seven.encode(), vec![LEVERAGE_LONG_INDEX, 7]);
+		assert_eq!(
+			PositionLeverage::decode(&mut &[LEVERAGE_LONG_INDEX, 7][..]).unwrap(),
+			long_seven
+		);
+
+		let short_fifteen = PositionLeverage::new(Side::Short, 15);
+		assert_eq!(
+			PositionLeverage::decode(&mut &short_fifteen.encode()[..]).unwrap(),
+			short_fifteen
+		);
+		assert!(short_fifteen.is_short());
+		assert_eq!(short_fifteen.value(), 15);
+
+		assert!(PositionLeverage::decode(&mut &[18][..]).is_err());
+	}
+
+	#[test]
+	fn position_leverage_should_deserialize_from_leverage_index() {
+		use serde::de::{
+			value::{Error as ValueError, U8Deserializer},
+			IntoDeserializer,
+		};
+
+		let mut all = LONGS.clone().to_vec();
+		all.extend_from_slice(&SHORTS);
+		for leverage in all {
+			let index = leverage.encode()[0];
+			let deserializer: U8Deserializer<ValueError> = index.into_deserializer();
+			assert_eq!(
+				PositionLeverage::deserialize(deserializer).unwrap(),
+				PositionLeverage::from(leverage)
+			);
+		}
+
+		let deserializer: U8Deserializer<ValueError> = 16u8.into_deserializer();
+		assert!(PositionLeverage::deserialize(deserializer).is_err());
+	}
 }
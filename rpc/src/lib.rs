@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Nonce};
-use sc_client_api::light::{Fetcher, RemoteBlockchain};
+use sc_client_api::{
+	light::{Fetcher, RemoteBlockchain},
+	BlockchainEvents,
+};
 use sc_consensus_babe::Epoch;
 use sc_finality_grandpa::{FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState};
 pub use sc_rpc::DenyUnsafe;
@@ -14,6 +17,10 @@ use sp_consensus_babe::BabeApi;
 use sp_keystore::SyncCryptoStorePtr;
 use sp_transaction_pool::TransactionPool;
 
+mod light;
+
+pub use light::{LightMarginProtocol, LightOracle, LightOracleApi, LightSyntheticProtocol};
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
 
@@ -74,6 +81,7 @@ pub fn create_full<C, P, SC, B>(deps: FullDeps<C, P, SC, B>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: BlockchainEvents<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
@@ -87,7 +95,10 @@ where
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 	B::State: sc_client_api::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
-	use margin_protocol_rpc::{MarginProtocol, MarginProtocolApi};
+	use jsonrpc_pubsub::manager::SubscriptionManager;
+	use margin_protocol_rpc::{
+		MarginProtocol, MarginProtocolApi, MarginProtocolSubscription, MarginProtocolSubscriptionApi,
+	};
 	use orml_oracle_rpc::{Oracle, OracleApi};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
 	use sc_consensus_babe_rpc::BabeRpcHandler;
@@ -137,11 +148,17 @@ where
 		shared_authority_set,
 		shared_voter_state,
 		justification_stream,
-		subscription_executor,
+		subscription_executor.clone(),
 		finality_provider,
 	)));
 	io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
 	io.extend_with(MarginProtocolApi::to_delegate(MarginProtocol::new(client.clone())));
+	io.extend_with(MarginProtocolSubscriptionApi::to_delegate(
+		MarginProtocolSubscription::new(
+			client.clone(),
+			SubscriptionManager::new(Arc::new(subscription_executor)),
+		),
+	));
 	io.extend_with(SyntheticProtocolApi::to_delegate(SyntheticProtocol::new(client)));
 
 	io
@@ -158,7 +175,9 @@ where
 	F: Fetcher<Block> + 'static,
 	P: TransactionPool + 'static,
 {
+	use margin_protocol_rpc::MarginProtocolApi;
 	use substrate_frame_rpc_system::{LightSystem, SystemApi};
+	use synthetic_protocol_rpc::SyntheticProtocolApi;
 
 	let LightDeps {
 		client,
@@ -168,11 +187,22 @@ where
 	} = deps;
 	let mut io = jsonrpc_core::IoHandler::default();
 	io.extend_with(SystemApi::<Hash, AccountId, Nonce>::to_delegate(LightSystem::new(
-		client,
-		remote_blockchain,
-		fetcher,
+		client.clone(),
+		remote_blockchain.clone(),
+		fetcher.clone(),
 		pool,
 	)));
+	io.extend_with(LightOracleApi::to_delegate(LightOracle::new(
+		client.clone(),
+		remote_blockchain.clone(),
+		fetcher.clone(),
+	)));
+	io.extend_with(MarginProtocolApi::<Hash, AccountId>::to_delegate(
+		LightMarginProtocol::new(client.clone(), remote_blockchain.clone(), fetcher.clone()),
+	));
+	io.extend_with(SyntheticProtocolApi::<Hash, AccountId>::to_delegate(
+		LightSyntheticProtocol::new(client, remote_blockchain, fetcher),
+	));
 
 	io
 }
@@ -0,0 +1,407 @@
+//! Light client implementations of laminar RPCs, which call runtime APIs on remote full nodes.
+
+use std::sync::Arc;
+
+use codec::{Codec, Decode, Encode};
+use futures::{
+	future::{ready, TryFutureExt},
+	FutureExt,
+};
+use jsonrpc_core::{BoxFuture, Error as RpcError, ErrorCode};
+use jsonrpc_derive::rpc;
+use margin_protocol_rpc::{
+	MarginPairExposure, MarginPoolInfo, MarginPoolMarket, MarginPoolState, MarginPositionState, MarginProtocolApi,
+	MarginTradeSimulation, MarginTraderState, MarginTraderStats,
+};
+use primitives::{
+	Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, LiquidityLock, LiquidityPoolId, Moment,
+	PendingWithdrawal, PositionLeverage, Price, SpreadTier, SwapRate, SwapRateAccumulation, TradingPair, WithdrawalId,
+};
+use sc_client_api::light::{future_header, Fetcher, RemoteBlockchain, RemoteCallRequest};
+use sp_blockchain::{Error as ClientError, HeaderBackend};
+use sp_runtime::generic::BlockId;
+use synthetic_protocol_rpc::{SyntheticPoolState, SyntheticProtocolApi};
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Calls runtime APIs through the `Fetcher` and `RemoteBlockchain` of a light client.
+pub struct LightRuntimeApiCaller<C, F> {
+	client: Arc<C>,
+	remote_blockchain: Arc<dyn RemoteBlockchain<Block>>,
+	fetcher: Arc<F>,
+}
+
+impl<C, F> LightRuntimeApiCaller<C, F>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+{
+	/// Create new `LightRuntimeApiCaller`.
+	pub fn new(client: Arc<C>, remote_blockchain: Arc<dyn RemoteBlockchain<Block>>, fetcher: Arc<F>) -> Self {
+		Self {
+			client,
+			remote_blockchain,
+			fetcher,
+		}
+	}
+
+	/// Call runtime API `method` with encoded `args` at block `at`, and decode the result.
+	///
+	/// If the block hash is not supplied assume the best block.
+	fn call<R: Decode + Send + 'static>(
+		&self,
+		at: Option<Hash>,
+		method: &'static str,
+		args: Vec<u8>,
+		error_message: &'static str,
+	) -> BoxFuture<R> {
+		let block = at.unwrap_or_else(|| self.client.info().best_hash);
+		let fetcher = self.fetcher.clone();
+
+		let future_result = future_header(&*self.remote_blockchain, &*self.fetcher, BlockId::hash(block))
+			.and_then(move |maybe_header| {
+				ready(maybe_header.ok_or_else(|| ClientError::UnknownBlock(format!("{}", block))))
+			})
+			.and_then(move |header| {
+				fetcher.remote_call(RemoteCallRequest {
+					block,
+					header,
+					method: method.into(),
+					call_data: args,
+					retry_count: None,
+				})
+			})
+			.and_then(move |result| {
+				ready(R::decode(&mut &result[..]).map_err(|e| ClientError::CallResultDecode(error_message, e)))
+			})
+			.map_err(move |e| RpcError {
+				code: ErrorCode::ServerError(RUNTIME_ERROR),
+				message: error_message.into(),
+				data: Some(format!("{:?}", e).into()),
+			});
+
+		Box::new(future_result.boxed().compat())
+	}
+}
+
+/// A struct that implements the [`MarginProtocolApi`] for light clients.
+pub struct LightMarginProtocol<C, F> {
+	caller: LightRuntimeApiCaller<C, F>,
+}
+
+impl<C, F> LightMarginProtocol<C, F>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+{
+	/// Create new `LightMarginProtocol`.
+	pub fn new(client: Arc<C>, remote_blockchain: Arc<dyn RemoteBlockchain<Block>>, fetcher: Arc<F>) -> Self {
+		Self {
+			caller: LightRuntimeApiCaller::new(client, remote_blockchain, fetcher),
+		}
+	}
+}
+
+impl<C, F, AccountId> MarginProtocolApi<Hash, AccountId> for LightMarginProtocol<C, F>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+	AccountId: Codec + Send + 'static,
+{
+	fn trader_state(&self, who: AccountId, pool_id: LiquidityPoolId, at: Option<Hash>) -> BoxFuture<MarginTraderState> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_trader_state",
+			(who, pool_id).encode(),
+			"Unable to get trader state.",
+		)
+	}
+
+	fn trader_stats(&self, who: AccountId, pool_id: LiquidityPoolId, at: Option<Hash>) -> BoxFuture<MarginTraderStats> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_trader_stats",
+			(who, pool_id).encode(),
+			"Unable to get trader stats.",
+		)
+	}
+
+	fn pool_state(&self, pool_id: LiquidityPoolId, at: Option<Hash>) -> BoxFuture<Option<MarginPoolState>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_pool_state",
+			pool_id.encode(),
+			"Unable to get pool state.",
+		)
+	}
+
+	fn trader_positions(&self, who: AccountId, at: Option<Hash>) -> BoxFuture<Vec<MarginPositionState>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_trader_positions",
+			who.encode(),
+			"Unable to get trader positions.",
+		)
+	}
+
+	fn simulate_open_position(
+		&self,
+		who: AccountId,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		leverage: PositionLeverage,
+		leveraged_amount: Balance,
+		price: Price,
+		at: Option<Hash>,
+	) -> BoxFuture<MarginTradeSimulation> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_simulate_open_position",
+			(who, pool_id, pair, leverage, leveraged_amount, price).encode(),
+			"Unable to simulate open position.",
+		)
+	}
+
+	fn simulate_close_position(
+		&self,
+		who: AccountId,
+		position_id: u64,
+		price: Price,
+		at: Option<Hash>,
+	) -> BoxFuture<MarginTradeSimulation> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_simulate_close_position",
+			(who, position_id, price).encode(),
+			"Unable to simulate close position.",
+		)
+	}
+
+	fn pool_exposure(&self, pool_id: LiquidityPoolId, at: Option<Hash>) -> BoxFuture<Vec<MarginPairExposure>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_pool_exposure",
+			pool_id.encode(),
+			"Unable to get pool exposure.",
+		)
+	}
+
+	fn pool_pending_withdrawals(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<Hash>,
+	) -> BoxFuture<Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_pool_pending_withdrawals",
+			pool_id.encode(),
+			"Unable to get pool pending withdrawals.",
+		)
+	}
+
+	fn pool_liquidity_lock(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<Hash>,
+	) -> BoxFuture<Option<LiquidityLock<BlockNumber>>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_pool_liquidity_lock",
+			pool_id.encode(),
+			"Unable to get pool liquidity lock.",
+		)
+	}
+
+	fn pool_spread_tiers(
+		&self,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		at: Option<Hash>,
+	) -> BoxFuture<Vec<SpreadTier>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_pool_spread_tiers",
+			(pool_id, pair).encode(),
+			"Unable to get pool spread tiers.",
+		)
+	}
+
+	fn pool_swap_rate(&self, pool_id: LiquidityPoolId, pair: TradingPair, at: Option<Hash>) -> BoxFuture<SwapRate> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_pool_swap_rate",
+			(pool_id, pair).encode(),
+			"Unable to get pool swap rate.",
+		)
+	}
+
+	fn pool_markets(&self, pool_id: LiquidityPoolId, at: Option<Hash>) -> BoxFuture<Vec<MarginPoolMarket>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_pool_markets",
+			pool_id.encode(),
+			"Unable to get pool markets.",
+		)
+	}
+
+	fn all_pools(&self, at: Option<Hash>) -> BoxFuture<Vec<MarginPoolInfo<AccountId>>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_all_pools",
+			().encode(),
+			"Unable to get all pools.",
+		)
+	}
+
+	fn swap_rate_history(
+		&self,
+		pool_id: LiquidityPoolId,
+		pair: TradingPair,
+		at: Option<Hash>,
+	) -> BoxFuture<Vec<SwapRateAccumulation<Moment>>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_swap_rate_history",
+			(pool_id, pair).encode(),
+			"Unable to get swap rate history.",
+		)
+	}
+
+	fn pools_of_trading_pair(&self, pair: TradingPair, at: Option<Hash>) -> BoxFuture<Vec<LiquidityPoolId>> {
+		self.caller.call(
+			at,
+			"MarginProtocolApi_pools_of_trading_pair",
+			pair.encode(),
+			"Unable to get pools of trading pair.",
+		)
+	}
+}
+
+/// A struct that implements the [`SyntheticProtocolApi`] for light clients.
+pub struct LightSyntheticProtocol<C, F> {
+	caller: LightRuntimeApiCaller<C, F>,
+}
+
+impl<C, F> LightSyntheticProtocol<C, F>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+{
+	/// Create new `LightSyntheticProtocol`.
+	pub fn new(client: Arc<C>, remote_blockchain: Arc<dyn RemoteBlockchain<Block>>, fetcher: Arc<F>) -> Self {
+		Self {
+			caller: LightRuntimeApiCaller::new(client, remote_blockchain, fetcher),
+		}
+	}
+}
+
+impl<C, F, AccountId> SyntheticProtocolApi<Hash, AccountId> for LightSyntheticProtocol<C, F>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+	AccountId: Decode + Send + 'static,
+{
+	fn pool_state(
+		&self,
+		pool_id: LiquidityPoolId,
+		currency_id: CurrencyId,
+		at: Option<Hash>,
+	) -> BoxFuture<Option<SyntheticPoolState>> {
+		self.caller.call(
+			at,
+			"SyntheticProtocolApi_pool_state",
+			(pool_id, currency_id).encode(),
+			"Unable to get pool state.",
+		)
+	}
+
+	fn pool_pending_withdrawals(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<Hash>,
+	) -> BoxFuture<Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)>> {
+		self.caller.call(
+			at,
+			"SyntheticProtocolApi_pool_pending_withdrawals",
+			pool_id.encode(),
+			"Unable to get pool pending withdrawals.",
+		)
+	}
+
+	fn pool_liquidity_lock(
+		&self,
+		pool_id: LiquidityPoolId,
+		at: Option<Hash>,
+	) -> BoxFuture<Option<LiquidityLock<BlockNumber>>> {
+		self.caller.call(
+			at,
+			"SyntheticProtocolApi_pool_liquidity_lock",
+			pool_id.encode(),
+			"Unable to get pool liquidity lock.",
+		)
+	}
+}
+
+/// Oracle RPC methods for light clients.
+///
+/// Same methods as `orml_oracle_rpc::OracleApi`, but resolved asynchronously as light clients
+/// need to fetch results from full nodes.
+#[rpc]
+pub trait LightOracleApi<BlockHash, ProviderId, Key, Value> {
+	#[rpc(name = "oracle_getValue")]
+	fn get_value(&self, provider_id: ProviderId, key: Key, at: Option<BlockHash>) -> BoxFuture<Option<Value>>;
+
+	#[rpc(name = "oracle_getAllValues")]
+	fn get_all_values(&self, provider_id: ProviderId, at: Option<BlockHash>) -> BoxFuture<Vec<(Key, Option<Value>)>>;
+}
+
+/// A struct that implements the [`LightOracleApi`].
+pub struct LightOracle<C, F> {
+	caller: LightRuntimeApiCaller<C, F>,
+}
+
+impl<C, F> LightOracle<C, F>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+{
+	/// Create new `LightOracle`.
+	pub fn new(client: Arc<C>, remote_blockchain: Arc<dyn RemoteBlockchain<Block>>, fetcher: Arc<F>) -> Self {
+		Self {
+			caller: LightRuntimeApiCaller::new(client, remote_blockchain, fetcher),
+		}
+	}
+}
+
+impl<C, F> LightOracleApi<Hash, DataProviderId, CurrencyId, dev_runtime::TimeStampedPrice> for LightOracle<C, F>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	F: Fetcher<Block> + 'static,
+{
+	fn get_value(
+		&self,
+		provider_id: DataProviderId,
+		key: CurrencyId,
+		at: Option<Hash>,
+	) -> BoxFuture<Option<dev_runtime::TimeStampedPrice>> {
+		self.caller.call(
+			at,
+			"OracleApi_get_value",
+			(provider_id, key).encode(),
+			"Unable to get value.",
+		)
+	}
+
+	fn get_all_values(
+		&self,
+		provider_id: DataProviderId,
+		at: Option<Hash>,
+	) -> BoxFuture<Vec<(CurrencyId, Option<dev_runtime::TimeStampedPrice>)>> {
+		self.caller.call(
+			at,
+			"OracleApi_get_all_values",
+			provider_id.encode(),
+			"Unable to get all values.",
+		)
+	}
+}
@@ -1,10 +1,14 @@
 use super::utils::{dollars, set_ausd_balance, set_balance};
-use crate::{AccountId, BaseLiquidityPoolsForMargin, BaseLiquidityPoolsMarginInstance, CurrencyId, Runtime};
+use crate::{
+	AccountId, BaseLiquidityPoolsForMargin, BaseLiquidityPoolsMarginInstance, CurrencyId,
+	LiquidityPoolMaxPendingWithdrawals, Runtime, System,
+};
 
 use frame_benchmarking::account;
+use frame_support::traits::Get;
 use frame_system::{self as frame_system, RawOrigin};
 use orml_benchmarking::runtime_benchmarks_instance;
-use primitives::IdentityInfo;
+use primitives::{IdentityInfo, PoolRole, VerificationLevel};
 use sp_runtime::DispatchError;
 use sp_std::prelude::*;
 
@@ -48,7 +52,13 @@ runtime_benchmarks_instance! {
 		set_ausd_balance(&owner, balance + dollars(1u128))?;
 
 		BaseLiquidityPoolsForMargin::deposit_liquidity(RawOrigin::Signed(owner.clone()).into(), 0, balance)?;
-	}: _(RawOrigin::Signed(owner), 0, balance - dollars(10u128)) // left 10 dollars for the existential deposit
+
+		// worst case: into the withdrawal queue with max pending withdrawals
+		BaseLiquidityPoolsForMargin::set_withdrawal_notice_period(RawOrigin::Root.into(), 10)?;
+		for _ in 1..LiquidityPoolMaxPendingWithdrawals::get() {
+			BaseLiquidityPoolsForMargin::withdraw_liquidity(RawOrigin::Signed(owner.clone()).into(), 0, dollars(1u128))?;
+		}
+	}: _(RawOrigin::Signed(owner), 0, dollars(1u128))
 
 	set_identity {
 		let owner = new_pool()?;
@@ -78,7 +88,7 @@ runtime_benchmarks_instance! {
 		set_balance(CurrencyId::LAMI, &owner, balance + dollars(1u128))?;
 
 		BaseLiquidityPoolsForMargin::set_identity(RawOrigin::Signed(owner.clone()).into(), 0, identity)?;
-	}: _(RawOrigin::Root, 0)
+	}: _(RawOrigin::Root, 0, VerificationLevel::KnownGood, None)
 
 	clear_identity {
 		let owner = new_pool()?;
@@ -100,6 +110,112 @@ runtime_benchmarks_instance! {
 		let owner = new_pool()?;
 		let to: AccountId = account("to", 0, SEED);
 	}: _(RawOrigin::Signed(owner), 0, to)
+
+	accept_liquidity_pool_transfer {
+		let owner = new_pool()?;
+		let to: AccountId = account("to", 0, SEED);
+		BaseLiquidityPoolsForMargin::transfer_liquidity_pool(RawOrigin::Signed(owner).into(), 0, to.clone())?;
+	}: _(RawOrigin::Signed(to), 0)
+
+	cancel_liquidity_pool_transfer {
+		let owner = new_pool()?;
+		let to: AccountId = account("to", 0, SEED);
+		BaseLiquidityPoolsForMargin::transfer_liquidity_pool(RawOrigin::Signed(owner.clone()).into(), 0, to)?;
+	}: _(RawOrigin::Signed(owner), 0)
+
+	deposit_for_shares {
+		let owner = new_pool()?;
+		let balance = dollars(100u128);
+		set_ausd_balance(&owner, balance + dollars(1u128))?;
+		BaseLiquidityPoolsForMargin::deposit_liquidity(RawOrigin::Signed(owner).into(), 0, balance)?;
+
+		let caller: AccountId = account("caller", 0, SEED);
+		set_ausd_balance(&caller, balance + dollars(1u128))?;
+	}: _(RawOrigin::Signed(caller), 0, balance)
+
+	redeem_shares {
+		let owner = new_pool()?;
+		let balance = dollars(100u128);
+		set_ausd_balance(&owner, balance + dollars(1u128))?;
+		BaseLiquidityPoolsForMargin::deposit_liquidity(RawOrigin::Signed(owner).into(), 0, balance)?;
+
+		let caller: AccountId = account("caller", 0, SEED);
+		set_ausd_balance(&caller, balance + dollars(1u128))?;
+		BaseLiquidityPoolsForMargin::deposit_for_shares(RawOrigin::Signed(caller.clone()).into(), 0, balance)?;
+
+		// worst case: into the withdrawal queue with max pending withdrawals
+		BaseLiquidityPoolsForMargin::set_withdrawal_notice_period(RawOrigin::Root.into(), 10)?;
+		for _ in 1..LiquidityPoolMaxPendingWithdrawals::get() {
+			BaseLiquidityPoolsForMargin::redeem_shares(RawOrigin::Signed(caller.clone()).into(), 0, dollars(1u128))?;
+		}
+	}: _(RawOrigin::Signed(caller), 0, dollars(1u128))
+
+	claim_withdrawal {
+		let owner = new_pool()?;
+		let balance = dollars(100u128);
+		set_ausd_balance(&owner, balance + dollars(1u128))?;
+		BaseLiquidityPoolsForMargin::deposit_liquidity(RawOrigin::Signed(owner).into(), 0, balance)?;
+
+		let caller: AccountId = account("caller", 0, SEED);
+		set_ausd_balance(&caller, balance + dollars(1u128))?;
+		BaseLiquidityPoolsForMargin::deposit_for_shares(RawOrigin::Signed(caller.clone()).into(), 0, balance)?;
+
+		System::set_block_number(1);
+		BaseLiquidityPoolsForMargin::set_withdrawal_notice_period(RawOrigin::Root.into(), 10)?;
+		BaseLiquidityPoolsForMargin::redeem_shares(RawOrigin::Signed(caller.clone()).into(), 0, balance)?;
+		System::set_block_number(11);
+	}: _(RawOrigin::Signed(caller), 0, 0)
+
+	cancel_withdrawal {
+		let owner = new_pool()?;
+		let balance = dollars(100u128);
+		set_ausd_balance(&owner, balance + dollars(1u128))?;
+		BaseLiquidityPoolsForMargin::deposit_liquidity(RawOrigin::Signed(owner.clone()).into(), 0, balance)?;
+
+		BaseLiquidityPoolsForMargin::set_withdrawal_notice_period(RawOrigin::Root.into(), 10)?;
+		BaseLiquidityPoolsForMargin::redeem_shares(RawOrigin::Signed(owner.clone()).into(), 0, balance)?;
+	}: _(RawOrigin::Signed(owner), 0, 0)
+
+	set_withdrawal_notice_period {
+	}: _(RawOrigin::Root, 10)
+
+	grant_role {
+		let owner = new_pool()?;
+		let operator: AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(owner), 0, operator, PoolRole::Operator)
+
+	revoke_role {
+		let owner = new_pool()?;
+		let operator: AccountId = account("operator", 0, SEED);
+		BaseLiquidityPoolsForMargin::grant_role(RawOrigin::Signed(owner.clone()).into(), 0, operator.clone(), PoolRole::Operator)?;
+	}: _(RawOrigin::Signed(owner), 0, operator)
+
+	revoke_verification {
+		let owner = new_pool()?;
+		let identity = IdentityInfo {
+			legal_name: "laminar".as_bytes().to_vec(),
+			display_name: vec![],
+			web: "https://laminar.one".as_bytes().to_vec(),
+			email: vec![],
+			image_url: vec![],
+		};
+
+		let balance = dollars(10000u128);
+		set_balance(CurrencyId::LAMI, &owner, balance + dollars(1u128))?;
+
+		BaseLiquidityPoolsForMargin::set_identity(RawOrigin::Signed(owner.clone()).into(), 0, identity)?;
+		BaseLiquidityPoolsForMargin::verify_identity(RawOrigin::Root.into(), 0, VerificationLevel::KnownGood, None)?;
+	}: _(RawOrigin::Root, 0, true)
+
+	lock_liquidity {
+		let owner = new_pool()?;
+
+		let balance = dollars(100u128);
+		set_ausd_balance(&owner, balance + dollars(1u128))?;
+		BaseLiquidityPoolsForMargin::deposit_liquidity(RawOrigin::Signed(owner.clone()).into(), 0, balance)?;
+
+		let until = System::block_number() + 100;
+	}: _(RawOrigin::Signed(owner), 0, balance, until)
 }
 
 #[cfg(test)]
@@ -176,4 +292,81 @@ mod tests {
 			assert_ok!(test_benchmark_transfer_liquidity_pool());
 		});
 	}
+
+	#[test]
+	fn accept_liquidity_pool_transfer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_accept_liquidity_pool_transfer());
+		});
+	}
+
+	#[test]
+	fn cancel_liquidity_pool_transfer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_liquidity_pool_transfer());
+		});
+	}
+
+	#[test]
+	fn deposit_for_shares() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_deposit_for_shares());
+		});
+	}
+
+	#[test]
+	fn redeem_shares() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_redeem_shares());
+		});
+	}
+
+	#[test]
+	fn claim_withdrawal() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_withdrawal());
+		});
+	}
+
+	#[test]
+	fn cancel_withdrawal() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_withdrawal());
+		});
+	}
+
+	#[test]
+	fn set_withdrawal_notice_period() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_withdrawal_notice_period());
+		});
+	}
+
+	#[test]
+	fn grant_role() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_grant_role());
+		});
+	}
+
+	#[test]
+	fn revoke_role() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_revoke_role());
+		});
+	}
+
+	#[test]
+	fn revoke_verification() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_revoke_verification());
+		});
+	}
+
+	#[test]
+	fn lock_liquidity() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_lock_liquidity());
+		});
+	}
 }
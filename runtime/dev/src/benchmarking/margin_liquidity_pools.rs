@@ -1,13 +1,14 @@
-use super::utils::dollars;
+use super::margin_protocol as margin_protocol_benchmarking;
+use super::utils::{dollars, set_price};
 use crate::{
-	AccountId, BaseLiquidityPoolsForMargin, MarginLiquidityPools, MarginProtocol, Origin, Runtime, StorageValue,
-	SyntheticCurrencyIds, System,
+	AccountId, BaseLiquidityPoolsForMargin, MarginLiquidityPools, MarginProtocol, MaxSpreadTiers, MinWindDownPeriod,
+	Origin, Runtime, StorageValue, SyntheticCurrencyIds, System,
 };
 
-use frame_support::traits::OnInitialize;
+use frame_support::traits::{Get, OnInitialize};
 use frame_system::RawOrigin;
-use margin_liquidity_pools::ONE_MINUTE;
-use sp_runtime::{DispatchError, FixedI128, Permill};
+use margin_liquidity_pools::{LeverageBounds, MarginPoolTradingPairConfig, ONE_MINUTE};
+use sp_runtime::{DispatchError, FixedI128, FixedPointNumber, Permill};
 use sp_std::prelude::*;
 
 use frame_benchmarking::account;
@@ -30,6 +31,31 @@ fn create_pool() -> Result<AccountId, DispatchError> {
 	Ok(caller)
 }
 
+/// Create a pool with an opened position, so it's winding down after disabled.
+fn create_winding_down_pool() -> Result<AccountId, DispatchError> {
+	let owner = margin_protocol_benchmarking::create_pool()?;
+
+	let trader: AccountId = account("trader", 0, SEED);
+	let balance = dollars(100u128);
+	margin_protocol_benchmarking::deposit_balance(&trader, balance)?;
+	margin_protocol_benchmarking::add_liquidity(&owner, balance)?;
+
+	margin_protocol_benchmarking::set_up_oracle();
+	set_price(vec![(CurrencyId::FEUR, Price::saturating_from_integer(1))])?;
+
+	MarginProtocol::open_position(
+		RawOrigin::Signed(trader).into(),
+		0,
+		EUR_USD,
+		Leverage::LongTwo.into(),
+		balance,
+		Price::saturating_from_integer(2),
+	)?;
+	BaseLiquidityPoolsForMargin::disable_pool(RawOrigin::Signed(owner.clone()).into(), 0)?;
+
+	Ok(owner)
+}
+
 runtime_benchmarks! {
 	{ Runtime, margin_liquidity_pools }
 
@@ -59,6 +85,28 @@ runtime_benchmarks! {
 	set_max_spread {
 	}: _(RawOrigin::Root, EUR_USD, Price::from_inner(1u128))
 
+	set_relative_spread {
+		let caller = create_pool()?;
+	}: _(RawOrigin::Signed(caller), 0, EUR_USD, 10, 10)
+
+	set_relative_max_spread {
+	}: _(RawOrigin::Root, EUR_USD, Some(100))
+
+	set_spread_tiers {
+		let t in 0 .. MaxSpreadTiers::get();
+		let caller = create_pool()?;
+		let tiers: Vec<SpreadTier> = (0..t)
+			.map(|i| SpreadTier {
+				threshold: dollars(1000u128 * (i as u128 + 1)),
+				add_on: i + 1,
+			})
+			.collect();
+	}: _(RawOrigin::Signed(caller), 0, EUR_USD, tiers)
+
+	set_volatility_factor {
+		let caller = create_pool()?;
+	}: _(RawOrigin::Signed(caller), 0, EUR_USD, Some(Price::saturating_from_rational(1, 2)))
+
 	set_accumulate_config {
 		let frequency = 60u64;
 		let offset = 1u64;
@@ -86,6 +134,66 @@ runtime_benchmarks! {
 		MarginLiquidityPools::enable_trading_pair(RawOrigin::Root.into(), EUR_USD)?;
 	}: _(RawOrigin::Signed(caller), 0, EUR_USD)
 
+	configure_pool {
+		let c in 1 .. SyntheticCurrencyIds::get().len() as u32;
+		let caller = create_pool()?;
+		let threshold = RiskThreshold {
+			margin_call: Permill::from_percent(5),
+			stop_out: Permill::from_percent(2),
+		};
+		let mut configs = vec![];
+		for currency_id in SyntheticCurrencyIds::get().into_iter().take(c as usize) {
+			let pair = TradingPair {
+				base: currency_id,
+				quote: CurrencyId::AUSD,
+			};
+			MarginProtocol::set_trading_pair_risk_threshold(
+				RawOrigin::Root.into(),
+				pair,
+				Some(threshold.clone()),
+				Some(threshold.clone()),
+				Some(threshold.clone()),
+			)?;
+			MarginLiquidityPools::enable_trading_pair(RawOrigin::Root.into(), pair)?;
+			configs.push(MarginPoolTradingPairConfig {
+				pair,
+				spread: Spreads::Absolute {
+					bid: Price::from_inner(1u128),
+					ask: Price::from_inner(1u128),
+				},
+				spread_tiers: (0..MaxSpreadTiers::get())
+					.map(|i| SpreadTier {
+						threshold: dollars(1000u128 * (i as u128 + 1)),
+						add_on: i + 1,
+					})
+					.collect(),
+				volatility_factor: Some(Price::saturating_from_rational(1, 2)),
+				enabled_trades: Leverages::all(),
+				enabled: true,
+			});
+		}
+	}: _(RawOrigin::Signed(caller), 0, configs, Some(dollars(100u128)))
+
+	set_trading_pair_min_leveraged_amount {
+		let caller = create_pool()?;
+	}: _(RawOrigin::Signed(caller), 0, EUR_USD, dollars(100u128))
+
+	set_max_leveraged_amount {
+		let caller = create_pool()?;
+	}: _(RawOrigin::Signed(caller), 0, EUR_USD, Leverage::LongFifty, Some(dollars(10_000u128)))
+
+	set_leverage_bounds {
+		let caller = create_pool()?;
+	}: _(RawOrigin::Signed(caller), 0, Some(LeverageBounds { min: 1, max: 10 }))
+
+	set_pool_swap_rate {
+		let caller = create_pool()?;
+		let adjustment = SwapRate {
+			long: FixedI128::from_inner(1.into()),
+			short: FixedI128::from_inner(1.into()),
+		};
+	}: _(RawOrigin::Signed(caller), 0, EUR_USD, adjustment)
+
 	liquidity_pool_disable_trading_pair {
 		let caller = create_pool()?;
 		MarginLiquidityPools::enable_trading_pair(RawOrigin::Root.into(), EUR_USD)?;
@@ -110,6 +218,9 @@ runtime_benchmarks! {
 	set_default_min_leveraged_amount {
 	}: _(RawOrigin::Root, dollars(100u128))
 
+	set_min_locked_liquidity_for_high_leverage {
+	}: _(RawOrigin::Root, dollars(10_000u128))
+
 	set_min_leveraged_amount {
 		let caller = create_pool()?;
 		MarginLiquidityPools::set_default_min_leveraged_amount(
@@ -119,8 +230,8 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Signed(caller), 0, 10u128.into())
 
 	on_initialize {
-		let r in 0 .. SyntheticCurrencyIds::get().len().saturating_sub(1) as u32;
-		let w in 0 .. 2;
+		let r in 1 .. SyntheticCurrencyIds::get().len() as u32;
+		let w in 0 .. 10;
 		let currency_ids = SyntheticCurrencyIds::get();
 
 		for i in 0 .. r {
@@ -130,18 +241,28 @@ runtime_benchmarks! {
 				quote: CurrencyId::AUSD,
 			};
 
-			if i < w {
+			if i == 0 {
 				MarginLiquidityPools::set_accumulate_config(Origin::root(), pair, ONE_MINUTE, 0u64)?;
 			} else {
 				// accumulate is not executed
 				MarginLiquidityPools::set_accumulate_config(Origin::root(), pair, ONE_MINUTE * 10, 0u64)?;
 			}
 		}
+		// swap rates are accumulated in each pool
+		for i in 0 .. w {
+			let owner: AccountId = account("owner", i, SEED);
+			BaseLiquidityPoolsForMargin::create_pool(RawOrigin::Signed(owner).into())?;
+		}
 		System::set_block_number(1);
 		pallet_timestamp::Now::<Runtime>::put(ONE_MINUTE * 1000); // 60_000ms
 	}: {
 		MarginLiquidityPools::on_initialize(System::block_number());
 	}
+
+	set_wind_down_deadline {
+		let caller = create_winding_down_pool()?;
+		let deadline = System::block_number() + MinWindDownPeriod::get();
+	}: _(RawOrigin::Signed(caller), 0, deadline)
 }
 
 #[cfg(test)]
@@ -246,4 +367,81 @@ mod tests {
 			assert_ok!(test_benchmark_on_initialize());
 		});
 	}
+
+	#[test]
+	fn set_wind_down_deadline() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_wind_down_deadline());
+		});
+	}
+
+	#[test]
+	fn set_min_locked_liquidity_for_high_leverage() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_min_locked_liquidity_for_high_leverage());
+		});
+	}
+
+	#[test]
+	fn set_relative_spread() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_relative_spread());
+		});
+	}
+
+	#[test]
+	fn set_relative_max_spread() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_relative_max_spread());
+		});
+	}
+
+	#[test]
+	fn set_spread_tiers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_spread_tiers());
+		});
+	}
+
+	#[test]
+	fn set_volatility_factor() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_volatility_factor());
+		});
+	}
+
+	#[test]
+	fn configure_pool() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_configure_pool());
+		});
+	}
+
+	#[test]
+	fn set_trading_pair_min_leveraged_amount() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_trading_pair_min_leveraged_amount());
+		});
+	}
+
+	#[test]
+	fn set_max_leveraged_amount() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_leveraged_amount());
+		});
+	}
+
+	#[test]
+	fn set_leverage_bounds() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_leverage_bounds());
+		});
+	}
+
+	#[test]
+	fn set_pool_swap_rate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_pool_swap_rate());
+		});
+	}
 }
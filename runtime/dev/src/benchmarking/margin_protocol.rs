@@ -1,10 +1,10 @@
-use super::utils::{dollars, lookup_of_account, set_ausd_balance, set_price};
+use super::utils::{dollars, lookup_of_account, set_ausd_balance, set_balance, set_price};
 use crate::{
-	AccountId, BaseLiquidityPoolsForMargin, LaminarOracle as Oracle, MarginLiquidityPools, MarginProtocol, Price,
-	Runtime,
+	AccountId, BaseLiquidityPoolsForMargin, LaminarOracle as Oracle, MarginLiquidityPools, MarginProtocol,
+	MarginProtocolWindDownMaxClosePositions, MinWindDownPeriod, Price, Runtime, System,
 };
 
-use frame_support::traits::ChangeMembers;
+use frame_support::traits::{ChangeMembers, Get};
 use frame_system::RawOrigin;
 use sp_runtime::{DispatchError, DispatchResult, FixedI128, FixedPointNumber, Permill};
 use sp_std::prelude::*;
@@ -22,7 +22,7 @@ const EUR_USD: TradingPair = TradingPair {
 	quote: CurrencyId::AUSD,
 };
 
-fn create_pool() -> Result<AccountId, DispatchError> {
+pub(super) fn create_pool() -> Result<AccountId, DispatchError> {
 	let owner: AccountId = account("owner", 0, SEED);
 	BaseLiquidityPoolsForMargin::create_pool(RawOrigin::Signed(owner.clone()).into())?;
 
@@ -51,18 +51,28 @@ fn create_pool() -> Result<AccountId, DispatchError> {
 	Ok(owner)
 }
 
-fn deposit_balance(who: &AccountId, balance: Balance) -> DispatchResult {
+pub(super) fn deposit_balance(who: &AccountId, balance: Balance) -> DispatchResult {
 	// extra dollar for fees
 	set_ausd_balance(&who, balance + dollars(1u128))?;
 	MarginProtocol::deposit(RawOrigin::Signed(who.clone()).into(), 0, balance)
 }
 
-fn add_liquidity(owner: &AccountId, liquidity: Balance) -> DispatchResult {
+pub(super) fn add_liquidity(owner: &AccountId, liquidity: Balance) -> DispatchResult {
 	set_ausd_balance(owner, liquidity + dollars(1u128))?;
 	BaseLiquidityPoolsForMargin::deposit_liquidity(RawOrigin::Signed(owner.clone()).into(), 0, liquidity)
 }
 
-fn set_up_oracle() {
+fn set_up_collateral() -> DispatchResult {
+	set_up_oracle();
+	set_price(vec![(CurrencyId::FEUR, Price::saturating_from_integer(1))])?;
+	MarginProtocol::set_collateral_haircut(
+		RawOrigin::Root.into(),
+		CurrencyId::FEUR,
+		Some(Permill::from_percent(10)),
+	)
+}
+
+pub(super) fn set_up_oracle() {
 	<Oracle as ChangeMembers<_>>::change_members_sorted(
 		&vec![],
 		&vec![],
@@ -109,7 +119,7 @@ runtime_benchmarks! {
 
 		set_up_oracle();
 		set_price(vec![(CurrencyId::FEUR, Price::saturating_from_integer(1))])?;
-	}: _(RawOrigin::Signed(trader), 0, EUR_USD, Leverage::LongTwo, balance, Price::saturating_from_integer(2))
+	}: _(RawOrigin::Signed(trader), 0, EUR_USD, Leverage::LongTwo.into(), balance, Price::saturating_from_integer(2))
 
 	// `open_position` when there is already ten positions in pool
 	open_position_with_ten_in_pool {
@@ -130,12 +140,12 @@ runtime_benchmarks! {
 				RawOrigin::Signed(trader.clone()).into(),
 				0,
 				EUR_USD,
-				Leverage::LongTwo,
+				Leverage::LongTwo.into(),
 				balance / 10,
 				Price::saturating_from_integer(2)
 			)?;
 		}
-	}: open_position(RawOrigin::Signed(trader), 0, EUR_USD, Leverage::LongTwo, balance, Price::saturating_from_integer(2))
+	}: open_position(RawOrigin::Signed(trader), 0, EUR_USD, Leverage::LongTwo.into(), balance, Price::saturating_from_integer(2))
 
 	close_position {
 		let pool_owner = create_pool()?;
@@ -154,7 +164,7 @@ runtime_benchmarks! {
 			RawOrigin::Signed(trader.clone()).into(),
 			0,
 			EUR_USD,
-			Leverage::LongTwo,
+			Leverage::LongTwo.into(),
 			balance,
 			Price::saturating_from_integer(2)
 		)?;
@@ -179,7 +189,7 @@ runtime_benchmarks! {
 				RawOrigin::Signed(trader.clone()).into(),
 				0,
 				EUR_USD,
-				Leverage::LongTwo,
+				Leverage::LongTwo.into(),
 				balance / 10,
 				Price::saturating_from_integer(2)
 			)?;
@@ -203,7 +213,7 @@ runtime_benchmarks! {
 			RawOrigin::Signed(trader.clone()).into(),
 			0,
 			EUR_USD,
-			Leverage::LongTwo,
+			Leverage::LongTwo.into(),
 			balance,
 			Price::saturating_from_integer(3)
 		)?;
@@ -231,7 +241,7 @@ runtime_benchmarks! {
 			RawOrigin::Signed(trader.clone()).into(),
 			0,
 			EUR_USD,
-			Leverage::LongTwo,
+			Leverage::LongTwo.into(),
 			balance,
 			Price::saturating_from_integer(3)
 		)?;
@@ -268,7 +278,7 @@ runtime_benchmarks! {
 			RawOrigin::Signed(trader.clone()).into(),
 			0,
 			EUR_USD,
-			Leverage::LongTwo,
+			Leverage::LongTwo.into(),
 			balance,
 			Price::saturating_from_integer(3)
 		)?;
@@ -297,7 +307,7 @@ runtime_benchmarks! {
 			RawOrigin::Signed(trader.clone()).into(),
 			0,
 			EUR_USD,
-			Leverage::LongTwo,
+			Leverage::LongTwo.into(),
 			balance,
 			Price::saturating_from_integer(2)
 		)?;
@@ -325,7 +335,7 @@ runtime_benchmarks! {
 			RawOrigin::Signed(trader.clone()).into(),
 			0,
 			EUR_USD,
-			Leverage::LongTwo,
+			Leverage::LongTwo.into(),
 			balance,
 			Price::saturating_from_integer(2)
 		)?;
@@ -357,7 +367,7 @@ runtime_benchmarks! {
 			RawOrigin::Signed(trader.clone()).into(),
 			0,
 			EUR_USD,
-			Leverage::LongTwo,
+			Leverage::LongTwo.into(),
 			balance,
 			Price::saturating_from_integer(2)
 		)?;
@@ -369,6 +379,41 @@ runtime_benchmarks! {
 		assert_eq!(MarginProtocol::positions_by_pool(0, (EUR_USD, 0)), None);
 	}
 
+	liquidity_pool_wind_down_close {
+		let p in 1 .. MarginProtocolWindDownMaxClosePositions::get();
+		let pool_owner = create_pool()?;
+
+		let trader: AccountId = account("trader", 0, SEED);
+		let balance = dollars(100u128);
+		deposit_balance(&trader, balance * p as u128)?;
+
+		let liquidity = balance * p as u128;
+		add_liquidity(&pool_owner, liquidity)?;
+
+		set_up_oracle();
+		set_price(vec![(CurrencyId::FEUR, Price::saturating_from_integer(1))])?;
+
+		for _ in 0..p {
+			MarginProtocol::open_position(
+				RawOrigin::Signed(trader.clone()).into(),
+				0,
+				EUR_USD,
+				Leverage::LongTwo.into(),
+				balance,
+				Price::saturating_from_integer(2)
+			)?;
+		}
+		assert_eq!(MarginProtocol::positions_by_pool(0, (EUR_USD, 0)), Some(()));
+
+		BaseLiquidityPoolsForMargin::disable_pool(RawOrigin::Signed(pool_owner.clone()).into(), 0)?;
+		let deadline = System::block_number() + MinWindDownPeriod::get();
+		MarginLiquidityPools::set_wind_down_deadline(RawOrigin::Signed(pool_owner.clone()).into(), 0, deadline)?;
+		System::set_block_number(deadline);
+	}: _(RawOrigin::None, 0)
+	verify {
+		assert_eq!(MarginProtocol::positions_by_pool(0, (EUR_USD, 0)), None);
+	}
+
 	set_trading_pair_risk_threshold {
 		let pool_owner: AccountId = account("owner", 0, SEED);
 		BaseLiquidityPoolsForMargin::create_pool(
@@ -385,6 +430,98 @@ runtime_benchmarks! {
 		assert_eq!(MarginProtocol::liquidity_pool_enp_threshold(EUR_USD), Some(threshold.clone()));
 		assert_eq!(MarginProtocol::liquidity_pool_ell_threshold(EUR_USD), Some(threshold));
 	}
+
+	transfer_margin {
+		let pool_owner = create_pool()?;
+
+		let trader: AccountId = account("trader", 0, SEED);
+		let balance = dollars(100u128);
+		deposit_balance(&trader, balance)?;
+	}: _(RawOrigin::Signed(trader.clone()), 0, 1, balance)
+	verify {
+		assert_eq!(MarginProtocol::balances(&trader, 0), FixedI128::zero());
+		assert_eq!(MarginProtocol::balances(&trader, 1), FixedI128::saturating_from_integer(100u128));
+	}
+
+	// disabling cross margin, with balance in `p` pools
+	set_cross_margin {
+		let p in 1 .. 20;
+		let pool_owner = create_pool()?;
+
+		let trader: AccountId = account("trader", 0, SEED);
+		deposit_balance(&trader, dollars(p as u128))?;
+		for pool_id in 1..p {
+			MarginProtocol::transfer_margin(RawOrigin::Signed(trader.clone()).into(), 0, pool_id, dollars(1u128))?;
+		}
+		MarginProtocol::set_cross_margin(RawOrigin::Signed(trader.clone()).into(), true, 0)?;
+	}: _(RawOrigin::Signed(trader.clone()), false, p)
+	verify {
+		assert_eq!(MarginProtocol::cross_margin_traders(&trader), None);
+	}
+
+	deposit_collateral {
+		let pool_owner = create_pool()?;
+		set_up_collateral()?;
+
+		let trader: AccountId = account("trader", 0, SEED);
+		let amount = dollars(100u128);
+		set_ausd_balance(&trader, dollars(1u128))?;
+		set_balance(CurrencyId::FEUR, &trader, amount)?;
+	}: _(RawOrigin::Signed(trader.clone()), 0, CurrencyId::FEUR, amount)
+	verify {
+		assert_eq!(MarginProtocol::collaterals(&trader, (0, CurrencyId::FEUR)), amount);
+	}
+
+	withdraw_collateral {
+		let pool_owner = create_pool()?;
+		set_up_collateral()?;
+
+		let trader: AccountId = account("trader", 0, SEED);
+		let amount = dollars(100u128);
+		set_ausd_balance(&trader, dollars(1u128))?;
+		set_balance(CurrencyId::FEUR, &trader, amount)?;
+		MarginProtocol::deposit_collateral(RawOrigin::Signed(trader.clone()).into(), 0, CurrencyId::FEUR, amount)?;
+	}: _(RawOrigin::Signed(trader.clone()), 0, CurrencyId::FEUR, amount)
+	verify {
+		assert_eq!(MarginProtocol::collaterals(&trader, (0, CurrencyId::FEUR)), 0);
+	}
+
+	set_collateral_haircut {
+	}: _(RawOrigin::Root, CurrencyId::FEUR, Some(Permill::from_percent(10)))
+	verify {
+		assert_eq!(MarginProtocol::collateral_haircuts(CurrencyId::FEUR), Some(Permill::from_percent(10)));
+	}
+
+	buy_pool_collateral {
+		let pool_owner = create_pool()?;
+		set_up_collateral()?;
+
+		let trader: AccountId = account("trader", 0, SEED);
+		let balance = dollars(100u128);
+		deposit_balance(&trader, balance)?;
+		set_balance(CurrencyId::FEUR, &trader, balance)?;
+		MarginProtocol::deposit_collateral(RawOrigin::Signed(trader.clone()).into(), 0, CurrencyId::FEUR, balance)?;
+		add_liquidity(&pool_owner, dollars(200u128))?;
+
+		set_price(vec![(CurrencyId::FEUR, Price::saturating_from_integer(2))])?;
+		MarginProtocol::open_position(
+			RawOrigin::Signed(trader.clone()).into(),
+			0,
+			EUR_USD,
+			Leverage::LongTwo.into(),
+			dollars(200u128),
+			Price::saturating_from_integer(3)
+		)?;
+		set_price(vec![(CurrencyId::FEUR, Price::saturating_from_integer(1))])?;
+		MarginProtocol::trader_stop_out(RawOrigin::None.into(), lookup_of_account(trader.clone()), 0)?;
+		assert_eq!(MarginProtocol::pool_collaterals(0, CurrencyId::FEUR), balance);
+
+		let buyer: AccountId = account("buyer", 0, SEED);
+		set_ausd_balance(&buyer, balance + dollars(1u128))?;
+	}: _(RawOrigin::Signed(buyer.clone()), 0, CurrencyId::FEUR, balance)
+	verify {
+		assert_eq!(MarginProtocol::pool_collaterals(0, CurrencyId::FEUR), 0);
+	}
 }
 
 #[cfg(test)]
@@ -491,10 +628,59 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn liquidity_pool_wind_down_close() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_liquidity_pool_wind_down_close());
+		});
+	}
+
 	#[test]
 	fn set_trading_pair_risk_threshold() {
 		new_test_ext().execute_with(|| {
 			assert_ok!(test_benchmark_set_trading_pair_risk_threshold());
 		});
 	}
+
+	#[test]
+	fn transfer_margin() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_margin());
+		});
+	}
+
+	#[test]
+	fn set_cross_margin() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_cross_margin());
+		});
+	}
+
+	#[test]
+	fn deposit_collateral() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_deposit_collateral());
+		});
+	}
+
+	#[test]
+	fn withdraw_collateral() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_withdraw_collateral());
+		});
+	}
+
+	#[test]
+	fn set_collateral_haircut() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_collateral_haircut());
+		});
+	}
+
+	#[test]
+	fn buy_pool_collateral() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_buy_pool_collateral());
+		});
+	}
 }
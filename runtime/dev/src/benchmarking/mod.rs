@@ -6,5 +6,6 @@ pub mod margin_protocol;
 pub mod synthetic_liquidity_pools;
 pub mod synthetic_protocol;
 pub mod synthetic_tokens;
+pub mod volatility;
 
 pub mod utils;
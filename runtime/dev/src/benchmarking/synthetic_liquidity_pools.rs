@@ -1,13 +1,15 @@
-use crate::{AccountId, BaseLiquidityPoolsForSynthetic, Runtime};
+use crate::{AccountId, BaseLiquidityPoolsForSynthetic, Runtime, SyntheticCurrencyIds};
 
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
-use sp_runtime::{DispatchError, Permill};
+use sp_runtime::{DispatchError, FixedPointNumber, Permill};
 use sp_std::prelude::*;
 
 use frame_benchmarking::account;
 use orml_benchmarking::runtime_benchmarks;
+use synthetic_liquidity_pools::SyntheticPoolCurrencyConfig;
 
-use primitives::{CurrencyId::*, Price};
+use primitives::{CurrencyId::*, Price, Spreads};
 
 const SEED: u32 = 0;
 
@@ -40,6 +42,36 @@ runtime_benchmarks! {
 
 	set_max_spread {
 	}: _(RawOrigin::Root, FEUR, Price::from_inner(10u128))
+
+	set_relative_spread {
+		let owner = create_pool()?;
+	}: _(RawOrigin::Signed(owner), 0, FEUR, 10, 10)
+
+	set_relative_max_spread {
+	}: _(RawOrigin::Root, FEUR, Some(100))
+
+	configure_pool {
+		let c in 1 .. SyntheticCurrencyIds::get().len() as u32;
+		let owner = create_pool()?;
+		let configs: Vec<SyntheticPoolCurrencyConfig> = SyntheticCurrencyIds::get()
+			.into_iter()
+			.take(c as usize)
+			.map(|currency_id| SyntheticPoolCurrencyConfig {
+				currency_id,
+				spread: Spreads::Absolute {
+					bid: Price::from_inner(1u128),
+					ask: Price::from_inner(1u128),
+				},
+				volatility_factor: Some(Price::saturating_from_rational(1, 2)),
+				additional_collateral_ratio: Some(Permill::from_percent(10)),
+				synthetic_enabled: true,
+			})
+			.collect();
+	}: _(RawOrigin::Signed(owner), 0, configs)
+
+	set_volatility_factor {
+		let owner = create_pool()?;
+	}: _(RawOrigin::Signed(owner), 0, FEUR, Some(Price::saturating_from_rational(1, 2)))
 }
 
 #[cfg(test)]
@@ -88,4 +120,32 @@ mod tests {
 			assert_ok!(test_benchmark_set_max_spread());
 		});
 	}
+
+	#[test]
+	fn set_relative_spread() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_relative_spread());
+		});
+	}
+
+	#[test]
+	fn set_relative_max_spread() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_relative_max_spread());
+		});
+	}
+
+	#[test]
+	fn set_volatility_factor() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_volatility_factor());
+		});
+	}
+
+	#[test]
+	fn configure_pool() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_configure_pool());
+		});
+	}
 }
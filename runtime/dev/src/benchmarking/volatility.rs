@@ -0,0 +1,34 @@
+use crate::{CurrencyId, Permill, Runtime};
+
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+runtime_benchmarks! {
+	{ Runtime, volatility }
+
+	_ {}
+
+	set_smoothing_factor {
+	}: _(RawOrigin::Root, CurrencyId::FEUR, Some(Permill::from_percent(10)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_set_smoothing_factor() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_smoothing_factor());
+		});
+	}
+}
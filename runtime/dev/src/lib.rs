@@ -26,14 +26,16 @@ use sp_core::{
 	crypto::KeyTypeId,
 	u32_trait::{_1, _2, _3, _4},
 };
-use sp_runtime::traits::{BlakeTwo256, Block as BlockT, NumberFor, OpaqueKeys, SaturatedConversion, StaticLookup};
+use sp_runtime::traits::{
+	BlakeTwo256, Block as BlockT, Convert, NumberFor, OpaqueKeys, SaturatedConversion, StaticLookup,
+};
 use sp_runtime::{
 	create_runtime_str,
 	curve::PiecewiseLinear,
 	generic, impl_opaque_keys,
 	traits::{Extrinsic, Saturating, Verify, Zero},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, DispatchResult, FixedPointNumber, ModuleId,
+	ApplyExtrinsicResult, DispatchError, DispatchResult, FixedPointNumber, ModuleId, RuntimeString,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -46,12 +48,16 @@ use orml_traits::{
 	create_median_value_data_provider, parameter_type_with_key, DataFeeder, DataProvider, DataProviderExtended,
 };
 pub use primitives::{
-	AccountId, AccountIndex, Amount, Balance, BlockNumber, CurrencyId, DataProviderId, EraIndex, Hash, LiquidityPoolId,
-	Moment, Nonce, Price, Signature,
+	AccountId, AccountIndex, Amount, Balance, BlockNumber, CurrencyId, DataProviderId, EraIndex, Hash, Leverage,
+	LiquidityLock, LiquidityPoolId, Moment, Nonce, PendingWithdrawal, PoolStatus, PositionLeverage, Price, Signature,
+	SpreadTier, SwapRate, SwapRateAccumulation, TradingPair, WithdrawalId,
 };
 pub use sp_arithmetic::FixedI128;
 
-use margin_protocol_rpc_runtime_api::{MarginPoolState, MarginTraderState};
+use margin_protocol_rpc_runtime_api::{
+	MarginLiquidationPrices, MarginPairExposure, MarginPoolInfo, MarginPoolMarket, MarginPoolState,
+	MarginPositionState, MarginTradeSimulation, MarginTraderState, MarginTraderStats,
+};
 use synthetic_protocol_rpc_runtime_api::SyntheticPoolState;
 
 // A few exports that help ease life for downstream crates.
@@ -105,10 +111,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("laminar"),
 	impl_name: create_runtime_str!("laminar"),
 	authoring_version: 1,
-	spec_version: 301,
+	spec_version: 302,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// The version infromation used to identify this runtime when compiled natively.
@@ -590,7 +596,7 @@ parameter_types! {
 type LaminarDataProvider = orml_oracle::Instance1;
 impl orml_oracle::Config<LaminarDataProvider> for Runtime {
 	type Event = Event;
-	type OnNewData = ();
+	type OnNewData = Volatility;
 	type CombineData = orml_oracle::DefaultCombineData<Runtime, MinimumCount, ExpiresIn, LaminarDataProvider>;
 	type Time = Timestamp;
 	type OracleKey = CurrencyId;
@@ -602,7 +608,7 @@ impl orml_oracle::Config<LaminarDataProvider> for Runtime {
 type BandDataProvider = orml_oracle::Instance2;
 impl orml_oracle::Config<BandDataProvider> for Runtime {
 	type Event = Event;
-	type OnNewData = ();
+	type OnNewData = Volatility;
 	type CombineData = orml_oracle::DefaultCombineData<Runtime, MinimumCount, ExpiresIn, BandDataProvider>;
 	type Time = Timestamp;
 	type OracleKey = CurrencyId;
@@ -688,18 +694,49 @@ impl synthetic_tokens::Config for Runtime {
 	type WeightInfo = weights::synthetic_tokens::WeightInfo<Runtime>;
 }
 
+/// Combined oracle prices, read without updating the oracle cache.
+pub struct CombinedDataProvider;
+impl DataProvider<CurrencyId, Price> for CombinedDataProvider {
+	fn get(currency: &CurrencyId) -> Option<Price> {
+		<AggregatedDataProvider as DataProviderExtended<CurrencyId, TimeStampedPrice>>::get_no_op(currency)
+			.map(|p| p.value)
+	}
+}
+
+impl volatility::Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = EnsureHalfFinancialCouncilOrRoot;
+	type PriceSource = CombinedDataProvider;
+	type WeightInfo = weights::volatility::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	pub const GetLiquidityCurrencyId: CurrencyId = CurrencyId::AUSD;
 	pub MaxSwap: FixedI128 = FixedI128::saturating_from_integer(2); // TODO: set this
+	pub const MinWindDownPeriod: BlockNumber = 3 * DAYS;
+	pub const HighLeverage: u8 = 50;
+	pub const MaxSpreadTiers: u32 = 8;
+	pub const MaxPoolConfigs: u32 = 20;
+	pub const MaxSwapRateHistory: u32 = 100;
 }
 
 type LiquidityCurrency = orml_currencies::Currency<Runtime, GetLiquidityCurrencyId>;
 
 pub type BaseLiquidityPoolsMarginInstance = base_liquidity_pools::Instance1;
+
+/// LP shares of margin liquidity pools are minted as transferable tokens.
+pub struct MarginLiquidityPoolShareCurrencyId;
+impl Convert<LiquidityPoolId, Option<CurrencyId>> for MarginLiquidityPoolShareCurrencyId {
+	fn convert(pool_id: LiquidityPoolId) -> Option<CurrencyId> {
+		Some(CurrencyId::MarginLiquidityPoolShare(pool_id))
+	}
+}
 parameter_types! {
 	pub const MarginLiquidityPoolsModuleId: ModuleId = margin_liquidity_pools::MODULE_ID;
 	pub const LiquidityPoolExistentialDeposit: Balance = 10 * DOLLARS;
 	pub const IdentityDeposit: Balance = 10_000 * DOLLARS;
+	pub const LiquidityPoolTransferExpiry: BlockNumber = 7 * DAYS;
+	pub const LiquidityPoolMaxPendingWithdrawals: u32 = 10;
 }
 
 impl base_liquidity_pools::Config<BaseLiquidityPoolsMarginInstance> for Runtime {
@@ -709,10 +746,14 @@ impl base_liquidity_pools::Config<BaseLiquidityPoolsMarginInstance> for Runtime
 	type ExistentialDeposit = LiquidityPoolExistentialDeposit;
 	type IdentityDeposit = IdentityDeposit;
 	type IdentityDepositCurrency = Balances;
+	type TransferExpiry = LiquidityPoolTransferExpiry;
+	type MaxPendingWithdrawals = LiquidityPoolMaxPendingWithdrawals;
 	type ModuleId = MarginLiquidityPoolsModuleId;
 	type OnDisableLiquidityPool = MarginLiquidityPools;
 	type OnRemoveLiquidityPool = MarginLiquidityPools;
 	type UpdateOrigin = EnsureHalfFinancialCouncilOrRoot;
+	type ShareCurrency = Currencies;
+	type ShareCurrencyId = MarginLiquidityPoolShareCurrencyId;
 	type WeightInfo = weights::base_liquidity_pools::WeightInfo<Runtime>;
 }
 
@@ -727,10 +768,14 @@ impl base_liquidity_pools::Config<BaseLiquidityPoolsSyntheticInstance> for Runti
 	type ExistentialDeposit = LiquidityPoolExistentialDeposit;
 	type IdentityDeposit = IdentityDeposit;
 	type IdentityDepositCurrency = Balances;
+	type TransferExpiry = LiquidityPoolTransferExpiry;
+	type MaxPendingWithdrawals = LiquidityPoolMaxPendingWithdrawals;
 	type ModuleId = SyntheticLiquidityPoolsModuleId;
 	type OnDisableLiquidityPool = SyntheticLiquidityPools;
 	type OnRemoveLiquidityPool = SyntheticLiquidityPools;
 	type UpdateOrigin = EnsureHalfFinancialCouncilOrRoot;
+	type ShareCurrency = Currencies;
+	type ShareCurrencyId = base_liquidity_pools::NoShareCurrency;
 	type WeightInfo = weights::base_liquidity_pools::WeightInfo<Runtime>;
 }
 
@@ -742,6 +787,12 @@ impl margin_liquidity_pools::Config for Runtime {
 	type MaxSwapRate = MaxSwap;
 	type UnixTime = Timestamp;
 	type Moment = Moment;
+	type MinWindDownPeriod = MinWindDownPeriod;
+	type HighLeverage = HighLeverage;
+	type MaxSpreadTiers = MaxSpreadTiers;
+	type MaxConfigs = MaxPoolConfigs;
+	type MaxSwapRateHistory = MaxSwapRateHistory;
+	type VolatilityProvider = Volatility;
 	type WeightInfo = weights::margin_liquidity_pools::WeightInfo<Runtime>;
 }
 
@@ -749,6 +800,8 @@ impl synthetic_liquidity_pools::Config for Runtime {
 	type Event = Event;
 	type BaseLiquidityPools = BaseLiquidityPoolsForSynthetic;
 	type UpdateOrigin = EnsureHalfFinancialCouncilOrRoot;
+	type VolatilityProvider = Volatility;
+	type MaxConfigs = MaxPoolConfigs;
 	type WeightInfo = weights::synthetic_liquidity_pools::WeightInfo<Runtime>;
 }
 
@@ -825,22 +878,72 @@ where
 parameter_types! {
 	pub const GetTraderMaxOpenPositions: usize = 200;
 	pub const GetPoolMaxOpenPositions: usize = 1000;
+	pub const MarginProtocolWindDownMaxClosePositions: u32 = 20;
 	pub TreasuryAccount: AccountId = pallet_treasury::Module::<Runtime>::account_id();
 }
 
 impl margin_protocol::Config for Runtime {
 	type Event = Event;
 	type LiquidityCurrency = LiquidityCurrency;
+	type MultiCurrency = orml_currencies::Module<Runtime>;
 	type LiquidityPools = margin_liquidity_pools::Module<Runtime>;
 	type PriceProvider = orml_traits::DefaultPriceProvider<CurrencyId, WrappedLaminarDataProvider>;
 	type GetTreasuryAccountId = TreasuryAccount;
 	type GetTraderMaxOpenPositions = GetTraderMaxOpenPositions;
 	type GetPoolMaxOpenPositions = GetPoolMaxOpenPositions;
+	type GetWindDownMaxClosePositions = MarginProtocolWindDownMaxClosePositions;
 	type UpdateOrigin = EnsureHalfFinancialCouncilOrRoot;
 	type UnsignedPriority = MarginProtocolUnsignedPriority;
 	type WeightInfo = weights::margin_protocol::WeightInfo<Runtime>;
 }
 
+fn margin_trade_simulation(result: Result<margin_protocol::TradeSimulation, DispatchError>) -> MarginTradeSimulation {
+	let (simulation, error) = match result {
+		Ok(simulation) => (simulation, None),
+		Err(e) => (Default::default(), Some(RuntimeString::Borrowed(e.into()))),
+	};
+
+	MarginTradeSimulation {
+		execution_price: simulation.execution_price,
+		margin_held: simulation.margin_held,
+		margin_level: simulation.margin_level,
+		enp: simulation.enp,
+		ell: simulation.ell,
+		error,
+	}
+}
+
+fn margin_pool_markets(pool_id: LiquidityPoolId) -> Vec<MarginPoolMarket> {
+	MarginLiquidityPools::enabled_trading_pairs_of_pool(pool_id)
+		.into_iter()
+		.map(|pair| {
+			let option = MarginLiquidityPools::pool_trading_pair_options(pool_id, pair);
+			let min_leveraged_amount =
+				MarginLiquidityPools::min_leveraged_amount(pool_id).max(option.min_leveraged_amount);
+			let (bid_spread, ask_spread) = MarginProtocol::price(pair.base, pair.quote).map_or((None, None), |price| {
+				MarginLiquidityPools::effective_spreads(pool_id, pair, price, min_leveraged_amount)
+			});
+
+			MarginPoolMarket {
+				pair,
+				bid_spread,
+				ask_spread,
+				bid_price: MarginProtocol::bid_price(pool_id, pair, min_leveraged_amount, None).ok(),
+				ask_price: MarginProtocol::ask_price(pool_id, pair, min_leveraged_amount, None).ok(),
+				enabled_leverages: MarginLiquidityPools::enabled_leverages(pool_id, pair),
+				leverage_bounds: MarginLiquidityPools::pool_options(pool_id)
+					.leverage_bounds
+					.map(|bounds| (bounds.min, bounds.max)),
+				min_leveraged_amount,
+				max_leveraged_amounts: option.max_leveraged_amounts,
+				swap_rate: MarginLiquidityPools::pool_swap_rate(pool_id, pair),
+				accumulated_swap_rate: MarginLiquidityPools::accumulated_swap_rate(pool_id, pair),
+				next_accumulate_time: MarginLiquidityPools::next_accumulate_time(pair),
+			}
+		})
+		.collect()
+}
+
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
@@ -880,10 +983,11 @@ construct_runtime!(
 		SyntheticTokens: synthetic_tokens::{Module, Storage, Call, Event, Config},
 		SyntheticProtocol: synthetic_protocol::{Module, Call, Event<T>},
 		MarginProtocol: margin_protocol::{Module, Storage, Call, Event<T>, Config, ValidateUnsigned},
-		BaseLiquidityPoolsForMargin: base_liquidity_pools::<Instance1>::{Module, Storage, Call, Event<T>},
+		BaseLiquidityPoolsForMargin: base_liquidity_pools::<Instance1>::{Module, Storage, Call, Event<T>, Config<T>},
 		MarginLiquidityPools: margin_liquidity_pools::{Module, Storage, Call, Event<T>, Config<T>},
-		BaseLiquidityPoolsForSynthetic: base_liquidity_pools::<Instance2>::{Module, Storage, Call, Event<T>},
+		BaseLiquidityPoolsForSynthetic: base_liquidity_pools::<Instance2>::{Module, Storage, Call, Event<T>, Config<T>},
 		SyntheticLiquidityPools: synthetic_liquidity_pools::{Module, Storage, Call, Event<T>, Config},
+		Volatility: volatility::{Module, Storage, Call, Event},
 	}
 );
 
@@ -1123,12 +1227,142 @@ impl_runtime_apis! {
 			}
 		}
 
+		fn trader_stats(who: AccountId, pool_id: LiquidityPoolId) -> MarginTraderStats {
+			let stats = MarginProtocol::trader_stats(&who, pool_id);
+			MarginTraderStats {
+				realized_pl: stats.realized_pl,
+				swap: stats.swap,
+				volume: stats.volume,
+				positions_opened: stats.positions_opened,
+				positions_closed: stats.positions_closed,
+				positions_stopped_out: stats.positions_stopped_out,
+			}
+		}
+
 		fn pool_state(pool_id: LiquidityPoolId) -> Option<MarginPoolState> {
 			let (enp, ell) = MarginProtocol::enp_and_ell(pool_id)?;
 			let required_deposit = MarginProtocol::pool_required_deposit(pool_id)?;
 
 			Some(MarginPoolState { enp, ell, required_deposit })
 		}
+
+		fn trader_positions(who: AccountId) -> Vec<MarginPositionState> {
+			MarginProtocol::positions_of_trader(&who)
+				.into_iter()
+				.map(|(position_id, position)| {
+					let liquidation_prices = MarginProtocol::margin_call_and_stop_out_prices_of_position(&position)
+						.ok()
+						.map(|(margin_call_price, stop_out_price)| MarginLiquidationPrices {
+							margin_call_price,
+							stop_out_price,
+						});
+					let leveraged_amount = MarginProtocol::leveraged_amount_of_position(&position);
+
+					MarginPositionState {
+						position_id,
+						pool_id: position.pool,
+						pair: position.pair,
+						is_long: position.leverage.is_long(),
+						leveraged_held: position.leveraged_held,
+						leveraged_debits: position.leveraged_debits,
+						open_price: MarginProtocol::open_price_of_position(&position),
+						bid_price: MarginProtocol::bid_price(position.pool, position.pair, leveraged_amount, None).ok(),
+						ask_price: MarginProtocol::ask_price(position.pool, position.pair, leveraged_amount, None).ok(),
+						unrealized_pl: MarginProtocol::unrealized_pl_of_position(&position).ok(),
+						accumulated_swap_rate: MarginProtocol::accumulated_swap_rate_of_position(&position).ok(),
+						margin_held: position.margin_held,
+						liquidation_prices,
+					}
+				})
+				.collect()
+		}
+
+		fn simulate_open_position(
+			who: AccountId,
+			pool_id: LiquidityPoolId,
+			pair: TradingPair,
+			leverage: PositionLeverage,
+			leveraged_amount: Balance,
+			price: Price,
+		) -> MarginTradeSimulation {
+			margin_trade_simulation(MarginProtocol::simulate_open_position(
+				&who,
+				pool_id,
+				pair,
+				leverage,
+				leveraged_amount,
+				price,
+			))
+		}
+
+		fn simulate_close_position(who: AccountId, position_id: u64, price: Price) -> MarginTradeSimulation {
+			margin_trade_simulation(MarginProtocol::simulate_close_position(&who, position_id, price))
+		}
+
+		fn pool_exposure(pool_id: LiquidityPoolId) -> Vec<MarginPairExposure> {
+			MarginProtocol::pool_exposure(pool_id)
+				.unwrap_or_default()
+				.into_iter()
+				.map(|exposure| MarginPairExposure {
+					pair: exposure.pair,
+					positions_count: exposure.positions_count,
+					net_position: exposure.net_position,
+					longest_leg: exposure.longest_leg,
+					unrealized_pl: exposure.unrealized_pl,
+					accumulated_swap_rate: exposure.accumulated_swap_rate,
+				})
+				.collect()
+		}
+
+		fn pool_pending_withdrawals(
+			pool_id: LiquidityPoolId,
+		) -> Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)> {
+			BaseLiquidityPoolsForMargin::pending_withdrawals_of_pool(pool_id)
+		}
+
+		fn pool_liquidity_lock(pool_id: LiquidityPoolId) -> Option<LiquidityLock<BlockNumber>> {
+			BaseLiquidityPoolsForMargin::active_liquidity_lock(pool_id)
+		}
+
+		fn pool_spread_tiers(pool_id: LiquidityPoolId, pair: TradingPair) -> Vec<SpreadTier> {
+			MarginLiquidityPools::pool_trading_pair_options(pool_id, pair).spread_tiers
+		}
+
+		fn pool_swap_rate(pool_id: LiquidityPoolId, pair: TradingPair) -> SwapRate {
+			MarginLiquidityPools::pool_swap_rate(pool_id, pair)
+		}
+
+		fn pool_markets(pool_id: LiquidityPoolId) -> Vec<MarginPoolMarket> {
+			margin_pool_markets(pool_id)
+		}
+
+		fn all_pools() -> Vec<MarginPoolInfo<AccountId>> {
+			[PoolStatus::Active, PoolStatus::WindingDown, PoolStatus::Disabled]
+				.iter()
+				.flat_map(|status| {
+					BaseLiquidityPoolsForMargin::pools_with_status(*status)
+						.into_iter()
+						.filter_map(move |pool_id| {
+							let owner = BaseLiquidityPoolsForMargin::pools(pool_id)?.owner;
+							Some(MarginPoolInfo {
+								pool_id,
+								owner,
+								status: *status,
+								is_margin_called: MarginProtocol::margin_called_pools(pool_id).is_some(),
+								markets: margin_pool_markets(pool_id),
+							})
+						})
+				})
+				.collect()
+		}
+
+		fn swap_rate_history(pool_id: LiquidityPoolId, pair: TradingPair) -> Vec<SwapRateAccumulation<Moment>> {
+			MarginLiquidityPools::swap_rate_history(pool_id, pair)
+		}
+
+		fn pools_of_trading_pair(pair: TradingPair) -> Vec<LiquidityPoolId> {
+			MarginLiquidityPools::pools_of_trading_pair(pair)
+		}
 	}
 
 	impl synthetic_protocol_rpc_runtime_api::SyntheticProtocolApi<Block, AccountId> for Runtime {
@@ -1138,6 +1372,16 @@ impl_runtime_apis! {
 
 			Some(SyntheticPoolState { collateral_ratio, is_safe })
 		}
+
+		fn pool_pending_withdrawals(
+			pool_id: LiquidityPoolId,
+		) -> Vec<(WithdrawalId, PendingWithdrawal<AccountId, BlockNumber>, Balance)> {
+			BaseLiquidityPoolsForSynthetic::pending_withdrawals_of_pool(pool_id)
+		}
+
+		fn pool_liquidity_lock(pool_id: LiquidityPoolId) -> Option<LiquidityLock<BlockNumber>> {
+			BaseLiquidityPoolsForSynthetic::active_liquidity_lock(pool_id)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1158,6 +1402,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, margin_protocol, benchmarking::margin_protocol);
 			add_benchmark!(params, batches, synthetic_protocol, benchmarking::synthetic_protocol);
 			add_benchmark!(params, batches, synthetic_tokens, benchmarking::synthetic_tokens);
+			add_benchmark!(params, batches, volatility, benchmarking::volatility);
 
 			if batches.is_empty() { return Err("Benchmark not found for this module.".into()) }
 			Ok(batches)
@@ -16,7 +16,7 @@ use margin_protocol::RiskThreshold;
 use margin_protocol_rpc_runtime_api::runtime_decl_for_MarginProtocolApi::MarginProtocolApi;
 use module_traits::LiquidityPools;
 use orml_traits::{BasicCurrency, MultiCurrency, PriceProvider};
-use primitives::{Balance, IdentityInfo, Leverage, Leverages, Price, SwapRate, TradingPair};
+use primitives::{Balance, IdentityInfo, Leverage, Leverages, Price, SwapRate, TradingPair, VerificationLevel};
 use sp_arithmetic::{FixedI128, FixedPointNumber};
 use sp_runtime::{DispatchResult, Permill};
 use std::ops::Range;
@@ -207,6 +207,8 @@ pub fn synthetic_verify_identity() -> DispatchResult {
 	BaseLiquidityPoolsForSynthetic::verify_identity(
 		<Runtime as frame_system::Config>::Origin::root(),
 		LIQUIDITY_POOL_ID_0,
+		VerificationLevel::KnownGood,
+		None,
 	)
 }
 
@@ -215,7 +217,8 @@ pub fn synthetic_clear_identity() -> DispatchResult {
 }
 
 pub fn synthetic_transfer_liquidity_pool(who: &AccountId, pool_id: LiquidityPoolId, to: AccountId) -> DispatchResult {
-	BaseLiquidityPoolsForSynthetic::transfer_liquidity_pool(origin_of(who), pool_id, to)
+	BaseLiquidityPoolsForSynthetic::transfer_liquidity_pool(origin_of(who), pool_id, to.clone())?;
+	BaseLiquidityPoolsForSynthetic::accept_liquidity_pool_transfer(origin_of(&to), pool_id)
 }
 
 pub fn synthetic_set_enabled_trades() -> DispatchResult {
@@ -420,7 +423,14 @@ pub fn margin_open_position(
 	amount: Balance,
 	price: Price,
 ) -> DispatchResult {
-	ModuleMarginProtocol::open_position(origin_of(who), LIQUIDITY_POOL_ID_0, pair, leverage, amount, price)
+	ModuleMarginProtocol::open_position(
+		origin_of(who),
+		LIQUIDITY_POOL_ID_0,
+		pair,
+		leverage.into(),
+		amount,
+		price,
+	)
 }
 
 pub fn margin_close_position(who: &AccountId, position_id: PositionId, price: Price) -> DispatchResult {
@@ -553,7 +563,12 @@ pub fn margin_set_identity() -> DispatchResult {
 }
 
 pub fn margin_verify_identity() -> DispatchResult {
-	BaseLiquidityPoolsForMargin::verify_identity(<Runtime as frame_system::Config>::Origin::root(), LIQUIDITY_POOL_ID_0)
+	BaseLiquidityPoolsForMargin::verify_identity(
+		<Runtime as frame_system::Config>::Origin::root(),
+		LIQUIDITY_POOL_ID_0,
+		VerificationLevel::KnownGood,
+		None,
+	)
 }
 
 pub fn margin_clear_identity() -> DispatchResult {
@@ -561,5 +576,6 @@ pub fn margin_clear_identity() -> DispatchResult {
 }
 
 pub fn margin_transfer_liquidity_pool(who: &AccountId, pool_id: LiquidityPoolId, to: AccountId) -> DispatchResult {
-	BaseLiquidityPoolsForMargin::transfer_liquidity_pool(origin_of(who), pool_id, to)
+	BaseLiquidityPoolsForMargin::transfer_liquidity_pool(origin_of(who), pool_id, to.clone())?;
+	BaseLiquidityPoolsForMargin::accept_liquidity_pool_transfer(origin_of(&to), pool_id)
 }
@@ -6,3 +6,4 @@ pub mod margin_protocol;
 pub mod synthetic_liquidity_pools;
 pub mod synthetic_protocol;
 pub mod synthetic_tokens;
+pub mod volatility;
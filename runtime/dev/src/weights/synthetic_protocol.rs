@@ -1,4 +1,4 @@
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! Weights partly estimated by hand. Regenerate with the benchmark CLI before release.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -12,17 +12,17 @@ pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> synthetic_protocol::WeightInfo for WeightInfo<T> {
 	fn mint() -> Weight {
 		(506_992_000 as Weight)
-			.saturating_add(DbWeight::get().reads(22 as Weight))
+			.saturating_add(DbWeight::get().reads(23 as Weight))
 			.saturating_add(DbWeight::get().writes(9 as Weight))
 	}
 	fn redeem() -> Weight {
 		(661_365_000 as Weight)
-			.saturating_add(DbWeight::get().reads(22 as Weight))
+			.saturating_add(DbWeight::get().reads(23 as Weight))
 			.saturating_add(DbWeight::get().writes(9 as Weight))
 	}
 	fn liquidate() -> Weight {
 		(567_526_000 as Weight)
-			.saturating_add(DbWeight::get().reads(20 as Weight))
+			.saturating_add(DbWeight::get().reads(21 as Weight))
 			.saturating_add(DbWeight::get().writes(8 as Weight))
 	}
 	fn add_collateral() -> Weight {
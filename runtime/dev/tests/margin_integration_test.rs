@@ -30,12 +30,15 @@ mod tests {
 				assert_eq!(collateral_balance(&POOL::get()), dollar(10_000));
 				assert_eq!(collateral_balance(&ALICE::get()), dollar(10_000));
 				assert_ok!(margin_deposit_liquidity(&POOL::get(), dollar(10_000)));
-				assert_ok!(margin_deposit_liquidity(&ALICE::get(), dollar(5000)));
 				assert_noop!(
-					margin_deposit_liquidity(&ALICE::get(), dollar(6_000)),
+					margin_deposit_liquidity(&ALICE::get(), dollar(5000)),
+					base_liquidity_pools::Error::<Runtime, BaseLiquidityPoolsMarginInstance>::NoPermission
+				);
+				assert_noop!(
+					margin_deposit_liquidity(&POOL::get(), dollar(1)),
 					orml_tokens::Error::<Runtime>::BalanceTooLow
 				);
-				assert_eq!(margin_liquidity(), dollar(15000));
+				assert_eq!(margin_liquidity(), dollar(10_000));
 
 				assert_noop!(
 					margin_withdraw_liquidity(&ALICE::get(), dollar(5000)),
@@ -73,11 +76,11 @@ mod tests {
 				assert_ok!(margin_liquidity_pool_enable_trading_pair(EUR_USD));
 				assert_ok!(margin_disable_trading_pair(EUR_USD));
 				assert_ok!(margin_liquidity_pool_disable_trading_pair(EUR_USD));
-				assert_ok!(margin_withdraw_liquidity(&POOL::get(), dollar(10_000)));
+				assert_ok!(margin_withdraw_liquidity(&POOL::get(), dollar(5000)));
 				assert_eq!(margin_liquidity(), dollar(5000));
 				assert_ok!(margin_disable_pool(&POOL::get()));
 				assert_ok!(margin_remove_pool(&POOL::get()));
-				assert_eq!(collateral_balance(&POOL::get()), dollar(15000));
+				assert_eq!(collateral_balance(&POOL::get()), dollar(10_000));
 			});
 	}
 
@@ -1120,15 +1123,14 @@ mod tests {
 		ExtBuilder::default()
 			.balances(vec![
 				(POOL::get(), AUSD, dollar(20_000)),
-				(ALICE::get(), AUSD, dollar(10_000)),
+				(ALICE::get(), AUSD, dollar(5000)),
 			])
 			.build()
 			.execute_with(|| {
 				assert_ok!(margin_create_pool());
 				assert_eq!(native_currency_balance(&POOL::get()), 100_000 * DOLLARS);
 
-				assert_ok!(margin_deposit_liquidity(&POOL::get(), dollar(10_000)));
-				assert_ok!(margin_deposit_liquidity(&ALICE::get(), dollar(5000)));
+				assert_ok!(margin_deposit_liquidity(&POOL::get(), dollar(15_000)));
 				assert_ok!(margin_deposit(&ALICE::get(), dollar(4000)));
 				assert_eq!(margin_liquidity(), dollar(15_000));
 
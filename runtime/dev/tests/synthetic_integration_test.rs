@@ -815,15 +815,14 @@ fn test_synthetic_transfer_liquidity_pool() {
 	ExtBuilder::default()
 		.balances(vec![
 			(POOL::get(), AUSD, dollar(20_000)),
-			(ALICE::get(), AUSD, dollar(10_000)),
+			(ALICE::get(), AUSD, dollar(5000)),
 		])
 		.build()
 		.execute_with(|| {
 			assert_ok!(synthetic_create_pool());
 			assert_eq!(native_currency_balance(&POOL::get()), 100_000 * DOLLARS);
 
-			assert_ok!(synthetic_deposit_liquidity(&POOL::get(), dollar(10_000)));
-			assert_ok!(synthetic_deposit_liquidity(&ALICE::get(), dollar(5000)));
+			assert_ok!(synthetic_deposit_liquidity(&POOL::get(), dollar(15_000)));
 			assert_eq!(synthetic_liquidity(), dollar(15_000));
 
 			// set identity
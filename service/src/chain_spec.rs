@@ -376,6 +376,8 @@ fn dev_genesis(
 				),
 			],
 		}),
+		base_liquidity_pools_Instance1: Some(Default::default()),
+		base_liquidity_pools_Instance2: Some(Default::default()),
 		margin_liquidity_pools: Some(MarginLiquidityPoolsConfig {
 			default_min_leveraged_amount: DOLLARS,
 			margin_liquidity_config: vec![
@@ -665,6 +667,8 @@ fn turbulence_genesis(
 				),
 			],
 		}),
+		base_liquidity_pools_Instance1: Some(Default::default()),
+		base_liquidity_pools_Instance2: Some(Default::default()),
 		margin_liquidity_pools: Some(MarginLiquidityPoolsConfig {
 			default_min_leveraged_amount: DOLLARS,
 			margin_liquidity_config: vec![